@@ -4,13 +4,14 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::commons::ConsensusError;
 use crate::user::cluster::Cluster;
 use crate::user::committee::Committee;
 use dusk_bytes::Serializable;
 use node_data::bls::PublicKey;
-use node_data::ledger::{to_str, StepVotes};
+use node_data::ledger::{to_str, Evidence, Signature, StepVotes};
 use node_data::message::payload::Vote;
-use node_data::message::SignInfo;
+use node_data::message::{SignInfo, StepMessage};
 use std::collections::BTreeMap;
 use std::fmt;
 use thiserror::Error;
@@ -19,10 +20,14 @@ use tracing::{debug, error};
 /// Aggregator collects votes per a block hash by aggregating signatures of
 /// voters.StepVotes Mapping of a block hash to both an aggregated signatures
 /// and a cluster of bls voters.
+///
+/// It also records the first vote cast by each voter on each step, so that a
+/// conflicting vote can be reported as an equivocation.
 #[derive(Default)]
-pub struct Aggregator(
-    BTreeMap<(u16, Vote), (AggrSignature, Cluster<PublicKey>)>,
-);
+pub struct Aggregator {
+    votes: BTreeMap<(u16, Vote), (AggrSignature, Cluster<PublicKey>)>,
+    voters: BTreeMap<(u16, PublicKey), (Vote, Signature)>,
+}
 
 #[derive(Debug, Error)]
 pub enum AggregatorError {
@@ -32,6 +37,34 @@ pub enum AggregatorError {
     NotCommitteeMember,
     #[error("Invalid signature to aggregate {0}")]
     InvalidSignature(dusk_bls12_381_sign::Error),
+    #[error("Vote conflicts with a former vote {0:?}")]
+    ConflictingVote(Vote, Signature),
+}
+
+impl AggregatorError {
+    /// Converts the failure of collecting the vote of `msg` into a
+    /// [`ConsensusError`].
+    ///
+    /// A conflicting vote is turned into an equivocation evidence, any other
+    /// failure just invalidates the vote.
+    pub(crate) fn into_consensus_error<M: StepMessage>(
+        self,
+        msg: &M,
+        vote: Vote,
+    ) -> ConsensusError {
+        match self {
+            Self::ConflictingVote(former, former_sig) => {
+                ConsensusError::Equivocation(Box::new(Evidence::DoubleVote {
+                    header: msg.header().clone(),
+                    step: M::STEP_NAME,
+                    signer: *msg.sign_info().signer.bytes(),
+                    first: (former, former_sig),
+                    second: (vote, msg.sign_info().signature),
+                }))
+            }
+            _ => ConsensusError::InvalidVote(vote),
+        }
+    }
 }
 
 impl From<dusk_bls12_381_sign::Error> for AggregatorError {
@@ -58,7 +91,21 @@ impl Aggregator {
             .votes_for(signer)
             .ok_or(AggregatorError::NotCommitteeMember)?;
 
-        let (aggr_sign, cluster) = self.0.entry((msg_step, *vote)).or_default();
+        // A committee member casting two different votes on the same step is
+        // equivocating. Report the former vote so that it can be turned into
+        // an evidence.
+        match self.voters.get(&(msg_step, signer.clone())) {
+            Some((former, former_sig)) if former != vote => {
+                return Err(AggregatorError::ConflictingVote(
+                    *former,
+                    *former_sig,
+                ));
+            }
+            _ => {}
+        }
+
+        let (aggr_sign, cluster) =
+            self.votes.entry((msg_step, *vote)).or_default();
 
         // Each committee has 64 slots.
         //
@@ -75,6 +122,9 @@ impl Aggregator {
         // Aggregate Signatures
         aggr_sign.add(signature)?;
 
+        self.voters
+            .insert((msg_step, signer.clone()), (*vote, sign_info.signature));
+
         // An committee member is allowed to vote only once per a single
         // step. Its vote has a weight value depending on how many times it
         // has been extracted in the sortition for this step.
@@ -125,7 +175,7 @@ impl Aggregator {
 
 impl fmt::Display for Aggregator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for (hash, value) in self.0.iter() {
+        for (hash, value) in self.votes.iter() {
             writeln!(
                 f,
                 "hash: {:?} total: {}",
@@ -181,7 +231,7 @@ mod tests {
 
     impl Aggregator {
         pub fn get_total(&self, step: u16, vote: Vote) -> Option<usize> {
-            if let Some(value) = self.0.get(&(step, vote)) {
+            if let Some(value) = self.votes.get(&(step, vote)) {
                 return Some(value.1.total_occurrences());
             }
             None
@@ -223,6 +273,7 @@ mod tests {
         // Also populate a vector of headers
         let mut p = Provisioners::empty();
         let mut input = vec![];
+        let mut conflicting = vec![];
         let mut mrb_header = Header::default();
         mrb_header.height = 0;
 
@@ -248,6 +299,13 @@ mod tests {
             // Message headers to be used in test for voting for hash:
             // block_hash
             input.push((msg.vote.clone(), msg));
+
+            // Messages voting against block_hash, conflicting with the above
+            conflicting.push(crate::build_validation_payload(
+                Vote::Invalid(block_hash),
+                &ru,
+                iteration,
+            ));
         }
 
         // Execute sortition with specific config
@@ -322,6 +380,34 @@ mod tests {
                     Err(AggregatorError::DuplicatedVote) => {}
                     _ => panic!("Vote should be discarded"),
                 }
+
+                // Ensure a conflicting vote is reported along with the former
+                let conflicting = &conflicting[expected_members[i]];
+                match a.collect_vote(
+                    &c,
+                    conflicting.sign_info(),
+                    &conflicting.vote,
+                    step,
+                ) {
+                    Err(AggregatorError::ConflictingVote(former, sig)) => {
+                        assert_eq!(former, vote);
+                        assert_eq!(sig, sign_info.signature);
+
+                        // Ensure the reported evidence is verifiable
+                        let err = AggregatorError::ConflictingVote(former, sig)
+                            .into_consensus_error(
+                                conflicting,
+                                conflicting.vote,
+                            );
+                        match err {
+                            ConsensusError::Equivocation(evidence) => {
+                                evidence.verify().expect("valid evidence")
+                            }
+                            _ => panic!("Equivocation should be reported"),
+                        }
+                    }
+                    _ => panic!("Vote should be reported as conflicting"),
+                }
             }
         }
     }
@@ -108,6 +108,7 @@ pub enum ConsensusError {
     MaxIterationReached,
     ChildTaskTerminated,
     Canceled,
+    Equivocation(Box<Evidence>),
 }
 
 impl From<StepSigError> for ConsensusError {
@@ -21,9 +21,9 @@ use crate::user::provisioners::Provisioners;
 use crate::{ratification, validation};
 use tracing::Instrument;
 
-use crate::iteration_ctx::IterationCtx;
+use crate::iteration_ctx::{IterationCtx, IterationState};
 use crate::step_votes_reg::CertInfoRegistry;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
 use tokio::sync::{oneshot, Mutex};
 use tokio::task::JoinHandle;
 
@@ -47,6 +47,9 @@ pub struct Consensus<T: Operations, D: Database> {
 
     // Database
     db: Arc<Mutex<D>>,
+
+    /// Snapshot of the iteration being executed, if any
+    state: Arc<StdMutex<Option<IterationState>>>,
 }
 
 impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
@@ -80,9 +83,16 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
             ),
             executor,
             db,
+            state: Default::default(),
         }
     }
 
+    /// Returns a handle to the snapshot of the iteration being executed,
+    /// which is updated at the start of each step.
+    pub fn iteration_state(&self) -> Arc<StdMutex<Option<IterationState>>> {
+        self.state.clone()
+    }
+
     /// Spins the consensus state machine. The consensus runs for the whole
     /// round until either a new round is produced or the node needs to re-sync.
     ///
@@ -141,6 +151,10 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
         abort(&mut quorum_task_handle).await;
         abort(&mut main_task_handle).await;
 
+        if let Ok(mut state) = self.state.lock() {
+            *state = None;
+        }
+
         result
     }
 
@@ -155,6 +169,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
         let future_msgs = self.future_msgs.clone();
         let executor = self.executor.clone();
         let db = self.db.clone();
+        let state = self.state.clone();
 
         tokio::spawn(async move {
             if ru.round > 0 {
@@ -220,6 +235,10 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                     // phase.
                     phase.reinitialize(msg, ru.round, iter).await;
 
+                    if let Ok(mut state) = state.lock() {
+                        *state = Some(iter_ctx.to_state(step_name));
+                    }
+
                     // Construct phase execution context
                     let ctx = ExecutionCtx::new(
                         &mut iter_ctx,
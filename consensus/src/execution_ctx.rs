@@ -16,7 +16,7 @@ use crate::user::provisioners::Provisioners;
 use crate::user::sortition;
 
 use node_data::bls::PublicKeyBytes;
-use node_data::ledger::Block;
+use node_data::ledger::{to_str, Block};
 use node_data::message::{AsyncQueue, Message, Payload};
 
 use node_data::StepName;
@@ -254,6 +254,7 @@ impl<'a, DB: Database, T: Operations + 'static> ExecutionCtx<'a, DB, T> {
                     )
                     .await
                 }
+                Payload::Evidence(_) => self.publish_evidence(m).await,
                 _ => {
                     // Not supported.
                 }
@@ -263,6 +264,29 @@ impl<'a, DB: Database, T: Operations + 'static> ExecutionCtx<'a, DB, T> {
         None
     }
 
+    /// Publishes an equivocation evidence so that it can be included in a
+    /// block by the next generators.
+    async fn publish_evidence(&self, msg: Message) {
+        // Messages from the past are not fully verified before being
+        // collected, so ensure the evidence holds before spreading it
+        if let Payload::Evidence(evidence) = &msg.payload {
+            if let Err(err) = evidence.verify() {
+                warn!(event = "invalid evidence", ?err);
+                return;
+            }
+
+            warn!(
+                event = "equivocation detected",
+                round = evidence.round(),
+                signer = to_str(evidence.signer().inner()),
+            );
+        }
+
+        self.outbound.send(msg).await.unwrap_or_else(|err| {
+            error!("unable to publish an evidence msg {:?}", err)
+        });
+    }
+
     /// Delegates the received message to the Phase handler for further
     /// processing.
     ///
@@ -318,6 +342,13 @@ impl<'a, DB: Database, T: Operations + 'static> ExecutionCtx<'a, DB, T> {
                 warn!(event = "No quorum reached", iter = msg.header.iteration);
                 return None;
             }
+            // The message failed the verification of its signature, which no
+            // honest node can produce
+            Err(e @ ConsensusError::InvalidSignature(_)) => {
+                error!("phase handler err: {:?}", e);
+                self.client.lock().await.report_invalid_msg(&msg).await;
+                return None;
+            }
             // An error here means this message is invalid due to failed
             // verification.
             Err(e) => {
@@ -344,6 +375,11 @@ impl<'a, DB: Database, T: Operations + 'static> ExecutionCtx<'a, DB, T> {
             Ok(HandleMsgOutput::Ready(m)) => Some(m),
             // Message collected but phase didn't reach a final result
             Ok(HandleMsgOutput::Pending) => None,
+            Err(ConsensusError::Equivocation(evidence)) => {
+                self.publish_evidence(Message::new_evidence(*evidence))
+                    .await;
+                None
+            }
             Err(err) => {
                 let event = "failed collect";
                 error!(event, ?err, ?msg_topic, msg_iter, msg_step, msg_round,);
@@ -5,7 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::Database;
-use crate::commons::{RoundUpdate, TimeoutSet};
+use crate::commons::{ConsensusError, RoundUpdate, TimeoutSet};
 use std::cmp;
 
 use crate::config::{MAX_STEP_TIMEOUT, TIMEOUT_INCREASE};
@@ -56,6 +56,25 @@ impl RoundCommittees {
     pub(crate) fn insert(&mut self, step: u16, committee: Committee) {
         self.committees.insert(step, committee);
     }
+
+    pub(crate) fn len(&self) -> usize {
+        self.committees.len()
+    }
+}
+
+/// A snapshot of the iteration being executed, as reported to operators.
+#[derive(Debug, Clone)]
+pub struct IterationState {
+    pub round: u64,
+    pub iteration: u8,
+    /// The step being executed
+    pub step: StepName,
+    /// The current timeout of each step
+    pub timeouts: TimeoutSet,
+    /// The generator of the iteration, if its committee is already known
+    pub generator: Option<PublicKeyBytes>,
+    /// The number of committees generated so far in the round
+    pub committees: usize,
 }
 
 /// Represents a shared state within a context of the execution of a single
@@ -142,6 +161,18 @@ impl<D: Database> IterationCtx<D> {
             .and_then(|c| c.iter().next().map(|p| *p.bytes()))
     }
 
+    /// Returns a snapshot of the iteration, while executing the given step.
+    pub(crate) fn to_state(&self, step: StepName) -> IterationState {
+        IterationState {
+            round: self.round,
+            iteration: self.iter,
+            step,
+            timeouts: self.timeouts.clone(),
+            generator: self.get_generator(self.iter),
+            committees: self.committees.len(),
+        }
+    }
+
     /// Collects a message from a past iteration
     pub(crate) async fn collect_past_event(
         &self,
@@ -149,31 +180,30 @@ impl<D: Database> IterationCtx<D> {
         msg: Message,
     ) -> Option<Message> {
         let committee = self.committees.get_committee(msg.get_step())?;
-        match msg.topic() {
+        let collected = match msg.topic() {
             node_data::message::Topics::Candidate => {
                 let mut handler = self.proposal_handler.lock().await;
-                _ = handler.collect_from_past(msg, ru, committee).await;
+                handler.collect_from_past(msg, ru, committee).await
             }
             node_data::message::Topics::Validation => {
                 let mut handler = self.validation_handler.lock().await;
-                if let Ok(HandleMsgOutput::Ready(m)) =
-                    handler.collect_from_past(msg, ru, committee).await
-                {
-                    return Some(m);
-                }
+                handler.collect_from_past(msg, ru, committee).await
             }
             node_data::message::Topics::Ratification => {
                 let mut handler = self.ratification_handler.lock().await;
-                if let Ok(HandleMsgOutput::Ready(m)) =
-                    handler.collect_from_past(msg, ru, committee).await
-                {
-                    return Some(m);
-                }
+                handler.collect_from_past(msg, ru, committee).await
             }
-            _ => {}
+            _ => return None,
         };
 
-        None
+        match collected {
+            Ok(HandleMsgOutput::Ready(m)) => Some(m),
+            // An equivocation is reported as an evidence message
+            Err(ConsensusError::Equivocation(evidence)) => {
+                Some(Message::new_evidence(*evidence))
+            }
+            _ => None,
+        }
     }
 }
 
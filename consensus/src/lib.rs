@@ -26,7 +26,10 @@ mod validation;
 pub use ratification::step::build_ratification_payload;
 pub use validation::step::build_validation_payload;
 
+pub use iteration_ctx::IterationState;
+
 mod iteration_ctx;
+pub mod metrics;
 pub mod merkle;
 
 #[cfg(test)]
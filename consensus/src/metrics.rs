@@ -0,0 +1,46 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::OnceLock;
+
+use node_data::StepName;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+
+/// Metrics of the consensus, registered in the default Prometheus registry.
+pub struct Metrics {
+    /// Votes received, by step.
+    pub votes_received: IntCounterVec,
+}
+
+/// Returns the consensus metrics, registering them on first use.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics {
+        votes_received: register_int_counter_vec!(
+            "dusk_consensus_votes_received_total",
+            "Votes received, by step",
+            &["step"]
+        )
+        .expect("Registering the metric should succeed"),
+    })
+}
+
+impl Metrics {
+    pub(crate) fn vote_received(&self, step: StepName) {
+        self.votes_received
+            .with_label_values(&[step_label(step)])
+            .inc();
+    }
+}
+
+/// The label of a step in the metrics.
+pub fn step_label(step: StepName) -> &'static str {
+    match step {
+        StepName::Proposal => "proposal",
+        StepName::Validation => "validation",
+        StepName::Ratification => "ratification",
+    }
+}
@@ -8,7 +8,10 @@ use std::fmt;
 use std::time::Duration;
 
 use dusk_bls12_381_sign::PublicKey;
-use node_data::ledger::{Block, Header, SpentTransaction, Transaction};
+use node_data::ledger::{
+    Block, Evidence, Header, SpentTransaction, Transaction,
+};
+use node_data::message::Message;
 use node_data::StepName;
 
 pub type StateRoot = [u8; 32];
@@ -26,6 +29,9 @@ pub struct CallParams {
     pub block_gas_limit: u64,
     pub generator_pubkey: node_data::bls::PublicKey,
     pub missed_generators: Vec<PublicKey>,
+    /// Provisioners to be hard-slashed for equivocating. It is filled by the
+    /// executor out of the pending evidence.
+    pub equivocators: Vec<PublicKey>,
 }
 
 #[derive(Default)]
@@ -33,6 +39,9 @@ pub struct Output {
     pub txs: Vec<SpentTransaction>,
     pub verification_output: VerificationOutput,
     pub discarded_txs: Vec<Transaction>,
+    /// Evidence to be included in the block, matching the slashed
+    /// equivocators
+    pub evidence: Vec<Evidence>,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -76,4 +85,8 @@ pub trait Operations: Send + Sync {
         step_name: StepName,
         elapsed: Duration,
     ) -> Result<(), Error>;
+
+    /// Reports a message whose signature failed verification, so that the
+    /// peer it was received from can be penalized.
+    async fn report_invalid_msg(&self, msg: &Message);
 }
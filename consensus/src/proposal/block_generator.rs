@@ -91,6 +91,7 @@ impl<T: Operations> Generator<T> {
             block_gas_limit: config::DEFAULT_BLOCK_GAS_LIMIT,
             generator_pubkey: ru.pubkey_bls.clone(),
             missed_generators,
+            equivocators: vec![],
         };
 
         let result = self
@@ -122,6 +123,7 @@ impl<T: Operations> Generator<T> {
             txroot,
             iteration,
             failed_iterations,
+            evidence: result.evidence,
         };
 
         // Apply a delay in block generator accordingly
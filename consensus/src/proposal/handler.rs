@@ -10,15 +10,24 @@ use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::user::committee::Committee;
 use async_trait::async_trait;
 use node_data::bls::PublicKeyBytes;
+use node_data::ledger::{Evidence, Header, Signature};
 use node_data::message::payload::Candidate;
 
 use crate::iteration_ctx::RoundCommittees;
 use node_data::message::{Message, Payload, StepMessage};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 pub struct ProposalHandler<D: Database> {
     pub(crate) db: Arc<Mutex<D>>,
+
+    /// The round the candidates are collected for.
+    round: u64,
+
+    /// The first candidate header received per iteration of the round, along
+    /// with the generator signature, used to detect double proposals.
+    candidates: BTreeMap<u8, (Box<Header>, Signature)>,
 }
 
 #[async_trait]
@@ -47,6 +56,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
     ) -> Result<HandleMsgOutput, ConsensusError> {
         // store candidate block
         let p = Self::unwrap_msg(&msg)?;
+        self.check_equivocation(p)?;
         self.db
             .lock()
             .await
@@ -57,10 +67,16 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
 
     async fn collect_from_past(
         &mut self,
-        _msg: Message,
+        msg: Message,
         _ru: &RoundUpdate,
-        _committee: &Committee,
+        committee: &Committee,
     ) -> Result<HandleMsgOutput, ConsensusError> {
+        let p = Self::unwrap_msg(&msg)?;
+        if !committee.is_member(&p.sign_info.signer) {
+            return Err(ConsensusError::NotCommitteeMember);
+        }
+
+        self.check_equivocation(p)?;
         Ok(HandleMsgOutput::Pending)
     }
 
@@ -72,7 +88,48 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
 
 impl<D: Database> ProposalHandler<D> {
     pub(crate) fn new(db: Arc<Mutex<D>>) -> Self {
-        Self { db }
+        Self {
+            db,
+            round: 0,
+            candidates: BTreeMap::new(),
+        }
+    }
+
+    /// Drops the candidates of a previous round.
+    pub(crate) fn reset(&mut self, round: u64) {
+        if self.round != round {
+            self.round = round;
+            self.candidates.clear();
+        }
+    }
+
+    /// Records the first candidate of an iteration and reports any other
+    /// candidate signed for the same iteration as a double proposal.
+    fn check_equivocation(
+        &mut self,
+        p: &Candidate,
+    ) -> Result<(), ConsensusError> {
+        let header = p.candidate.header();
+        let signature = p.sign_info.signature;
+
+        match self.candidates.get(&header.iteration) {
+            Some((first, first_sig)) if first.hash != header.hash => {
+                Err(ConsensusError::Equivocation(Box::new(
+                    Evidence::DoubleProposal {
+                        first: (first.clone(), *first_sig),
+                        second: (Box::new(header.clone()), signature),
+                    },
+                )))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.candidates.insert(
+                    header.iteration,
+                    (Box::new(header.clone()), signature),
+                );
+                Ok(())
+            }
+        }
     }
 
     fn verify_new_block(
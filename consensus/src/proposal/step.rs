@@ -45,6 +45,8 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
         round: u64,
         iteration: u8,
     ) {
+        self.handler.lock().await.reset(round);
+
         debug!(event = "init", name = self.name(), round, iter = iteration,)
     }
 
@@ -5,6 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::{ConsensusError, RoundUpdate};
+use crate::metrics::metrics;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::step_votes_reg::SafeCertificateInfoRegistry;
 use async_trait::async_trait;
@@ -12,7 +13,7 @@ use node_data::ledger::Certificate;
 use node_data::{ledger, StepName};
 use tracing::{error, warn};
 
-use crate::aggregator::Aggregator;
+use crate::aggregator::{Aggregator, AggregatorError};
 
 use crate::iteration_ctx::RoundCommittees;
 use crate::quorum::verifiers::verify_votes;
@@ -62,6 +63,7 @@ impl MsgHandler for RatificationHandler {
         committee: &Committee,
     ) -> Result<HandleMsgOutput, ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
+        metrics().vote_received(StepName::Ratification);
         let iteration = p.header().iteration;
 
         if iteration != self.curr_iteration {
@@ -83,7 +85,7 @@ impl MsgHandler for RatificationHandler {
                     msg_step = p.get_step(),
                     msg_round = p.header().round,
                 );
-                ConsensusError::InvalidVote(p.vote)
+                error.into_consensus_error(&p, p.vote)
             })?;
 
         // Record any signature in global registry
@@ -117,6 +119,7 @@ impl MsgHandler for RatificationHandler {
         committee: &Committee,
     ) -> Result<HandleMsgOutput, ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
+        metrics().vote_received(StepName::Ratification);
 
         // Collect vote, if msg payload is ratification type
         let collect_vote = self.aggregator.collect_vote(
@@ -151,6 +154,10 @@ impl MsgHandler for RatificationHandler {
                     msg_step = p.get_step(),
                     msg_round = p.header().round,
                 );
+
+                if let AggregatorError::ConflictingVote(..) = error {
+                    return Err(error.into_consensus_error(&p, p.vote));
+                }
             }
         };
 
@@ -98,6 +98,11 @@ impl Provisioners {
         self.members.remove(pubkey_bls)
     }
 
+    /// Returns the stake of a provisioner, if it is a member.
+    pub fn get_member(&self, pubkey_bls: &PublicKey) -> Option<&Stake> {
+        self.members.get(pubkey_bls)
+    }
+
     /// Adds a new member with reward=0 and elibile_since=0.
     ///
     /// Useful for implementing unit tests.
@@ -105,6 +110,11 @@ impl Provisioners {
         self.add_member_with_stake(pubkey_bls, Stake::from_value(value));
     }
 
+    /// Returns all the members, along with their stake.
+    pub fn iter(&self) -> impl Iterator<Item = (&PublicKey, &Stake)> {
+        self.members.iter()
+    }
+
     // Returns a pair of count of all provisioners and count of eligible
     // provisioners for the specified round.
     pub fn get_provisioners_info(&self, round: u64) -> (usize, usize) {
@@ -4,8 +4,9 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::aggregator::Aggregator;
+use crate::aggregator::{Aggregator, AggregatorError};
 use crate::commons::{ConsensusError, RoundUpdate};
+use crate::metrics::metrics;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::step_votes_reg::SafeCertificateInfoRegistry;
 use async_trait::async_trait;
@@ -86,6 +87,7 @@ impl MsgHandler for ValidationHandler {
         committee: &Committee,
     ) -> Result<HandleMsgOutput, ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
+        metrics().vote_received(StepName::Validation);
 
         // NoQuorum cannot be cast from validation committee
         if p.vote == Vote::NoQuorum {
@@ -111,7 +113,7 @@ impl MsgHandler for ValidationHandler {
                     msg_step = p.get_step(),
                     msg_round = p.header().round,
                 );
-                ConsensusError::InvalidVote(p.vote)
+                error.into_consensus_error(&p, p.vote)
             })?;
         // Record result in global round registry
         _ = self.sv_registry.lock().await.add_step_votes(
@@ -149,6 +151,7 @@ impl MsgHandler for ValidationHandler {
         committee: &Committee,
     ) -> Result<HandleMsgOutput, ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
+        metrics().vote_received(StepName::Validation);
 
         // NoQuorum cannot be cast from validation committee
         if p.vote == Vote::NoQuorum {
@@ -187,6 +190,10 @@ impl MsgHandler for ValidationHandler {
                     msg_step = p.get_step(),
                     msg_round = p.header().round,
                 );
+
+                if let AggregatorError::ConflictingVote(..) = error {
+                    return Err(error.into_consensus_error(&p, p.vote));
+                }
             }
         }
         Ok(HandleMsgOutput::Pending)
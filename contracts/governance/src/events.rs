@@ -0,0 +1,33 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The events emitted by the contract, together with the topics they are
+//! emitted with.
+
+use dusk_pki::PublicKey;
+
+/// Topic of the events emitted on every balance change.
+pub const TRANSFER_TOPIC: &str = "transfer";
+/// Topic of the events emitted when an allowance is set.
+pub const APPROVAL_TOPIC: &str = "approval";
+/// Topic of the events emitted when the contract is paused or unpaused.
+pub const PAUSE_TOPIC: &str = "pause";
+
+/// A mint has no `from`, and a burn has no `to`.
+pub type TransferEvent = (
+    Option<PublicKey>, // from
+    Option<PublicKey>, // to
+    u64,               // amount
+);
+
+pub type ApprovalEvent = (
+    PublicKey, // owner
+    PublicKey, // spender
+    u64,       // amount
+);
+
+/// Whether the contract is paused after the change.
+pub type PauseEvent = bool;
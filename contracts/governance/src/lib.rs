@@ -10,9 +10,12 @@
 
 extern crate alloc;
 
+mod events;
 mod msg;
 mod state;
 
+use dusk_pki::PublicKey;
+use dusk_schnorr::Signature;
 use msg::*;
 use rusk_abi::ContractId;
 use state::GovernanceState;
@@ -25,58 +28,93 @@ static mut STATE: GovernanceState = GovernanceState::new();
 
 #[no_mangle]
 unsafe fn transfer(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed, batch)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed, batch)| {
         let msg = transfer_msg(seed, &batch);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.transfer(batch)
     })
 }
 
 #[no_mangle]
 unsafe fn fee(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed, batch)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed, batch)| {
         let msg = fee_msg(seed, &batch);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.fee(batch)
     })
 }
 
 #[no_mangle]
 unsafe fn mint(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed, address, amount)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed, address, amount)| {
         let msg = mint_msg(seed, address, amount);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.mint(address, amount)
     })
 }
 
 #[no_mangle]
 unsafe fn burn(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed, address, amount)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed, address, amount)| {
         let msg = burn_msg(seed, address, amount);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.burn(address, amount)
     })
 }
 
 #[no_mangle]
 unsafe fn pause(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed)| {
         let msg = pause_msg(seed);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.pause()
     })
 }
 
 #[no_mangle]
 unsafe fn unpause(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |(signature, seed)| {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed)| {
         let msg = unpause_msg(seed);
-        STATE.assert_signature(signature, seed, msg);
+        STATE.assert_approvals(approvals, seed, msg);
         STATE.unpause()
     })
 }
 
+#[no_mangle]
+unsafe fn approve(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(
+        arg_len,
+        |(approvals, seed, owner, spender, amount, signature)| {
+            let msg = approve_msg(seed, owner, spender, amount);
+            assert_holder_signature(owner, &msg, signature);
+            STATE.assert_approvals(approvals, seed, msg);
+            STATE.approve(owner, spender, amount)
+        },
+    )
+}
+
+#[no_mangle]
+unsafe fn transfer_from(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(
+        arg_len,
+        |(approvals, seed, spender, from, to, amount, signature)| {
+            let msg = transfer_from_msg(seed, spender, from, to, amount);
+            assert_holder_signature(spender, &msg, signature);
+            STATE.assert_approvals(approvals, seed, msg);
+            STATE.transfer_from(spender, from, to, amount)
+        },
+    )
+}
+
+#[no_mangle]
+unsafe fn rotate_authority(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(approvals, seed, authority)| {
+        let msg = rotate_authority_msg(seed, &authority);
+        STATE.assert_approvals(approvals, seed, msg);
+        STATE.set_authority(authority)
+    })
+}
+
 // Queries
 
 #[no_mangle]
@@ -84,6 +122,18 @@ unsafe fn balance(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |address| STATE.balance(&address))
 }
 
+#[no_mangle]
+unsafe fn allowance(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(owner, spender)| {
+        STATE.allowance(&owner, &spender)
+    })
+}
+
+#[no_mangle]
+unsafe fn is_paused(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.is_paused())
+}
+
 #[no_mangle]
 unsafe fn total_supply(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |_: ()| STATE.total_supply())
@@ -117,6 +167,18 @@ unsafe fn set_broker(arg_len: u32) -> u32 {
     })
 }
 
+/// Asserts the `msg` is signed by the holder of the given `address`, so that
+/// the authority can't act on its behalf.
+///
+/// # Panics
+/// When the signature is not valid.
+fn assert_holder_signature(address: PublicKey, msg: &[u8], sig: Signature) {
+    let msg = rusk_abi::hash(msg.to_vec());
+    if !rusk_abi::verify_schnorr(msg, address, sig) {
+        panic!("Invalid holder signature");
+    }
+}
+
 /// Asserts the call is made "from the outside", meaning that it's not an
 /// inter-contract call.
 ///
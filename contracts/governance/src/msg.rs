@@ -10,6 +10,7 @@
 use alloc::vec::Vec;
 
 use dusk_bls12_381::BlsScalar;
+use dusk_bls12_381_sign::{PublicKey as BlsPublicKey, Signature};
 use dusk_pki::PublicKey;
 
 const SCRATCH_SIZE: usize = 128;
@@ -21,6 +22,16 @@ pub type Transfer = (
     u64,               // timestamp
 );
 
+pub type Authority = (
+    Vec<BlsPublicKey>, // keys
+    u32,               // threshold
+);
+
+pub type Approvals = (
+    u64,            // bitset of the signing authority keys
+    Vec<Signature>, // signatures, in the order of the keys
+);
+
 pub fn transfer_msg(seed: BlsScalar, batch: &Vec<Transfer>) -> Vec<u8> {
     rkyv::to_bytes::<_, SCRATCH_SIZE>(&(0u8, seed, batch.clone()))
         .expect("Serializing should be infallible")
@@ -56,3 +67,32 @@ pub fn unpause_msg(seed: BlsScalar) -> Vec<u8> {
         .expect("Serializing should be infallible")
         .to_vec()
 }
+
+pub fn rotate_authority_msg(seed: BlsScalar, authority: &Authority) -> Vec<u8> {
+    rkyv::to_bytes::<_, SCRATCH_SIZE>(&(2u8, seed, authority.clone()))
+        .expect("Serializing should be infallible")
+        .to_vec()
+}
+
+pub fn approve_msg(
+    seed: BlsScalar,
+    owner: PublicKey,
+    spender: PublicKey,
+    amount: u64,
+) -> Vec<u8> {
+    rkyv::to_bytes::<_, SCRATCH_SIZE>(&(3u8, seed, owner, spender, amount))
+        .expect("Serializing should be infallible")
+        .to_vec()
+}
+
+pub fn transfer_from_msg(
+    seed: BlsScalar,
+    spender: PublicKey,
+    from: PublicKey,
+    to: PublicKey,
+    amount: u64,
+) -> Vec<u8> {
+    rkyv::to_bytes::<_, SCRATCH_SIZE>(&(4u8, seed, spender, from, to, amount))
+        .expect("Serializing should be infallible")
+        .to_vec()
+}
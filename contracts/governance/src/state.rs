@@ -11,34 +11,43 @@ use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use dusk_bls12_381::BlsScalar;
-use dusk_bls12_381_sign::{PublicKey as BlsPublicKey, Signature};
+use dusk_bls12_381_sign::PublicKey as BlsPublicKey;
 use dusk_pki::PublicKey;
 
-use crate::Transfer;
+use crate::events::*;
+use crate::{Approvals, Authority, Transfer};
 use contract_helpers::{Map, Set};
 
 #[derive(Debug)]
 pub struct GovernanceState {
     balances: Map<PublicKey, u64>,
+    allowances: Map<(PublicKey, PublicKey), u64>,
     seeds: Set<BlsScalar>,
 
     total_supply: u64,
     paused: bool,
 
     broker: AtomicPtr<PublicKey>,
-    authority: AtomicPtr<BlsPublicKey>,
+    authority: Vec<BlsPublicKey>,
+    threshold: u32,
 }
 
+/// Maximum number of keys in the authority, bound by the size of the bitset
+/// used in [`Approvals`].
+const MAX_AUTHORITY_KEYS: usize = u64::BITS as usize;
+
 impl GovernanceState {
     /// Create a new instance of the governance state.
     pub const fn new() -> Self {
         Self {
             balances: Map::new(),
+            allowances: Map::new(),
             seeds: Set::new(),
             total_supply: 0,
             paused: false,
             broker: AtomicPtr::new(ptr::null_mut()),
-            authority: AtomicPtr::new(ptr::null_mut()),
+            authority: Vec::new(),
+            threshold: 0,
         }
     }
 
@@ -108,6 +117,8 @@ impl GovernanceState {
         self.total_supply = new_supply.unwrap();
 
         self.add_balance(address, amount);
+
+        emit_transfer(None, Some(address), amount);
     }
 
     /// Burns a given `amount` of tokens from the given `address`.
@@ -117,19 +128,75 @@ impl GovernanceState {
         }
 
         let remaining = self.sub_balance(address, amount);
+        let burned = amount - remaining;
+
+        self.total_supply = self.total_supply.saturating_sub(burned);
+
+        emit_transfer(Some(address), None, burned);
+    }
+
+    /// Allows the `spender` to transfer up to `amount` tokens from the
+    /// `owner`, replacing any previous allowance.
+    pub fn approve(
+        &mut self,
+        owner: PublicKey,
+        spender: PublicKey,
+        amount: u64,
+    ) {
+        if amount == 0 {
+            self.allowances.remove(&(owner, spender));
+        } else {
+            self.allowances.insert((owner, spender), amount);
+        }
 
-        self.total_supply =
-            self.total_supply.saturating_sub(amount - remaining);
+        let event: ApprovalEvent = (owner, spender, amount);
+        rusk_abi::emit(APPROVAL_TOPIC, event);
+    }
+
+    /// Transfers `amount` tokens `from` an address `to` another, on behalf of
+    /// the `spender`, deducting them from its allowance.
+    ///
+    /// # Panics
+    /// When the contract is paused, or the allowance or the balance of `from`
+    /// are not enough to cover the `amount`.
+    pub fn transfer_from(
+        &mut self,
+        spender: PublicKey,
+        from: PublicKey,
+        to: PublicKey,
+        amount: u64,
+    ) {
+        if self.paused {
+            panic!("The contract is paused");
+        }
+
+        let allowance = self.allowance(&from, &spender);
+        if allowance < amount {
+            panic!("Insufficient allowance");
+        }
+        if self.balance(&from) < amount {
+            panic!("Insufficient balance");
+        }
+
+        self.allowances.insert((from, spender), allowance - amount);
+        self.checked_transfer(from, to, amount);
     }
 
     /// Pause the governance contract.
     pub fn pause(&mut self) {
         self.paused = true;
+        rusk_abi::emit::<PauseEvent>(PAUSE_TOPIC, true);
     }
 
     /// Unpause the governance contract.
     pub fn unpause(&mut self) {
         self.paused = false;
+        rusk_abi::emit::<PauseEvent>(PAUSE_TOPIC, false);
+    }
+
+    /// Whether the contract is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
     }
 
     /// Set the broker in the contract.
@@ -149,43 +216,84 @@ impl GovernanceState {
         }
     }
 
-    /// Set the authority in the contract.
+    /// Set the authority in the contract, i.e. the set of keys allowed to
+    /// approve operations and the number of approvals required.
     ///
-    /// This function should only be called once, but it does support being
-    /// called multiple times.
-    pub fn set_authority(&self, authority: BlsPublicKey) {
-        let authority = Box::leak(Box::new(authority));
-        let last_authority = self
-            .authority
-            .swap(authority as *mut BlsPublicKey, Ordering::SeqCst);
+    /// # Panics
+    /// When there are no keys, more than 64 keys or duplicate keys, or when the
+    /// threshold is zero or greater than the number of keys.
+    pub fn set_authority(&mut self, authority: Authority) {
+        let (keys, threshold) = authority;
 
-        // If the authority was already set we need to recoup the memory,
-        // otherwise we will leak memory.
-        if last_authority != ptr::null_mut() {
-            let _ = unsafe { Box::from_raw(last_authority) };
+        if keys.is_empty() || keys.len() > MAX_AUTHORITY_KEYS {
+            panic!("Invalid number of authority keys");
         }
+        if threshold == 0 || threshold as usize > keys.len() {
+            panic!("Invalid authority threshold");
+        }
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].contains(key) {
+                panic!("Duplicate authority key");
+            }
+        }
+
+        self.authority = keys;
+        self.threshold = threshold;
     }
 
-    /// Asserts that the signature and the given seed are valid. The seed
+    /// Asserts that the approvals and the given seed are valid. The seed
     /// shouldn't have been used before.
     ///
+    /// The approvals consist of a bitset marking which of the authority keys
+    /// signed the message, and one signature per set bit, in the order of the
+    /// keys. At least `threshold` valid signatures are required, and they are
+    /// aggregated to be verified all at once.
+    ///
     /// # Panics
-    /// When the signature isn't valid, the seed has already been used, or the
-    /// authority hasn't been set.
-    pub fn assert_signature(
+    /// When any signature isn't valid, there are not enough of them, the seed
+    /// has already been used, or the authority hasn't been set.
+    pub fn assert_approvals(
         &mut self,
-        signature: Signature,
+        approvals: Approvals,
         seed: BlsScalar,
         message: Vec<u8>,
     ) {
-        let authority = self.get_authority();
+        if self.authority.is_empty() {
+            panic!("Authority not set");
+        }
 
         if self.seeds.contains(&seed) {
             panic!("Seed already used");
         }
         self.seeds.insert(seed);
 
-        if !rusk_abi::verify_bls(message, authority, signature) {
+        let (signers, signatures) = approvals;
+
+        let n_keys = self.authority.len();
+        if n_keys < MAX_AUTHORITY_KEYS && signers >> n_keys != 0 {
+            panic!("Unknown signer");
+        }
+        if signers.count_ones() as usize != signatures.len() {
+            panic!("Signers and signatures mismatch");
+        }
+        if signers.count_ones() < self.threshold {
+            panic!("Not enough approvals");
+        }
+
+        let keys = self
+            .authority
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| signers & (1 << i) != 0)
+            .map(|(_, key)| *key)
+            .collect();
+
+        let signature = match signatures.split_first() {
+            Some((first, rest)) => first.aggregate(rest),
+            None => panic!("Not enough approvals"),
+        };
+
+        if !rusk_abi::verify_bls_multisig(message, keys, signature) {
             panic!("Invalid signature");
         }
     }
@@ -194,12 +302,11 @@ impl GovernanceState {
     ///
     /// # Panics
     /// If the authority hasn't been set.
-    pub fn get_authority(&self) -> BlsPublicKey {
-        let authority = self.authority.load(Ordering::SeqCst);
-        if authority.is_null() {
+    pub fn get_authority(&self) -> Authority {
+        if self.authority.is_empty() {
             panic!("Authority not set");
         }
-        unsafe { *authority }
+        (self.authority.clone(), self.threshold)
     }
 
     /// Get the current broker.
@@ -264,6 +371,15 @@ impl GovernanceState {
         self.total_supply
     }
 
+    /// The amount of tokens the `spender` is allowed to transfer from the
+    /// `owner`.
+    pub fn allowance(&self, owner: &PublicKey, spender: &PublicKey) -> u64 {
+        self.allowances
+            .get(&(*owner, *spender))
+            .copied()
+            .unwrap_or_default()
+    }
+
     fn checked_transfer(
         &mut self,
         from: PublicKey,
@@ -276,6 +392,17 @@ impl GovernanceState {
             self.mint(to, remaining);
         }
 
-        self.add_balance(to, amount - remaining);
+        let transferred = amount - remaining;
+        self.add_balance(to, transferred);
+
+        emit_transfer(Some(from), Some(to), transferred);
+    }
+}
+
+/// Emits the event of a balance change, skipping the ones with no value.
+fn emit_transfer(from: Option<PublicKey>, to: Option<PublicKey>, amount: u64) {
+    if amount > 0 {
+        let event: TransferEvent = (from, to, amount);
+        rusk_abi::emit(TRANSFER_TOPIC, event);
     }
 }
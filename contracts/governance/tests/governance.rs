@@ -11,15 +11,25 @@ extern crate alloc;
 mod msg;
 use msg::*;
 
+#[allow(unused)]
+#[path = "../src/events.rs"]
+mod events;
+use events::*;
+
+use bytecheck::CheckBytes;
 use dusk_bls12_381::BlsScalar;
 use dusk_bls12_381_sign::{
     PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
 };
 use dusk_pki::{PublicKey, SecretKey};
+use dusk_schnorr::Signature;
 use ff::Field;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-use rusk_abi::{ContractData, ContractId, Session, VM};
+use rkyv::de::deserializers::SharedDeserializeMap;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{Archive, Deserialize};
+use rusk_abi::{ContractData, ContractId, Event, Session, VM};
 
 const GOVERNANCE_ID: ContractId = {
     let mut bytes = [0u8; 32];
@@ -33,11 +43,7 @@ const OWNER: [u8; 32] = [0; 32];
 
 /// Instantiate the virtual machine with the transfer contract deployed, with a
 /// single note owned by the given public spend key.
-fn instantiate(
-    vm: &VM,
-    authority: &BlsPublicKey,
-    broker: &PublicKey,
-) -> Session {
+fn instantiate(vm: &VM, authority: &Authority, broker: &PublicKey) -> Session {
     let governance_bytecode = include_bytes!(
         "../../../target/wasm32-unknown-unknown/release/governance_contract.wasm"
     );
@@ -83,6 +89,83 @@ fn balance(session: &mut Session, pk: &PublicKey) -> u64 {
         .expect("Querying the total supply should succeed")
 }
 
+fn allowance(
+    session: &mut Session,
+    owner: &PublicKey,
+    spender: &PublicKey,
+) -> u64 {
+    session
+        .call(GOVERNANCE_ID, "allowance", &(*owner, *spender), POINT_LIMIT)
+        .map(|r| r.data)
+        .expect("Querying the allowance should succeed")
+}
+
+fn is_paused(session: &mut Session) -> bool {
+    session
+        .call(GOVERNANCE_ID, "is_paused", &(), POINT_LIMIT)
+        .map(|r| r.data)
+        .expect("Querying the pause state should succeed")
+}
+
+/// Deserialize the data of the events with the given `topic`.
+fn events<T>(events: &[Event], topic: &str) -> Vec<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, SharedDeserializeMap>
+        + for<'a> CheckBytes<DefaultValidator<'a>>,
+{
+    events
+        .iter()
+        .filter(|e| e.source == GOVERNANCE_ID && e.topic == topic)
+        .map(|e| rkyv::from_bytes(&e.data).expect("Event should deserialize"))
+        .collect()
+}
+
+/// Query the authority of the governance contract.
+fn authority(session: &mut Session) -> Authority {
+    session
+        .call(GOVERNANCE_ID, "authority", &(), POINT_LIMIT)
+        .map(|r| r.data)
+        .expect("Querying the authority should succeed")
+}
+
+/// Sign the given message with the authority keys at the `signers` indexes.
+fn approve(
+    keys: &[(BlsSecretKey, BlsPublicKey)],
+    signers: &[usize],
+    msg: &[u8],
+) -> Approvals {
+    let mut bitset = 0;
+    let mut signatures = vec![];
+
+    for &i in signers {
+        let (sk, pk) = &keys[i];
+        bitset |= 1 << i;
+        signatures.push(sk.sign(pk, msg));
+    }
+
+    (bitset, signatures)
+}
+
+/// Signs a message as the holder of an address.
+fn sign(rng: &mut StdRng, sk: &SecretKey, msg: &[u8]) -> Signature {
+    Signature::new(sk, rng, rusk_abi::hash(msg.to_vec()))
+}
+
+/// Generate `n` random authority keys.
+fn authority_keys(
+    rng: &mut StdRng,
+    n: usize,
+) -> Vec<(BlsSecretKey, BlsPublicKey)> {
+    (0..n)
+        .map(|_| {
+            let sk = BlsSecretKey::random(rng);
+            let pk = BlsPublicKey::from(&sk);
+            (sk, pk)
+        })
+        .collect()
+}
+
 #[test]
 fn balance_overflow() {
     let rng = &mut StdRng::seed_from_u64(0xbeef);
@@ -97,7 +180,7 @@ fn balance_overflow() {
     let alice = PublicKey::from(&SecretKey::random(rng));
     let bob = PublicKey::from(&SecretKey::random(rng));
 
-    let session = &mut instantiate(vm, &authority, &broker);
+    let session = &mut instantiate(vm, &(vec![authority], 1), &broker);
 
     assert_eq!(total_supply(session), 0);
     assert_eq!(balance(session, &alice), 0);
@@ -106,13 +189,13 @@ fn balance_overflow() {
     // Make a "mint" call
     let seed = BlsScalar::random(&mut *rng);
     let msg = mint_msg(seed, alice, u64::MAX);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "mint",
-            &(signature, seed, alice, u64::MAX),
+            &(approvals, seed, alice, u64::MAX),
             POINT_LIMIT,
         )
         .expect("Minting should succeed");
@@ -127,13 +210,13 @@ fn balance_overflow() {
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = transfer_msg(seed, &batch);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "transfer",
-            &(signature, seed, batch),
+            &(approvals, seed, batch),
             POINT_LIMIT,
         )
         .expect_err("The transaction should fail due to overflow");
@@ -154,24 +237,24 @@ fn same_seed() {
 
     let broker = PublicKey::from(&SecretKey::random(rng));
 
-    let session = &mut instantiate(vm, &authority, &broker);
+    let session = &mut instantiate(vm, &(vec![authority], 1), &broker);
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = pause_msg(seed);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
-        .call::<_, ()>(GOVERNANCE_ID, "pause", &(signature, seed), POINT_LIMIT)
+        .call::<_, ()>(GOVERNANCE_ID, "pause", &(approvals, seed), POINT_LIMIT)
         .expect("Pausing the contract should succeed");
 
     let msg = unpause_msg(seed);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "unpause",
-            &(signature, seed),
+            &(approvals, seed),
             POINT_LIMIT,
         )
         .expect_err("Unpausing the contract with the same seed error");
@@ -188,14 +271,15 @@ fn wrong_signature() {
 
     let broker = PublicKey::from(&SecretKey::random(rng));
 
-    let session = &mut instantiate(vm, &authority, &broker);
+    let session = &mut instantiate(vm, &(vec![authority], 1), &broker);
 
     let seed = BlsScalar::random(&mut *rng);
     let wrong_message = vec![1, 0, 1, 0, 1, 0];
     let wrong_sig = authority_sk.sign(&authority, &wrong_message);
+    let approvals: Approvals = (1, vec![wrong_sig]);
 
     session
-        .call::<_, ()>(GOVERNANCE_ID, "pause", &(wrong_sig, seed), POINT_LIMIT)
+        .call::<_, ()>(GOVERNANCE_ID, "pause", &(approvals, seed), POINT_LIMIT)
         .expect_err("Pausing the contract with a wrong signature should error");
 }
 
@@ -213,7 +297,7 @@ fn mint_burn_transfer() {
     let alice = PublicKey::from(&SecretKey::random(rng));
     let bob = PublicKey::from(&SecretKey::random(rng));
 
-    let session = &mut instantiate(vm, &authority, &broker);
+    let session = &mut instantiate(vm, &(vec![authority], 1), &broker);
 
     assert_eq!(total_supply(session), 0);
     assert_eq!(balance(session, &alice), 0);
@@ -222,13 +306,13 @@ fn mint_burn_transfer() {
     // Make a "mint" call
     let seed = BlsScalar::random(&mut *rng);
     let msg = mint_msg(seed, alice, 100);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "mint",
-            &(signature, seed, alice, 100),
+            &(approvals, seed, alice, 100),
             POINT_LIMIT,
         )
         .expect("Minting should succeed");
@@ -243,13 +327,13 @@ fn mint_burn_transfer() {
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = transfer_msg(seed, &batch);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "transfer",
-            &(signature, seed, batch),
+            &(approvals, seed, batch),
             POINT_LIMIT,
         )
         .expect("The transaction should succeed");
@@ -264,13 +348,13 @@ fn mint_burn_transfer() {
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = transfer_msg(seed, &batch);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "transfer",
-            &(signature, seed, batch),
+            &(approvals, seed, batch),
             POINT_LIMIT,
         )
         .expect("The transaction should succeed");
@@ -294,7 +378,7 @@ fn fee() {
     let alice = PublicKey::from(&SecretKey::random(rng));
     let bob = PublicKey::from(&SecretKey::random(rng));
 
-    let session = &mut instantiate(vm, &authority, &broker);
+    let session = &mut instantiate(vm, &(vec![authority], 1), &broker);
 
     assert_eq!(total_supply(session), 0);
     assert_eq!(balance(session, &alice), 0);
@@ -309,13 +393,13 @@ fn fee() {
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = fee_msg(seed, &batch);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "fee",
-            &(signature, seed, batch),
+            &(approvals, seed, batch),
             POINT_LIMIT,
         )
         .expect("The fee payment should succeed");
@@ -335,13 +419,13 @@ fn fee() {
 
     let seed = BlsScalar::random(&mut *rng);
     let msg = transfer_msg(seed, &batch);
-    let signature = authority_sk.sign(&authority, &msg);
+    let approvals: Approvals = (1, vec![authority_sk.sign(&authority, &msg)]);
 
     session
         .call::<_, ()>(
             GOVERNANCE_ID,
             "transfer",
-            &(signature, seed, batch),
+            &(approvals, seed, batch),
             POINT_LIMIT,
         )
         .expect("The batch processing should succeed");
@@ -351,3 +435,480 @@ fn fee() {
     assert_eq!(balance(session, &bob), 10);
     assert_eq!(balance(session, &broker), 250);
 }
+
+#[test]
+fn threshold_approvals() {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let vm = &mut rusk_abi::new_ephemeral_vm()
+        .expect("Creating ephemeral VM should work");
+
+    let keys = authority_keys(rng, 3);
+    let authority = (keys.iter().map(|(_, pk)| *pk).collect(), 2);
+
+    let broker = PublicKey::from(&SecretKey::random(rng));
+    let alice = PublicKey::from(&SecretKey::random(rng));
+
+    let session = &mut instantiate(vm, &authority, &broker);
+
+    // A single approval is not enough
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = mint_msg(seed, alice, 100);
+    let approvals = approve(&keys, &[1], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "mint",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect_err("Minting with a single approval should error");
+
+    // Signatures must match the signers in the bitset
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = mint_msg(seed, alice, 100);
+    let (_, signatures) = approve(&keys, &[0, 2], &msg);
+    let approvals: Approvals = (0b011, signatures);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "mint",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect_err("Minting with mismatching signers should error");
+
+    // Signers outside of the authority are rejected
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = mint_msg(seed, alice, 100);
+    let (_, signatures) = approve(&keys, &[0, 2], &msg);
+    let approvals: Approvals = (0b1001, signatures);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "mint",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect_err("Minting with an unknown signer should error");
+
+    assert_eq!(total_supply(session), 0);
+
+    // Two out of three approvals are enough
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = mint_msg(seed, alice, 100);
+    let approvals = approve(&keys, &[0, 2], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "mint",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect("Minting with enough approvals should succeed");
+
+    assert_eq!(total_supply(session), 100);
+    assert_eq!(balance(session, &alice), 100);
+}
+
+#[test]
+fn rotate_authority() {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let vm = &mut rusk_abi::new_ephemeral_vm()
+        .expect("Creating ephemeral VM should work");
+
+    let keys = authority_keys(rng, 3);
+    let authority = (keys.iter().map(|(_, pk)| *pk).collect(), 2);
+
+    let broker = PublicKey::from(&SecretKey::random(rng));
+
+    let session = &mut instantiate(vm, &authority, &broker);
+    assert_eq!(self::authority(session), authority);
+
+    let new_keys = authority_keys(rng, 2);
+    let new_authority: Authority =
+        (new_keys.iter().map(|(_, pk)| *pk).collect(), 1);
+
+    // The new authority can't approve its own rotation
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = rotate_authority_msg(seed, &new_authority);
+    let approvals = approve(&new_keys, &[0], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "rotate_authority",
+            &(approvals, seed, new_authority.clone()),
+            POINT_LIMIT,
+        )
+        .expect_err("Rotating without the current authority should error");
+
+    // An invalid threshold is rejected
+    let invalid_authority: Authority = (new_authority.0.clone(), 3);
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = rotate_authority_msg(seed, &invalid_authority);
+    let approvals = approve(&keys, &[0, 1], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "rotate_authority",
+            &(approvals, seed, invalid_authority),
+            POINT_LIMIT,
+        )
+        .expect_err("Rotating to an invalid threshold should error");
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = rotate_authority_msg(seed, &new_authority);
+    let approvals = approve(&keys, &[0, 1], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "rotate_authority",
+            &(approvals, seed, new_authority.clone()),
+            POINT_LIMIT,
+        )
+        .expect("Rotating the authority should succeed");
+
+    assert_eq!(self::authority(session), new_authority);
+
+    // The old authority can't approve anymore
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = pause_msg(seed);
+    let approvals = approve(&keys, &[0, 1], &msg);
+
+    session
+        .call::<_, ()>(GOVERNANCE_ID, "pause", &(approvals, seed), POINT_LIMIT)
+        .expect_err("Pausing with the old authority should error");
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = pause_msg(seed);
+    let approvals = approve(&new_keys, &[1], &msg);
+
+    session
+        .call::<_, ()>(GOVERNANCE_ID, "pause", &(approvals, seed), POINT_LIMIT)
+        .expect("Pausing with the new authority should succeed");
+}
+
+#[test]
+fn approve_transfer_from() {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let vm = &mut rusk_abi::new_ephemeral_vm()
+        .expect("Creating ephemeral VM should work");
+
+    let keys = authority_keys(rng, 1);
+    let authority = (vec![keys[0].1], 1);
+
+    let broker = PublicKey::from(&SecretKey::random(rng));
+
+    let alice_sk = SecretKey::random(rng);
+    let alice = PublicKey::from(&alice_sk);
+    let bob_sk = SecretKey::random(rng);
+    let bob = PublicKey::from(&bob_sk);
+    let charlie = PublicKey::from(&SecretKey::random(rng));
+
+    let session = &mut instantiate(vm, &authority, &broker);
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = mint_msg(seed, alice, 100);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "mint",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect("Minting should succeed");
+
+    assert_eq!(
+        events::<TransferEvent>(&receipt.events, TRANSFER_TOPIC),
+        vec![(None, Some(alice), 100)]
+    );
+
+    // Allow bob to spend 60 of alice's tokens
+    assert_eq!(allowance(session, &alice, &bob), 0);
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = approve_msg(seed, alice, bob, 60);
+    let approvals = approve(&keys, &[0], &msg);
+
+    // The authority can't approve on behalf of alice
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "approve",
+            &(
+                approvals.clone(),
+                seed,
+                alice,
+                bob,
+                60,
+                sign(rng, &bob_sk, &msg),
+            ),
+            POINT_LIMIT,
+        )
+        .expect_err("Approving without the owner signature should error");
+    assert_eq!(allowance(session, &alice, &bob), 0);
+
+    let signature = sign(rng, &alice_sk, &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "approve",
+            &(approvals, seed, alice, bob, 60, signature),
+            POINT_LIMIT,
+        )
+        .expect("Approving should succeed");
+
+    assert_eq!(
+        events::<ApprovalEvent>(&receipt.events, APPROVAL_TOPIC),
+        vec![(alice, bob, 60)]
+    );
+    assert_eq!(allowance(session, &alice, &bob), 60);
+    assert_eq!(allowance(session, &bob, &alice), 0);
+
+    // Spending over the allowance fails
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = transfer_from_msg(seed, bob, alice, charlie, 80);
+    let approvals = approve(&keys, &[0], &msg);
+    let signature = sign(rng, &bob_sk, &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer_from",
+            &(approvals, seed, bob, alice, charlie, 80, signature),
+            POINT_LIMIT,
+        )
+        .expect_err("Transferring over the allowance should error");
+
+    // Spending within the allowance succeeds and reduces it
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = transfer_from_msg(seed, bob, alice, charlie, 50);
+    let approvals = approve(&keys, &[0], &msg);
+
+    // The authority can't spend on behalf of bob
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer_from",
+            &(
+                approvals.clone(),
+                seed,
+                bob,
+                alice,
+                charlie,
+                50,
+                sign(rng, &alice_sk, &msg),
+            ),
+            POINT_LIMIT,
+        )
+        .expect_err("Transferring without the spender signature should error");
+    assert_eq!(allowance(session, &alice, &bob), 60);
+
+    let signature = sign(rng, &bob_sk, &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer_from",
+            &(approvals, seed, bob, alice, charlie, 50, signature),
+            POINT_LIMIT,
+        )
+        .expect("Transferring within the allowance should succeed");
+
+    assert_eq!(
+        events::<TransferEvent>(&receipt.events, TRANSFER_TOPIC),
+        vec![(Some(alice), Some(charlie), 50)]
+    );
+    assert_eq!(allowance(session, &alice, &bob), 10);
+    assert_eq!(balance(session, &alice), 50);
+    assert_eq!(balance(session, &bob), 0);
+    assert_eq!(balance(session, &charlie), 50);
+    assert_eq!(total_supply(session), 100);
+
+    // Spending more than the balance fails, even with enough allowance
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = approve_msg(seed, alice, bob, 1000);
+    let approvals = approve(&keys, &[0], &msg);
+    let signature = sign(rng, &alice_sk, &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "approve",
+            &(approvals, seed, alice, bob, 1000, signature),
+            POINT_LIMIT,
+        )
+        .expect("Approving should succeed");
+
+    assert_eq!(allowance(session, &alice, &bob), 1000);
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = transfer_from_msg(seed, bob, alice, charlie, 60);
+    let approvals = approve(&keys, &[0], &msg);
+    let signature = sign(rng, &bob_sk, &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer_from",
+            &(approvals, seed, bob, alice, charlie, 60, signature),
+            POINT_LIMIT,
+        )
+        .expect_err("Transferring over the balance should error");
+
+    assert_eq!(balance(session, &alice), 50);
+    assert_eq!(allowance(session, &alice, &bob), 1000);
+}
+
+#[test]
+fn balance_events() {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let vm = &mut rusk_abi::new_ephemeral_vm()
+        .expect("Creating ephemeral VM should work");
+
+    let keys = authority_keys(rng, 1);
+    let authority = (vec![keys[0].1], 1);
+
+    let broker = PublicKey::from(&SecretKey::random(rng));
+
+    let alice = PublicKey::from(&SecretKey::random(rng));
+    let bob = PublicKey::from(&SecretKey::random(rng));
+
+    let session = &mut instantiate(vm, &authority, &broker);
+
+    let batch = vec![
+        (None, Some(alice), 100, TIMESTAMP),
+        (Some(alice), Some(bob), 30, TIMESTAMP),
+        (Some(bob), None, 10, TIMESTAMP),
+    ];
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = transfer_msg(seed, &batch);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer",
+            &(approvals, seed, batch),
+            POINT_LIMIT,
+        )
+        .expect("The batch processing should succeed");
+
+    assert_eq!(
+        events::<TransferEvent>(&receipt.events, TRANSFER_TOPIC),
+        vec![
+            (None, Some(alice), 100),
+            (Some(alice), Some(bob), 30),
+            (Some(bob), None, 10),
+        ]
+    );
+
+    // Fees to the broker are transfers as well
+    let batch = vec![(Some(alice), None, 20, TIMESTAMP)];
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = fee_msg(seed, &batch);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "fee",
+            &(approvals, seed, batch),
+            POINT_LIMIT,
+        )
+        .expect("The fee payment should succeed");
+
+    assert_eq!(
+        events::<TransferEvent>(&receipt.events, TRANSFER_TOPIC),
+        vec![(Some(alice), Some(broker), 20)]
+    );
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = burn_msg(seed, alice, 100);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "burn",
+            &(approvals, seed, alice, 100),
+            POINT_LIMIT,
+        )
+        .expect("Burning should succeed");
+
+    // Only the available balance is burned
+    assert_eq!(
+        events::<TransferEvent>(&receipt.events, TRANSFER_TOPIC),
+        vec![(Some(alice), None, 50)]
+    );
+}
+
+#[test]
+fn pause_events() {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let vm = &mut rusk_abi::new_ephemeral_vm()
+        .expect("Creating ephemeral VM should work");
+
+    let keys = authority_keys(rng, 1);
+    let authority = (vec![keys[0].1], 1);
+
+    let broker = PublicKey::from(&SecretKey::random(rng));
+
+    let alice = PublicKey::from(&SecretKey::random(rng));
+    let bob = PublicKey::from(&SecretKey::random(rng));
+
+    let session = &mut instantiate(vm, &authority, &broker);
+
+    assert!(!is_paused(session));
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = pause_msg(seed);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(GOVERNANCE_ID, "pause", &(approvals, seed), POINT_LIMIT)
+        .expect("Pausing the contract should succeed");
+
+    assert_eq!(events::<PauseEvent>(&receipt.events, PAUSE_TOPIC), [true]);
+    assert!(is_paused(session));
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = transfer_from_msg(seed, bob, alice, bob, 0);
+    let approvals = approve(&keys, &[0], &msg);
+
+    session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "transfer_from",
+            &(approvals, seed, bob, alice, bob, 0),
+            POINT_LIMIT,
+        )
+        .expect_err("Transferring while paused should error");
+
+    let seed = BlsScalar::random(&mut *rng);
+    let msg = unpause_msg(seed);
+    let approvals = approve(&keys, &[0], &msg);
+
+    let receipt = session
+        .call::<_, ()>(
+            GOVERNANCE_ID,
+            "unpause",
+            &(approvals, seed),
+            POINT_LIMIT,
+        )
+        .expect("Unpausing the contract should succeed");
+
+    assert_eq!(events::<PauseEvent>(&receipt.events, PAUSE_TOPIC), [false]);
+    assert!(!is_paused(session));
+}
@@ -41,6 +41,9 @@ pub struct Stake {
 }
 
 /// Unstake a value from the stake contract.
+///
+/// The unstaked value is not paid out straight away, but is locked for the
+/// unbonding period and later claimed using [`ClaimUnstaked`].
 #[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
 #[archive_attr(derive(CheckBytes))]
 pub struct Unstake {
@@ -48,8 +51,18 @@ pub struct Unstake {
     pub public_key: PublicKey,
     /// Signature belonging to the given public key.
     pub signature: Signature,
+}
+
+/// Claim a previously unstaked value, once its unbonding period has elapsed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ClaimUnstaked {
+    /// Public key that unstaked.
+    pub public_key: PublicKey,
+    /// Signature belonging to the given public key.
+    pub signature: Signature,
     /// Note to withdraw to.
-    pub note: Vec<u8>, // todo: not sure it will stay as Vec
+    pub note: Vec<u8>,
     /// A proof of the `WFCT` circuit.
     pub proof: Vec<u8>,
 }
@@ -67,3 +80,36 @@ pub struct Withdraw {
     /// A nonce to prevent replay.
     pub nonce: BlsScalar,
 }
+
+/// Event emitted by the stake contract whenever the stake of a provisioner
+/// changes.
+///
+/// The kind of change is given by the topic the event is emitted with, one of
+/// [`STAKED_TOPIC`], [`UNSTAKED_TOPIC`], [`CLAIMED_TOPIC`],
+/// [`WITHDRAWN_TOPIC`], [`REWARD_TOPIC`], [`SLASH_TOPIC`] or
+/// [`HARD_SLASH_TOPIC`].
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct StakingEvent {
+    /// Public key of the provisioner the event refers to.
+    pub public_key: PublicKey,
+    /// Value involved in the change.
+    pub value: u64,
+    /// Height of the block the change happened in.
+    pub height: u64,
+}
+
+/// Topic of the event emitted when a value is staked.
+pub const STAKED_TOPIC: &str = "staked";
+/// Topic of the event emitted when a stake is unstaked and starts unbonding.
+pub const UNSTAKED_TOPIC: &str = "unstaked";
+/// Topic of the event emitted when unbonded funds are claimed.
+pub const CLAIMED_TOPIC: &str = "claimed";
+/// Topic of the event emitted when the accumulated reward is withdrawn.
+pub const WITHDRAWN_TOPIC: &str = "withdrawn";
+/// Topic of the event emitted when a provisioner is rewarded.
+pub const REWARD_TOPIC: &str = "reward";
+/// Topic of the event emitted when a provisioner reward is slashed.
+pub const SLASH_TOPIC: &str = "slash";
+/// Topic of the event emitted when a provisioner stake is slashed.
+pub const HARD_SLASH_TOPIC: &str = "hard_slash";
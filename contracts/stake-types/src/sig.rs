@@ -32,7 +32,13 @@ pub fn stake_signature_message(
 }
 
 /// Signature message used for [`Unstake`].
-pub fn unstake_signature_message<T>(counter: u64, note: T) -> Vec<u8>
+#[must_use]
+pub fn unstake_signature_message(counter: u64) -> [u8; u64::SIZE] {
+    counter.to_bytes()
+}
+
+/// Signature message used for [`ClaimUnstaked`].
+pub fn claim_unstaked_signature_message<T>(counter: u64, note: T) -> Vec<u8>
 where
     T: AsRef<[u8]>,
 {
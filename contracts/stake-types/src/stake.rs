@@ -13,6 +13,10 @@ pub type BlockHeight = u64;
 /// Epoch used for stake operations
 pub const EPOCH: u64 = 2160;
 
+/// Default number of blocks unstaked funds are locked before they can be
+/// claimed.
+pub const UNBONDING_PERIOD: u64 = EPOCH;
+
 /// Calculate the block height at which the next epoch takes effect.
 #[must_use]
 pub const fn next_epoch(block_height: BlockHeight) -> u64 {
@@ -151,3 +155,40 @@ impl StakeData {
         next_epoch(block_height) + maturity_blocks
     }
 }
+
+/// Funds removed from a stake, waiting for the unbonding period to elapse.
+///
+/// While unbonding the funds no longer count towards the provisioner's stake,
+/// but they can still be slashed. Once the `release_height` is reached they
+/// can be claimed.
+///
+/// Every unstake creates its own entry, so that funds already unbonding keep
+/// their release height.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Deserialize, Serialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Unbonding {
+    /// Amount pending withdrawal.
+    pub value: u64,
+    /// Block height from which the amount can be claimed.
+    pub release_height: BlockHeight,
+}
+
+impl Unbonding {
+    /// Create a new pending withdrawal of `value`, claimable from the
+    /// `release_height`.
+    #[must_use]
+    pub const fn new(value: u64, release_height: BlockHeight) -> Self {
+        Self {
+            value,
+            release_height,
+        }
+    }
+
+    /// Returns true if the funds can be claimed at the given `block_height`.
+    #[must_use]
+    pub const fn is_mature(&self, block_height: BlockHeight) -> bool {
+        block_height >= self.release_height
+    }
+}
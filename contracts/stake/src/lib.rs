@@ -44,6 +44,14 @@ unsafe fn unstake(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn claim_unstaked(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.claim_unstaked(arg)
+    })
+}
+
 #[no_mangle]
 unsafe fn withdraw(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |arg| {
@@ -59,6 +67,18 @@ unsafe fn get_stake(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |pk: PublicKey| STATE.get_stake(&pk).cloned())
 }
 
+#[no_mangle]
+unsafe fn get_unbonding(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |pk: PublicKey| {
+        STATE.get_unbonding(&pk).to_vec()
+    })
+}
+
+#[no_mangle]
+unsafe fn unbonding_period(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.unbonding_period())
+}
+
 #[no_mangle]
 unsafe fn slashed_amount(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |_: ()| STATE.slashed_amount())
@@ -81,6 +101,14 @@ unsafe fn insert_stake(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn set_unbonding_period(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |unbonding_period| {
+        assert_external_caller();
+        STATE.set_unbonding_period(unbonding_period)
+    })
+}
+
 #[no_mangle]
 unsafe fn reward(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |(pk, value)| {
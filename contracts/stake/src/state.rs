@@ -9,6 +9,7 @@ use core::cmp::min;
 use crate::*;
 
 use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 
 use dusk_bls12_381_sign::PublicKey;
 use dusk_bytes::Serializable;
@@ -25,9 +26,14 @@ use transfer_contract_types::*;
 ///
 /// Rewards may be received by a public key regardless of whether they have a
 /// valid stake.
+///
+/// Unstaked funds are held for an unbonding period before they can be
+/// claimed, during which they remain subject to slashing.
 #[derive(Debug, Default, Clone)]
 pub struct StakeState {
     stakes: BTreeMap<[u8; PublicKey::SIZE], StakeData>,
+    unbonding: BTreeMap<[u8; PublicKey::SIZE], Vec<Unbonding>>,
+    unbonding_period: u64,
     slashed_amount: u64,
 }
 
@@ -35,6 +41,8 @@ impl StakeState {
     pub const fn new() -> Self {
         Self {
             stakes: BTreeMap::new(),
+            unbonding: BTreeMap::new(),
+            unbonding_period: UNBONDING_PERIOD,
             slashed_amount: 0u64,
         }
     }
@@ -71,6 +79,8 @@ impl StakeState {
 
         let _: bool = rusk_abi::call(transfer_module, "stct", &stct)
             .expect("Sending note to contract should succeed");
+
+        emit_event(STAKED_TOPIC, stake.public_key, stake.value);
     }
 
     pub fn unstake(&mut self, unstake: Unstake) {
@@ -85,26 +95,81 @@ impl StakeState {
         loaded_stake.increment_counter();
 
         // verify signature
-        let digest =
-            unstake_signature_message(counter, unstake.note.as_slice());
+        let digest = unstake_signature_message(counter).to_vec();
 
         if !rusk_abi::verify_bls(digest, unstake.public_key, unstake.signature)
         {
             panic!("Invalid signature!");
         }
+
+        // lock the unstaked value until the unbonding period has elapsed.
+        // Funds still unbonding from previous unstakes keep their own release
+        // height.
+        let release_height = rusk_abi::block_height() + self.unbonding_period;
+
+        self.unbonding
+            .entry(unstake.public_key.to_bytes())
+            .or_default()
+            .push(Unbonding::new(value, release_height));
+
+        emit_event(UNSTAKED_TOPIC, unstake.public_key, value);
+    }
+
+    pub fn claim_unstaked(&mut self, claim: ClaimUnstaked) {
+        let key = claim.public_key.to_bytes();
+        let unbonding = self
+            .unbonding
+            .get_mut(&key)
+            .expect("There should be unstaked funds to claim!");
+
+        // claim all the funds that are mature, leaving the others unbonding
+        let block_height = rusk_abi::block_height();
+        let value: u64 = unbonding
+            .iter()
+            .filter(|u| u.is_mature(block_height))
+            .map(|u| u.value)
+            .sum();
+
+        if value == 0 {
+            panic!("The unstaked funds are still unbonding!");
+        }
+
+        unbonding.retain(|u| !u.is_mature(block_height));
+        if unbonding.is_empty() {
+            self.unbonding.remove(&key);
+        }
+
+        // increment the signature counter
+        let loaded_stake = self
+            .get_stake_mut(&claim.public_key)
+            .expect("A stake should exist in the map to be claimed!");
+
+        let counter = loaded_stake.counter();
+        loaded_stake.increment_counter();
+
+        // verify signature
+        let digest =
+            claim_unstaked_signature_message(counter, claim.note.as_slice());
+
+        if !rusk_abi::verify_bls(digest, claim.public_key, claim.signature) {
+            panic!("Invalid signature!");
+        }
+
         // make call to transfer contract to withdraw a note from this contract
-        // containing the value of the stake
+        // containing the value that was unbonding
         let transfer_module = TRANSFER_CONTRACT;
         let _: bool = rusk_abi::call(
             transfer_module,
             "wfct_raw",
             &WfctRaw {
                 value,
-                note: unstake.note,
-                proof: unstake.proof,
+                note: claim.note,
+                proof: claim.proof,
             },
         )
         .expect("Withdrawing note from contract should be successful");
+
+        emit_event(CLAIMED_TOPIC, claim.public_key, value);
     }
 
     pub fn withdraw(&mut self, withdraw: Withdraw) {
@@ -152,6 +217,8 @@ impl StakeState {
             },
         )
         .expect("Minting a reward note should succeed");
+
+        emit_event(WITHDRAWN_TOPIC, withdraw.public_key, reward);
     }
 
     /// Gets a reference to a stake.
@@ -164,6 +231,26 @@ impl StakeState {
         self.stakes.get_mut(&key.to_bytes())
     }
 
+    /// Gets the funds of a key that are waiting for the unbonding period to
+    /// elapse, ordered by release height.
+    pub fn get_unbonding(&self, key: &PublicKey) -> &[Unbonding] {
+        self.unbonding
+            .get(&key.to_bytes())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Number of blocks unstaked funds are locked before they can be claimed.
+    pub fn unbonding_period(&self) -> u64 {
+        self.unbonding_period
+    }
+
+    /// Sets the number of blocks unstaked funds are locked for. Funds already
+    /// unbonding keep their release height.
+    pub fn set_unbonding_period(&mut self, unbonding_period: u64) {
+        self.unbonding_period = unbonding_period;
+    }
+
     /// Pushes the given `stake` onto the state for a given `public_key`.
     pub fn insert_stake(&mut self, public_key: PublicKey, stake: StakeData) {
         self.stakes.insert(public_key.to_bytes(), stake);
@@ -192,6 +279,8 @@ impl StakeState {
     pub fn reward(&mut self, public_key: &PublicKey, value: u64) {
         let stake = self.load_or_create_stake_mut(public_key);
         stake.increase_reward(value);
+
+        emit_event(REWARD_TOPIC, *public_key, value);
     }
 
     /// Total amount slashed from the genesis
@@ -212,48 +301,72 @@ impl StakeState {
         let to_slash = min(to_slash, stake.reward);
         stake.reward -= to_slash;
 
+        // A stake that is unbonding has no eligibility to shift
         if stake.reward == 0 {
-            let (_, eligibility) = stake
-                .amount
-                .as_mut()
-                .expect("The stake to slash should be active");
-            *eligibility = next_epoch(rusk_abi::block_height());
+            if let Some((_, eligibility)) = stake.amount.as_mut() {
+                *eligibility = next_epoch(rusk_abi::block_height());
+            }
         }
 
         // Update the total slashed amount
         self.slashed_amount += to_slash;
+
+        emit_event(SLASH_TOPIC, *public_key, to_slash);
     }
 
     /// Slash the given `to_slash` amount from a `public_key` stake
     ///
     /// If the stake is less than the `to_slash` amount, then the stake is
-    /// depleted
+    /// depleted and the remainder is slashed from any funds still unbonding
     pub fn hard_slash(&mut self, public_key: &PublicKey, to_slash: u64) {
+        let key = public_key.to_bytes();
+
         let stake_info = self
-            .get_stake_mut(public_key)
+            .stakes
+            .get_mut(&key)
             .expect("The stake to slash should exist");
 
-        let stake = stake_info
-            .amount
-            .as_mut()
-            .expect("The stake amount to slash should exist");
+        let unbonding = self.unbonding.get_mut(&key);
+
+        if stake_info.amount.is_none() && unbonding.is_none() {
+            panic!("The stake amount to slash should exist");
+        }
+
+        let mut slashed = 0;
 
-        let to_slash = min(to_slash, stake.0);
-        if to_slash > 0 {
-            // Update the staked amount
-            stake.0 -= to_slash;
+        if let Some((value, _)) = stake_info.amount.as_mut() {
+            let to_slash = min(to_slash, *value);
+            *value -= to_slash;
+            slashed += to_slash;
+        }
 
+        if let Some(unbonding) = unbonding {
+            for entry in unbonding.iter_mut() {
+                let to_slash = min(to_slash - slashed, entry.value);
+                entry.value -= to_slash;
+                slashed += to_slash;
+            }
+
+            unbonding.retain(|entry| entry.value > 0);
+            if unbonding.is_empty() {
+                self.unbonding.remove(&key);
+            }
+        }
+
+        if slashed > 0 {
             // Update the module balance to reflect the change in the amount
             // withdrawable from the contract
             let _: bool = rusk_abi::call(
                 TRANSFER_CONTRACT,
                 "sub_module_balance",
-                &(STAKE_CONTRACT, to_slash),
+                &(STAKE_CONTRACT, slashed),
             )
             .expect("Subtracting balance should succeed");
 
             // Update the total slashed amount
-            self.slashed_amount += to_slash;
+            self.slashed_amount += slashed;
+
+            emit_event(HARD_SLASH_TOPIC, *public_key, slashed);
         }
     }
 
@@ -266,3 +379,17 @@ impl StakeState {
         }
     }
 }
+
+/// Emits a [`StakingEvent`] with the given `topic`, at the current block
+/// height.
+fn emit_event(topic: &'static str, public_key: PublicKey, value: u64) {
+    let height = rusk_abi::block_height();
+    rusk_abi::emit(
+        topic,
+        StakingEvent {
+            public_key,
+            value,
+            height,
+        },
+    );
+}
@@ -22,8 +22,10 @@ use rusk_abi::dusk::{dusk, LUX};
 use rusk_abi::{CallReceipt, ContractData, ContractError, Error, Session, VM};
 use rusk_abi::{STAKE_CONTRACT, TRANSFER_CONTRACT};
 use stake_contract_types::{
-    stake_signature_message, unstake_signature_message,
-    withdraw_signature_message, Stake, StakeData, Unstake, Withdraw,
+    claim_unstaked_signature_message, stake_signature_message,
+    unstake_signature_message, withdraw_signature_message, ClaimUnstaked,
+    Stake, StakeData, StakingEvent, Unbonding, Unstake, Withdraw, STAKED_TOPIC,
+    UNBONDING_PERIOD,
 };
 use transfer_circuits::{
     CircuitInput, CircuitInputSignature, ExecuteCircuitOneTwo,
@@ -197,6 +199,7 @@ fn stake_withdraw_unstake() {
     const STCT_FEE: u64 = dusk(1.0);
     const WITHDRAW_FEE: u64 = dusk(1.0);
     const WFCT_FEE: u64 = dusk(1.0);
+    const CLAIM_FEE: u64 = dusk(1.0);
 
     let rng = &mut StdRng::seed_from_u64(0xfeeb);
 
@@ -371,6 +374,20 @@ fn stake_withdraw_unstake() {
 
     println!("STAKE   : {gas_spent} gas");
 
+    let event = receipt
+        .events
+        .iter()
+        .find(|e| e.source == STAKE_CONTRACT && e.topic == STAKED_TOPIC)
+        .expect("A staked event should be emitted");
+    let event: StakingEvent = rkyv::from_bytes(&event.data)
+        .expect("The event should be a staking event");
+
+    assert_eq!(event.public_key, pk, "Event should refer to the staked key");
+    assert_eq!(
+        event.value, crossover_value,
+        "Event value should match sent amount"
+    );
+
     let stake_data: Option<StakeData> = session
         .call(STAKE_CONTRACT, "get_stake", &pk, POINT_LIMIT)
         .expect("Getting the stake should succeed")
@@ -651,33 +668,14 @@ fn stake_withdraw_unstake() {
     let change_blinder = JubJubScalar::random(rng);
     let change_note = Note::obfuscated(rng, &psk, change_value, change_blinder);
 
-    let withdraw_value = crossover_value;
-    let withdraw_blinder = JubJubScalar::random(rng);
-    let withdraw_note =
-        Note::obfuscated(rng, &psk, withdraw_value, withdraw_blinder);
-
-    // Fashion a WFCT proof and an `Unstake` struct instance
+    // Fashion an `Unstake` struct instance
 
-    let wfct_circuit = WithdrawFromTransparentCircuit::new(
-        *withdraw_note.value_commitment(),
-        withdraw_value,
-        withdraw_blinder,
-    );
-    let (wfct_prover, _) = prover_verifier("WithdrawFromTransparentCircuit");
-
-    let (wfct_proof, _) = wfct_prover
-        .prove(rng, &wfct_circuit)
-        .expect("Proving WFCT circuit should succeed");
-
-    let unstake_digest =
-        unstake_signature_message(stake_data.counter, withdraw_note.to_bytes());
+    let unstake_digest = unstake_signature_message(stake_data.counter);
     let unstake_sig = sk.sign(&pk, unstake_digest.as_slice());
 
     let unstake = Unstake {
         public_key: pk,
         signature: unstake_sig,
-        note: withdraw_note.to_bytes().to_vec(),
-        proof: wfct_proof.to_bytes().to_vec(),
     };
     let unstake_bytes = rkyv::to_bytes::<_, 2048>(&unstake)
         .expect("Serializing Unstake should succeed")
@@ -815,4 +813,232 @@ fn stake_withdraw_unstake() {
     update_root(&mut session).expect("Updating the root should succeed");
 
     println!("UNSTAKE : {gas_spent} gas");
+
+    let stake_data: Option<StakeData> = session
+        .call(STAKE_CONTRACT, "get_stake", &pk, POINT_LIMIT)
+        .expect("Getting the stake should succeed")
+        .data;
+    let stake_data = stake_data.expect("The stake should exist");
+
+    assert_eq!(stake_data.amount, None, "The amount should be unstaked");
+    assert_eq!(stake_data.counter, 3, "Counter should increment once");
+
+    let unbonding: Vec<Unbonding> = session
+        .call(STAKE_CONTRACT, "get_unbonding", &pk, POINT_LIMIT)
+        .expect("Getting the unbonding funds should succeed")
+        .data;
+
+    assert_eq!(
+        unbonding,
+        vec![Unbonding::new(crossover_value, 3 + UNBONDING_PERIOD)],
+        "The unstaked amount should be unbonding"
+    );
+
+    // Start claiming the unstaked amount once the unbonding period is over
+
+    let leaves = leaves_from_height(&mut session, 3)
+        .expect("Getting the notes should succeed");
+
+    let input_notes =
+        filter_notes_owned_by(vk, leaves.into_iter().map(|leaf| leaf.note));
+
+    assert_eq!(
+        input_notes.len(),
+        2,
+        "Only the change and refund notes should be produced by unstaking"
+    );
+
+    let mut input_values = [0u64; 2];
+    let mut input_blinders = [JubJubScalar::zero(); 2];
+    let mut input_nullifiers = [BlsScalar::zero(); 2];
+
+    for i in 0..2 {
+        input_values[i] = input_notes[i]
+            .value(Some(&vk))
+            .expect("The given view key should own the note");
+        input_blinders[i] = input_notes[i]
+            .blinding_factor(Some(&vk))
+            .expect("The given view key should own the note");
+        input_nullifiers[i] = input_notes[i].gen_nullifier(&ssk);
+    }
+
+    let input_value: u64 = input_values.iter().sum();
+
+    let gas_limit = CLAIM_FEE;
+    let gas_price = LUX;
+
+    let fee = Fee::new(rng, gas_limit, gas_price, &psk);
+
+    // The change note should have the value of the input note, minus what is
+    // maximally spent.
+    let change_value = input_value - gas_price * gas_limit;
+    let change_blinder = JubJubScalar::random(rng);
+    let change_note = Note::obfuscated(rng, &psk, change_value, change_blinder);
+
+    let withdraw_value = crossover_value;
+    let withdraw_blinder = JubJubScalar::random(rng);
+    let withdraw_note =
+        Note::obfuscated(rng, &psk, withdraw_value, withdraw_blinder);
+
+    // Fashion a WFCT proof and a `ClaimUnstaked` struct instance
+
+    let wfct_circuit = WithdrawFromTransparentCircuit::new(
+        *withdraw_note.value_commitment(),
+        withdraw_value,
+        withdraw_blinder,
+    );
+    let (wfct_prover, _) = prover_verifier("WithdrawFromTransparentCircuit");
+
+    let (wfct_proof, _) = wfct_prover
+        .prove(rng, &wfct_circuit)
+        .expect("Proving WFCT circuit should succeed");
+
+    let claim_digest = claim_unstaked_signature_message(
+        stake_data.counter,
+        withdraw_note.to_bytes(),
+    );
+    let claim_sig = sk.sign(&pk, claim_digest.as_slice());
+
+    let claim = ClaimUnstaked {
+        public_key: pk,
+        signature: claim_sig,
+        note: withdraw_note.to_bytes().to_vec(),
+        proof: wfct_proof.to_bytes().to_vec(),
+    };
+    let claim_bytes = rkyv::to_bytes::<_, 2048>(&claim)
+        .expect("Serializing ClaimUnstaked should succeed")
+        .to_vec();
+
+    let call = Some((
+        STAKE_CONTRACT.to_bytes(),
+        String::from("claim_unstaked"),
+        claim_bytes,
+    ));
+
+    // Compose the circuit. In this case we're using two inputs and one output.
+    let mut execute_circuit = ExecuteCircuitTwoTwo::new();
+
+    execute_circuit.set_fee(&fee);
+
+    execute_circuit
+        .add_output_with_data(change_note, change_value, change_blinder)
+        .expect("appending output should succeed");
+
+    let input_opening_0 = opening(&mut session, *input_notes[0].pos())
+        .expect("Querying the opening for the given position should succeed")
+        .expect("An opening should exist for a note in the tree");
+    let input_opening_1 = opening(&mut session, *input_notes[1].pos())
+        .expect("Querying the opening for the given position should succeed")
+        .expect("An opening should exist for a note in the tree");
+
+    // Generate pk_r_p
+    let sk_r_0 = ssk.sk_r(input_notes[0].stealth_address());
+    let pk_r_p_0 = GENERATOR_NUMS_EXTENDED * sk_r_0.as_ref();
+    let sk_r_1 = ssk.sk_r(input_notes[1].stealth_address());
+    let pk_r_p_1 = GENERATOR_NUMS_EXTENDED * sk_r_1.as_ref();
+
+    // The transaction hash must be computed before signing
+    let anchor =
+        root(&mut session).expect("Getting the anchor should be successful");
+
+    let tx_hash_input_bytes = Transaction::hash_input_bytes_from_components(
+        &[input_nullifiers[0], input_nullifiers[1]],
+        &[change_note],
+        &anchor,
+        &fee,
+        &None,
+        &call,
+    );
+    let tx_hash = rusk_abi::hash(tx_hash_input_bytes);
+
+    execute_circuit.set_tx_hash(tx_hash);
+
+    let circuit_input_signature_0 =
+        CircuitInputSignature::sign(rng, &ssk, &input_notes[0], tx_hash);
+    let circuit_input_signature_1 =
+        CircuitInputSignature::sign(rng, &ssk, &input_notes[1], tx_hash);
+
+    let circuit_input_0 = CircuitInput::new(
+        input_opening_0,
+        input_notes[0],
+        pk_r_p_0.into(),
+        input_values[0],
+        input_blinders[0],
+        input_nullifiers[0],
+        circuit_input_signature_0,
+    );
+    let circuit_input_1 = CircuitInput::new(
+        input_opening_1,
+        input_notes[1],
+        pk_r_p_1.into(),
+        input_values[1],
+        input_blinders[1],
+        input_nullifiers[1],
+        circuit_input_signature_1,
+    );
+
+    execute_circuit
+        .add_input(circuit_input_0)
+        .expect("appending input should succeed");
+    execute_circuit
+        .add_input(circuit_input_1)
+        .expect("appending input should succeed");
+
+    let (prover_key, _) = prover_verifier("ExecuteCircuitTwoTwo");
+    let (execute_proof, _) = prover_key
+        .prove(rng, &execute_circuit)
+        .expect("Proving should be successful");
+
+    let tx = Transaction {
+        anchor,
+        nullifiers: vec![input_nullifiers[0], input_nullifiers[1]],
+        outputs: vec![change_note],
+        fee,
+        crossover: None,
+        proof: execute_proof.to_bytes().to_vec(),
+        call,
+    };
+
+    // claiming before the release height should fail
+    let base = session.commit().expect("Committing should succeed");
+    let mut session = rusk_abi::new_session(vm, base, 4)
+        .expect("Instantiating new session should succeed");
+
+    let receipt =
+        execute(&mut session, tx.clone()).expect("Executing TX should succeed");
+    receipt
+        .data
+        .expect_err("Claiming while unbonding should error");
+
+    // claiming at the release height should succeed
+    let release_height = 3 + UNBONDING_PERIOD;
+    let mut session = rusk_abi::new_session(vm, base, release_height)
+        .expect("Instantiating new session should succeed");
+
+    let receipt =
+        execute(&mut session, tx).expect("Executing TX should succeed");
+    let gas_spent = receipt.gas_spent;
+    receipt.data.expect("Executed TX should not error");
+    update_root(&mut session).expect("Updating the root should succeed");
+
+    println!("CLAIM   : {gas_spent} gas");
+
+    let unbonding: Vec<Unbonding> = session
+        .call(STAKE_CONTRACT, "get_unbonding", &pk, POINT_LIMIT)
+        .expect("Getting the unbonding funds should succeed")
+        .data;
+
+    assert!(
+        unbonding.is_empty(),
+        "The unbonding funds should be claimed"
+    );
+
+    let leaves = leaves_from_height(&mut session, release_height)
+        .expect("Getting the notes should succeed");
+    assert_eq!(
+        leaves.len(),
+        3,
+        "There should be three notes in the tree at this block height \
+        due to there also being a withdrawn note having been produced"
+    );
 }
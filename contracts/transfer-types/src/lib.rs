@@ -11,10 +11,12 @@
 #![deny(clippy::pedantic)]
 
 extern crate alloc;
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_pki::StealthAddress;
+use dusk_schnorr::Signature;
 
 use bytecheck::CheckBytes;
 use phoenix_core::{Message, Note};
@@ -142,3 +144,326 @@ pub struct Mint {
     /// A nonce to prevent replay.
     pub nonce: BlsScalar,
 }
+
+/// Name of the transfer contract function a transaction should call to deploy
+/// a new contract. The call is never executed by the transfer contract itself,
+/// the node deploys the contract once the transaction inputs are spent.
+pub const DEPLOY_FN_NAME: &str = "deploy";
+
+/// Gas charged for each byte of bytecode deployed.
+pub const GAS_PER_DEPLOY_BYTE: u64 = 100;
+
+/// Size of the owner of a deployed contract, in bytes.
+pub const DEPLOY_OWNER_SIZE: usize = 64;
+
+/// Deploy a new contract.
+#[derive(Debug, Clone, PartialEq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Deploy {
+    /// The WASM bytecode of the contract.
+    pub bytecode: Vec<u8>,
+    /// The owner of the contract.
+    pub owner: [u8; DEPLOY_OWNER_SIZE],
+    /// Serialized argument passed to the `init` function of the contract, if
+    /// any.
+    ///
+    /// The VM doesn't report the gas spent by the `init` function, so when
+    /// present all the gas left to the transaction is charged.
+    pub init_args: Option<Vec<u8>>,
+    /// A nonce allowing the same owner to deploy the same bytecode more than
+    /// once.
+    pub nonce: u64,
+    /// Signature of the owner over the [`signature_message`], using the `A`
+    /// component of their public spend key.
+    ///
+    /// [`signature_message`]: Deploy::signature_message
+    pub signature: Signature,
+}
+
+impl Deploy {
+    /// The message the owner signs to deploy a contract. The signature is
+    /// over the hash of these bytes, as computed by `rusk_abi`.
+    ///
+    /// Every variable-length field is prefixed with its length, so bytes
+    /// can't be moved from one field to another under the same signature.
+    #[must_use]
+    pub fn signature_message(
+        bytecode: &[u8],
+        owner: &[u8; DEPLOY_OWNER_SIZE],
+        init_args: Option<&[u8]>,
+        nonce: u64,
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        extend_prefixed(&mut bytes, bytecode);
+        bytes.extend(owner);
+
+        match init_args {
+            Some(init_args) => {
+                bytes.push(1);
+                extend_prefixed(&mut bytes, init_args);
+            }
+            None => bytes.push(0),
+        }
+
+        bytes.extend(nonce.to_le_bytes());
+
+        bytes
+    }
+
+    /// The bytes from which the id of the deployed contract is derived. These
+    /// are the same as the [`signature_message`], so that a pending deploy
+    /// can't be copied to claim its id with different arguments.
+    ///
+    /// [`signature_message`]: Deploy::signature_message
+    #[must_use]
+    pub fn id_bytes(&self) -> Vec<u8> {
+        Self::signature_message(
+            &self.bytecode,
+            &self.owner,
+            self.init_args.as_deref(),
+            self.nonce,
+        )
+    }
+}
+
+/// Name of the transfer contract function a transaction should call to
+/// schedule the upgrade of a contract.
+pub const UPGRADE_FN_NAME: &str = "upgrade";
+
+/// Number of blocks between an upgrade being scheduled and it being applied.
+pub const UPGRADE_TIMELOCK: u64 = 2160;
+
+/// Gas available to deploy the new bytecode of an upgraded contract, and to the
+/// migration call.
+pub const MIGRATION_GAS_LIMIT: u64 = 1_000_000_000;
+
+/// Topic of the events emitted by the transfer contract when an upgrade is
+/// scheduled, applied, or fails.
+pub const UPGRADE_TOPIC: &str = "upgrade";
+
+/// A call performed on the new bytecode of a contract during its upgrade.
+///
+/// While the migration runs, the contract with the old bytecode is still
+/// available under its id, allowing the new bytecode to pull the state it
+/// should keep.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Migration {
+    /// Name of the function to call.
+    pub fn_name: String,
+    /// Serialized argument of the function.
+    pub fn_args: Vec<u8>,
+}
+
+/// Schedule the upgrade of a contract, swapping its bytecode once
+/// [`UPGRADE_TIMELOCK`] blocks have passed.
+#[derive(Debug, Clone, PartialEq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Upgrade {
+    /// The contract to upgrade.
+    pub contract: ModuleId,
+    /// The new WASM bytecode of the contract.
+    pub bytecode: Vec<u8>,
+    /// The migration to perform on the new bytecode, if any.
+    pub migration: Option<Migration>,
+    /// Signature of the owner of the contract over the
+    /// [`signature_message`], using the `A` component of their public spend
+    /// key.
+    ///
+    /// [`signature_message`]: Upgrade::signature_message
+    pub signature: Signature,
+}
+
+impl Upgrade {
+    /// The message the owner of the contract signs to upgrade it. The
+    /// signature is over the hash of these bytes, as computed by `rusk_abi`.
+    ///
+    /// The `nonce` is the upgrade nonce of the contract, as reported by the
+    /// transfer contract, preventing signatures from being replayed. Every
+    /// variable-length field is prefixed with its length, so bytes can't be
+    /// moved from one field to another under the same signature.
+    #[must_use]
+    pub fn signature_message(
+        contract: &ModuleId,
+        nonce: u64,
+        bytecode: &[u8],
+        migration: Option<&Migration>,
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend(contract);
+        bytes.extend(nonce.to_le_bytes());
+        extend_prefixed(&mut bytes, bytecode);
+
+        match migration {
+            Some(migration) => {
+                bytes.push(1);
+                extend_prefixed(&mut bytes, migration.fn_name.as_bytes());
+                extend_prefixed(&mut bytes, &migration.fn_args);
+            }
+            None => bytes.push(0),
+        }
+
+        bytes
+    }
+}
+
+/// Appends the little-endian length of `field`, followed by `field` itself.
+fn extend_prefixed(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend((field.len() as u64).to_le_bytes());
+    bytes.extend(field);
+}
+
+/// An upgrade waiting for its timelock to expire.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PendingUpgrade {
+    /// The new WASM bytecode of the contract.
+    pub bytecode: Vec<u8>,
+    /// The migration to perform on the new bytecode, if any.
+    pub migration: Option<Migration>,
+    /// The block height at which the upgrade is applied.
+    pub height: u64,
+}
+
+/// The status of an upgrade.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Deserialize, Serialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub enum UpgradeStatus {
+    /// The upgrade is scheduled, and will be applied at the event's height.
+    Scheduled,
+    /// The upgrade was applied at the event's height.
+    Applied,
+    /// The upgrade failed at the event's height, leaving the contract
+    /// untouched.
+    Failed,
+}
+
+/// Event emitted by the transfer contract over the lifetime of an upgrade.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct UpgradeEvent {
+    /// The upgraded contract.
+    pub contract: ModuleId,
+    /// Hash of the new bytecode.
+    pub code_hash: BlsScalar,
+    /// The status of the upgrade.
+    pub status: UpgradeStatus,
+    /// The height the status refers to.
+    pub height: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+    use alloc::vec;
+    use dusk_bytes::Serializable;
+    use dusk_pki::{PublicKey, SecretKey, SecretSpendKey};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn resplit_upgrade_rejected() {
+        let rng = &mut StdRng::seed_from_u64(0xbeef);
+
+        let ssk = SecretSpendKey::random(rng);
+        let sk = SecretKey::from(ssk.a());
+        let pk = PublicKey::from(ssk.public_spend_key().A());
+
+        let contract = [1u8; 32];
+        let nonce = 3;
+
+        let signed = Migration {
+            fn_name: "migrate".to_string(),
+            fn_args: vec![4, 5, 6],
+        };
+        let msg = Upgrade::signature_message(
+            &contract,
+            nonce,
+            &[1, 2, 3],
+            Some(&signed),
+        );
+        let sig = Signature::new(&sk, rng, rusk_abi::hash(msg));
+
+        // The same bytes, moved across the bytecode, function name and
+        // argument boundaries.
+        let resplits = [
+            (vec![1, 2, 3, b'm'], "igrate", vec![4, 5, 6]),
+            (vec![1, 2], "\x03migrate", vec![4, 5, 6]),
+            (vec![1, 2, 3], "migrat", vec![b'e', 4, 5, 6]),
+            (vec![1, 2, 3], "migrate\x04", vec![5, 6]),
+        ];
+
+        for (bytecode, fn_name, fn_args) in resplits {
+            let migration = Migration {
+                fn_name: fn_name.to_string(),
+                fn_args,
+            };
+            let msg = Upgrade::signature_message(
+                &contract,
+                nonce,
+                &bytecode,
+                Some(&migration),
+            );
+            assert!(
+                !rusk_abi::verify_schnorr(rusk_abi::hash(msg), pk, sig),
+                "A re-split upgrade should not verify"
+            );
+        }
+
+        let msg = Upgrade::signature_message(
+            &contract,
+            nonce,
+            &[1, 2, 3],
+            Some(&signed),
+        );
+        assert!(
+            rusk_abi::verify_schnorr(rusk_abi::hash(msg), pk, sig),
+            "The signed upgrade should verify"
+        );
+    }
+
+    #[test]
+    fn copied_deploy_rejected() {
+        let rng = &mut StdRng::seed_from_u64(0xcafe);
+
+        let ssk = SecretSpendKey::random(rng);
+        let owner = ssk.public_spend_key();
+        let pk = PublicKey::from(owner.A());
+
+        let bytecode = [1u8, 2, 3];
+        let owner = owner.to_bytes();
+
+        let msg = Deploy::signature_message(&bytecode, &owner, Some(&[4]), 0);
+        let sk = SecretKey::from(ssk.a());
+        let deploy = Deploy {
+            bytecode: bytecode.to_vec(),
+            owner,
+            init_args: Some(vec![4]),
+            nonce: 0,
+            signature: Signature::new(&sk, rng, rusk_abi::hash(msg)),
+        };
+
+        let copied = Deploy {
+            init_args: Some(vec![5]),
+            ..deploy.clone()
+        };
+        assert_ne!(
+            deploy.id_bytes(),
+            copied.id_bytes(),
+            "Deploys with different arguments should have different ids"
+        );
+
+        let verify = |deploy: &Deploy| {
+            let msg = rusk_abi::hash(deploy.id_bytes());
+            rusk_abi::verify_schnorr(msg, pk, deploy.signature)
+        };
+        assert!(verify(&deploy), "The signed deploy should verify");
+        assert!(!verify(&copied), "A copied deploy should not verify");
+    }
+}
@@ -14,6 +14,7 @@ mod circuits;
 mod error;
 mod state;
 mod tree;
+mod upgrade;
 
 use rusk_abi::{ContractId, STAKE_CONTRACT};
 use state::TransferState;
@@ -121,6 +122,36 @@ unsafe fn leaves_from_pos(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |pos| STATE.leaves_from_pos(pos))
 }
 
+#[no_mangle]
+unsafe fn owner(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |contract| STATE.contract_owner(contract))
+}
+
+#[no_mangle]
+unsafe fn deployed_owner(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |contract| STATE.deployed_owner(contract))
+}
+
+#[no_mangle]
+unsafe fn code_hash(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |contract| STATE.code_hash(contract))
+}
+
+#[no_mangle]
+unsafe fn upgrade_nonce(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |contract| STATE.upgrade_nonce(contract))
+}
+
+#[no_mangle]
+unsafe fn upgrade_events(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |contract| STATE.upgrade_events(contract))
+}
+
+#[no_mangle]
+unsafe fn due_upgrades(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |height| STATE.due_upgrades(height))
+}
+
 // "Management" transactions
 
 #[no_mangle]
@@ -155,6 +186,22 @@ unsafe fn update_root(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn finish_upgrades(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(height, results)| {
+        assert_external_caller();
+        STATE.finish_upgrades(height, results)
+    })
+}
+
+#[no_mangle]
+unsafe fn set_deployed(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(contract, code_hash, owner)| {
+        assert_external_caller();
+        STATE.set_deployed(contract, code_hash, owner)
+    })
+}
+
 #[no_mangle]
 unsafe fn add_module_balance(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |(module, value)| {
@@ -7,6 +7,7 @@
 use crate::circuits::*;
 use crate::error::Error;
 use crate::tree::Tree;
+use crate::upgrade::Upgrades;
 
 use alloc::collections::btree_map::Entry;
 use alloc::collections::{BTreeMap, BTreeSet};
@@ -15,14 +16,17 @@ use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_jubjub::{JubJubAffine, JubJubExtended};
-use dusk_pki::{Ownable, PublicKey, StealthAddress};
+use dusk_pki::{Ownable, PublicKey, PublicSpendKey, StealthAddress};
 use phoenix_core::transaction::*;
 use phoenix_core::{Crossover, Fee, Message, Note};
 use poseidon_merkle::Opening as PoseidonOpening;
 use rusk_abi::{
     ContractError, ContractId, PaymentInfo, PublicInput, STAKE_CONTRACT,
 };
-use transfer_contract_types::{Mint, Stct, Wfco, WfcoRaw, Wfct, Wfctc};
+use transfer_contract_types::{
+    Mint, ModuleId, PendingUpgrade, Stct, UpgradeEvent, Wfco, WfcoRaw, Wfct,
+    Wfctc, DEPLOY_FN_NAME, UPGRADE_FN_NAME,
+};
 
 /// Arity of the transfer tree.
 pub const A: usize = 4;
@@ -37,6 +41,7 @@ pub struct TransferState {
     message_mapping_set: BTreeMap<ContractId, StealthAddress>,
     var_crossover: Option<Crossover>,
     var_crossover_addr: Option<StealthAddress>,
+    upgrades: Upgrades,
 }
 
 impl TransferState {
@@ -50,6 +55,7 @@ impl TransferState {
             message_mapping_set: BTreeMap::new(),
             var_crossover: None,
             var_crossover_addr: None,
+            upgrades: Upgrades::new(),
         }
     }
 
@@ -360,11 +366,34 @@ impl TransferState {
         let mut result = Ok(Vec::new());
 
         if let Some((contract_id, fn_name, fn_args)) = tx.call {
-            result = rusk_abi::call_raw(
-                ContractId::from_bytes(contract_id),
-                &fn_name,
-                &fn_args,
-            );
+            let contract_id = ContractId::from_bytes(contract_id);
+            let is_self = contract_id == rusk_abi::self_id();
+
+            match fn_name.as_str() {
+                // Deployments are carried out by the node once the inputs
+                // are spent, so there is nothing to call.
+                DEPLOY_FN_NAME if is_self => {}
+                UPGRADE_FN_NAME if is_self => {
+                    result = self.upgrades.schedule(&fn_args);
+                }
+                _ => {
+                    // The VM doesn't meter host functions, so the gas charged
+                    // for them is deducted from what's left for the call.
+                    let gas_left = rusk_abi::limit()
+                        .saturating_sub(rusk_abi::spent())
+                        .saturating_sub(rusk_abi::host_gas_spent());
+
+                    result = match gas_left {
+                        0 => Err(ContractError::OutOfGas),
+                        gas_left => rusk_abi::call_raw_with_limit(
+                            contract_id,
+                            &fn_name,
+                            &fn_args,
+                            gas_left,
+                        ),
+                    };
+                }
+            }
         }
 
         result
@@ -429,6 +458,62 @@ impl TransferState {
         self.roots.insert(root);
     }
 
+    /// Upgrades due to be applied at the given block height.
+    pub fn due_upgrades(&self, height: u64) -> Vec<(ModuleId, PendingUpgrade)> {
+        self.upgrades.due(height)
+    }
+
+    /// Records the outcome of the upgrades applied at the given block height.
+    pub fn finish_upgrades(
+        &mut self,
+        height: u64,
+        results: Vec<(ModuleId, bool)>,
+    ) {
+        self.upgrades.finish(height, results)
+    }
+
+    /// Records the hash of the bytecode and the owner of a contract deployed
+    /// by a transaction.
+    pub fn set_deployed(
+        &mut self,
+        contract: ModuleId,
+        code_hash: BlsScalar,
+        owner: [u8; PublicSpendKey::SIZE],
+    ) {
+        self.upgrades.set_deployed(contract, code_hash, owner)
+    }
+
+    /// Get the owner of a contract.
+    pub fn contract_owner(
+        &self,
+        contract: ModuleId,
+    ) -> Option<[u8; PublicSpendKey::SIZE]> {
+        Upgrades::owner(contract)
+    }
+
+    /// Get the owner a contract was deployed with by a transaction.
+    pub fn deployed_owner(
+        &self,
+        contract: ModuleId,
+    ) -> Option<[u8; PublicSpendKey::SIZE]> {
+        self.upgrades.deployed_owner(contract)
+    }
+
+    /// Get the hash of the bytecode of a contract.
+    pub fn code_hash(&self, contract: ModuleId) -> Option<BlsScalar> {
+        self.upgrades.code_hash(contract)
+    }
+
+    /// Get the nonce to sign the next upgrade of a contract with.
+    pub fn upgrade_nonce(&self, contract: ModuleId) -> u64 {
+        self.upgrades.nonce(contract)
+    }
+
+    /// Get the events of all upgrades of a contract.
+    pub fn upgrade_events(&self, contract: ModuleId) -> Vec<UpgradeEvent> {
+        self.upgrades.history(contract)
+    }
+
     /// Get the root of the tree.
     pub fn root(&self) -> BlsScalar {
         self.tree.root()
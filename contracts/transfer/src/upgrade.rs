@@ -0,0 +1,206 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_pki::{PublicKey, PublicSpendKey};
+use rusk_abi::{ContractError, ContractId, STAKE_CONTRACT, TRANSFER_CONTRACT};
+use transfer_contract_types::{
+    ModuleId, PendingUpgrade, Upgrade, UpgradeEvent, UpgradeStatus,
+    GAS_PER_DEPLOY_BYTE, UPGRADE_TIMELOCK, UPGRADE_TOPIC,
+};
+
+/// Size of the owner of a contract, in bytes.
+const OWNER_SIZE: usize = PublicSpendKey::SIZE;
+
+/// Keeps track of the upgrades of contracts, from the moment they are
+/// scheduled by their owner until they are applied by the node.
+pub struct Upgrades {
+    pending: BTreeMap<ContractId, PendingUpgrade>,
+    nonces: BTreeMap<ContractId, u64>,
+    code_hashes: BTreeMap<ContractId, BlsScalar>,
+    owners: BTreeMap<ContractId, [u8; OWNER_SIZE]>,
+    history: BTreeMap<ContractId, Vec<UpgradeEvent>>,
+}
+
+impl Upgrades {
+    pub const fn new() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            nonces: BTreeMap::new(),
+            code_hashes: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            history: BTreeMap::new(),
+        }
+    }
+
+    /// Schedules the upgrade serialized in `fn_args`, after checking it is
+    /// signed by the owner of the contract. A previously scheduled upgrade of
+    /// the same contract is replaced.
+    ///
+    /// The node charges gas for each byte of the upgrade once it is
+    /// scheduled, so upgrades whose charge exceeds the gas left are refused.
+    pub fn schedule(
+        &mut self,
+        fn_args: &[u8],
+    ) -> Result<Vec<u8>, ContractError> {
+        let upgrade_gas =
+            GAS_PER_DEPLOY_BYTE.saturating_mul(fn_args.len() as u64);
+        let gas_left = rusk_abi::limit()
+            .saturating_sub(rusk_abi::spent())
+            .saturating_sub(rusk_abi::host_gas_spent());
+        if upgrade_gas > gas_left {
+            return Err(ContractError::OutOfGas);
+        }
+
+        let upgrade = rkyv::from_bytes::<Upgrade>(fn_args)
+            .map_err(|_| panic_error("Invalid upgrade data"))?;
+
+        let contract = ContractId::from_bytes(upgrade.contract);
+        if contract == TRANSFER_CONTRACT || contract == STAKE_CONTRACT {
+            return Err(panic_error("Genesis contracts cannot be upgraded"));
+        }
+
+        let owner = Self::owner(upgrade.contract)
+            .ok_or_else(|| panic_error("Contract not found"))?;
+        let owner = PublicSpendKey::from_bytes(&owner)
+            .map_err(|_| panic_error("Invalid contract owner"))?;
+
+        let nonce = self.nonce(upgrade.contract);
+        let msg = Upgrade::signature_message(
+            &upgrade.contract,
+            nonce,
+            &upgrade.bytecode,
+            upgrade.migration.as_ref(),
+        );
+        let msg = rusk_abi::hash(msg);
+
+        let pk = PublicKey::from(owner.A());
+        if !rusk_abi::verify_schnorr(msg, pk, upgrade.signature) {
+            return Err(panic_error("Invalid owner signature"));
+        }
+
+        let height = rusk_abi::block_height() + UPGRADE_TIMELOCK;
+        let code_hash = rusk_abi::hash(upgrade.bytecode.clone());
+
+        self.nonces.insert(contract, nonce + 1);
+        self.pending.insert(
+            contract,
+            PendingUpgrade {
+                bytecode: upgrade.bytecode,
+                migration: upgrade.migration,
+                height,
+            },
+        );
+        self.record(UpgradeEvent {
+            contract: upgrade.contract,
+            code_hash,
+            status: UpgradeStatus::Scheduled,
+            height,
+        });
+
+        Ok(Vec::new())
+    }
+
+    /// Upgrades whose timelock expired at the given `height`.
+    pub fn due(&self, height: u64) -> Vec<(ModuleId, PendingUpgrade)> {
+        self.pending
+            .iter()
+            .filter(|(_, upgrade)| upgrade.height <= height)
+            .map(|(contract, upgrade)| (contract.to_bytes(), upgrade.clone()))
+            .collect()
+    }
+
+    /// Records the outcome of the upgrades applied by the node at the given
+    /// `height`, removing them from the pending ones.
+    pub fn finish(&mut self, height: u64, results: Vec<(ModuleId, bool)>) {
+        for (contract, applied) in results {
+            let id = ContractId::from_bytes(contract);
+
+            if let Some(upgrade) = self.pending.remove(&id) {
+                let code_hash = rusk_abi::hash(upgrade.bytecode);
+
+                let status = if applied {
+                    self.code_hashes.insert(id, code_hash);
+                    UpgradeStatus::Applied
+                } else {
+                    UpgradeStatus::Failed
+                };
+
+                self.record(UpgradeEvent {
+                    contract,
+                    code_hash,
+                    status,
+                    height,
+                });
+            }
+        }
+    }
+
+    /// Records the hash of the bytecode and the owner of a newly deployed
+    /// contract.
+    pub fn set_deployed(
+        &mut self,
+        contract: ModuleId,
+        code_hash: BlsScalar,
+        owner: [u8; OWNER_SIZE],
+    ) {
+        let id = ContractId::from_bytes(contract);
+        self.code_hashes.insert(id, code_hash);
+        self.owners.insert(id, owner);
+    }
+
+    /// The owner of the given contract.
+    pub fn owner(contract: ModuleId) -> Option<[u8; OWNER_SIZE]> {
+        rusk_abi::owner(ContractId::from_bytes(contract))
+    }
+
+    /// The hash of the bytecode of the given contract, if it was deployed or
+    /// upgraded through a transaction.
+    pub fn code_hash(&self, contract: ModuleId) -> Option<BlsScalar> {
+        self.code_hashes
+            .get(&ContractId::from_bytes(contract))
+            .copied()
+    }
+
+    /// The owner the given contract was deployed with, if it was deployed
+    /// through a transaction.
+    pub fn deployed_owner(&self, contract: ModuleId) -> Option<[u8; OWNER_SIZE]> {
+        self.owners.get(&ContractId::from_bytes(contract)).copied()
+    }
+
+    /// The nonce the owner of a contract should sign the next upgrade with.
+    pub fn nonce(&self, contract: ModuleId) -> u64 {
+        self.nonces
+            .get(&ContractId::from_bytes(contract))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The events of all upgrades of the given contract.
+    pub fn history(&self, contract: ModuleId) -> Vec<UpgradeEvent> {
+        self.history
+            .get(&ContractId::from_bytes(contract))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn record(&mut self, event: UpgradeEvent) {
+        rusk_abi::emit(UPGRADE_TOPIC, event.clone());
+
+        self.history
+            .entry(ContractId::from_bytes(event.contract))
+            .or_default()
+            .push(event);
+    }
+}
+
+fn panic_error(msg: &str) -> ContractError {
+    ContractError::Panic(String::from(msg))
+}
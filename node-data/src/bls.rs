@@ -12,12 +12,12 @@ use dusk_bytes::DeserializableSlice;
 use dusk_bytes::Serializable;
 
 use rand::rngs::StdRng;
-use rand_core::SeedableRng;
+use rand_core::{RngCore, SeedableRng};
 use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::warn;
 
 pub const PUBLIC_BLS_SIZE: usize = dusk_bls12_381_sign::PublicKey::SIZE;
@@ -145,6 +145,36 @@ pub fn load_keys(
     Ok((sk, PublicKey::new(pk)))
 }
 
+/// Saves consensus keys to a file encrypted with the given password, in the
+/// format read by [`load_keys`].
+pub fn save_keys(
+    path: &Path,
+    pwd: &str,
+    sk: &dusk_bls12_381_sign::SecretKey,
+    pk: &dusk_bls12_381_sign::PublicKey,
+) -> anyhow::Result<()> {
+    let keys = BlsKeyPair {
+        secret_key_bls: base64::encode(sk.to_bytes()),
+        public_key_bls: base64::encode(pk.to_bytes()),
+    };
+    let bytes = serde_json::to_vec(&keys)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(pwd.as_bytes());
+    let hashed_pwd = hasher.finalize().to_vec();
+
+    fs::write(path, encrypt(&bytes, &hashed_pwd)).map_err(|e| {
+        anyhow::anyhow!("{} should be writable {e}", path.display())
+    })
+}
+
+/// Bls key pair helper structure
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BlsKeyPair {
+    secret_key_bls: String,
+    public_key_bls: String,
+}
+
 /// Fetches BLS public and secret keys from an encrypted consensus keys file.
 ///
 /// Panics on any error.
@@ -155,15 +185,6 @@ fn read_from_file(
     dusk_bls12_381_sign::PublicKey,
     dusk_bls12_381_sign::SecretKey,
 )> {
-    use serde::Deserialize;
-
-    /// Bls key pair helper structure
-    #[derive(Deserialize)]
-    struct BlsKeyPair {
-        secret_key_bls: String,
-        public_key_bls: String,
-    }
-
     // attempt to load and decode wallet
     println!("{path:?}");
     let ciphertext = fs::read(&path).map_err(|e| {
@@ -216,6 +237,15 @@ fn decrypt(data: &[u8], pwd: &[u8]) -> Result<Vec<u8>, BlockModeError> {
     cipher.decrypt_vec(enc)
 }
 
+fn encrypt(data: &[u8], pwd: &[u8]) -> Vec<u8> {
+    type Aes256Cbc = Cbc<Aes256, Pkcs7>;
+    let mut iv = [0u8; 16];
+    StdRng::from_entropy().fill_bytes(&mut iv);
+
+    let cipher = Aes256Cbc::new_from_slices(pwd, &iv).expect("valid data");
+    [&iv[..], &cipher.encrypt_vec(data)].concat()
+}
+
 /// Loads wallet files from $DUSK_WALLET_DIR and returns a vector of all loaded
 /// consensus keys.
 ///
@@ -240,3 +270,27 @@ pub fn load_provisioners_keys(
 
     keys
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_keys() {
+        let sk = SecretKey::random(&mut StdRng::seed_from_u64(0xbeef));
+        let pk = dusk_bls12_381_sign::PublicKey::from(&sk);
+
+        let path = std::env::temp_dir()
+            .join(format!("consensus-{}.keys", std::process::id()));
+        save_keys(&path, "password", &sk, &pk).unwrap();
+
+        let loaded = load_keys(path.display().to_string(), "password".into());
+        let wrong = load_keys(path.display().to_string(), "wrong".into());
+        fs::remove_file(&path).unwrap();
+
+        let (loaded_sk, loaded_pk) = loaded.unwrap();
+        assert_eq!(loaded_sk, sk);
+        assert_eq!(loaded_pk.inner(), &pk);
+        assert!(wrong.is_err());
+    }
+}
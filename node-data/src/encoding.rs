@@ -6,14 +6,15 @@
 
 use crate::bls::PublicKeyBytes;
 use crate::ledger::{
-    Block, Certificate, Header, IterationsInfo, Label, SpentTransaction,
+    Block, Certificate, EpochCheckpoint, Evidence, Header, IterationsInfo,
+    Label, ProvisionerStake, Signature, SpentTransaction, StakeEvent,
     StepVotes, Transaction,
 };
 use crate::message::payload::{
     QuorumType, Ratification, RatificationResult, ValidationResult, Vote,
 };
 use crate::message::{ConsensusHeader, SignInfo};
-use crate::Serializable;
+use crate::{Serializable, StepName};
 use std::io::{self, Read, Write};
 
 impl Serializable for Block {
@@ -130,6 +131,104 @@ impl Serializable for SpentTransaction {
     }
 }
 
+impl Serializable for StakeEvent {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        Self::write_var_le_bytes32(w, self.topic.as_bytes())?;
+        w.write_all(self.provisioner.inner())?;
+        w.write_all(&self.value.to_le_bytes())?;
+        w.write_all(&self.height.to_le_bytes())?;
+
+        Ok(())
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let topic = Self::read_var_le_bytes32(r)?;
+        let topic = String::from_utf8(topic)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let provisioner = PublicKeyBytes(Self::read_bytes(r)?);
+        let value = Self::read_u64_le(r)?;
+        let height = Self::read_u64_le(r)?;
+
+        Ok(Self {
+            topic,
+            provisioner,
+            value,
+            height,
+        })
+    }
+}
+
+impl Serializable for EpochCheckpoint {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.height.to_le_bytes())?;
+        w.write_all(&self.block_hash)?;
+        w.write_all(&self.state_root)?;
+        w.write_all(&self.prev_state_root)?;
+
+        let len = self.provisioners.len() as u32;
+        w.write_all(&len.to_le_bytes())?;
+        for p in &self.provisioners {
+            p.write(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let height = Self::read_u64_le(r)?;
+        let block_hash = Self::read_bytes(r)?;
+        let state_root = Self::read_bytes(r)?;
+        let prev_state_root = Self::read_bytes(r)?;
+
+        let len = Self::read_u32_le(r)?;
+        let provisioners = (0..len)
+            .map(|_| ProvisionerStake::read(r))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            height,
+            block_hash,
+            state_root,
+            prev_state_root,
+            provisioners,
+        })
+    }
+}
+
+impl Serializable for ProvisionerStake {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.provisioner.inner())?;
+        w.write_all(&self.value.to_le_bytes())?;
+        w.write_all(&self.reward.to_le_bytes())?;
+        w.write_all(&self.eligible_since.to_le_bytes())?;
+
+        Ok(())
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let provisioner = PublicKeyBytes(Self::read_bytes(r)?);
+        let value = Self::read_u64_le(r)?;
+        let reward = Self::read_u64_le(r)?;
+        let eligible_since = Self::read_u64_le(r)?;
+
+        Ok(Self {
+            provisioner,
+            value,
+            reward,
+            eligible_since,
+        })
+    }
+}
+
 impl Serializable for Header {
     fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
         self.marshal_hashable(w)?;
@@ -287,6 +386,93 @@ impl Serializable for IterationsInfo {
     }
 }
 
+impl Serializable for Evidence {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self {
+            Evidence::DoubleProposal { first, second } => {
+                w.write_all(&[0])?;
+                for (header, signature) in [first, second] {
+                    header.write(w)?;
+                    w.write_all(signature.inner())?;
+                }
+            }
+            Evidence::DoubleVote {
+                header,
+                step,
+                signer,
+                first,
+                second,
+            } => {
+                w.write_all(&[1])?;
+                header.write(w)?;
+                w.write_all(&[*step as u8])?;
+                w.write_all(signer.inner())?;
+                for (vote, signature) in [first, second] {
+                    vote.write(w)?;
+                    w.write_all(signature.inner())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let evidence = match Self::read_u8(r)? {
+            0 => {
+                let mut read_signed = || -> io::Result<_> {
+                    let header = Header::read(r)?;
+                    let signature = Signature::from(Self::read_bytes(r)?);
+                    Ok((Box::new(header), signature))
+                };
+                let first = read_signed()?;
+                let second = read_signed()?;
+                Evidence::DoubleProposal { first, second }
+            }
+            1 => {
+                let header = ConsensusHeader::read(r)?;
+                let step = match Self::read_u8(r)? {
+                    0 => StepName::Proposal,
+                    1 => StepName::Validation,
+                    2 => StepName::Ratification,
+                    _ => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "Invalid step",
+                        ))
+                    }
+                };
+                let signer = PublicKeyBytes(Self::read_bytes(r)?);
+                let mut read_signed = || -> io::Result<_> {
+                    let vote = Vote::read(r)?;
+                    let signature = Signature::from(Self::read_bytes(r)?);
+                    Ok((vote, signature))
+                };
+                let first = read_signed()?;
+                let second = read_signed()?;
+                Evidence::DoubleVote {
+                    header,
+                    step,
+                    signer,
+                    first,
+                    second,
+                }
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid evidence",
+                ))
+            }
+        };
+
+        Ok(evidence)
+    }
+}
+
 impl From<u8> for Label {
     fn from(value: u8) -> Self {
         match value {
@@ -444,6 +630,21 @@ mod tests {
         assert_serializable::<Block>();
     }
 
+    #[test]
+    fn test_encoding_evidence() {
+        assert_serializable::<Evidence>();
+    }
+
+    #[test]
+    fn test_encoding_stake_event() {
+        assert_serializable::<StakeEvent>();
+    }
+
+    #[test]
+    fn test_encoding_epoch_checkpoint() {
+        assert_serializable::<EpochCheckpoint>();
+    }
+
     #[test]
     fn test_encoding_ratification_result() {
         assert_serializable::<RatificationResult>();
@@ -6,9 +6,10 @@
 
 use crate::bls::{self, PublicKeyBytes};
 use crate::message::payload::{RatificationResult, Vote};
-use crate::Serializable;
+use crate::message::ConsensusHeader;
+use crate::{Serializable, StepName};
 
-use dusk_bytes::DeserializableSlice;
+use dusk_bytes::{DeserializableSlice, Serializable as DuskSerializable};
 use rusk_abi::hash::Hasher;
 use sha3::Digest;
 use std::io::{self, Read, Write};
@@ -42,6 +43,7 @@ pub struct Header {
     pub iteration: u8,
     pub prev_block_cert: Certificate,
     pub failed_iterations: IterationsInfo,
+    pub evidence: Vec<Evidence>,
 
     // Block hash
     pub hash: Hash,
@@ -108,6 +110,41 @@ pub struct SpentTransaction {
     pub err: Option<String>,
 }
 
+/// A change of a provisioner stake, as reported by the stake contract
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StakeEvent {
+    /// Kind of change, i.e. the topic the event was emitted with
+    pub topic: String,
+    pub provisioner: PublicKeyBytes,
+    pub value: u64,
+    pub height: u64,
+}
+
+/// A snapshot of the chain taken at an epoch boundary, which the chain can be
+/// reverted to
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(any(feature = "faker", test), derive(Dummy))]
+pub struct EpochCheckpoint {
+    pub height: u64,
+    pub block_hash: Hash,
+    pub state_root: Hash,
+    /// State root of the block preceding the checkpoint, needed to restore
+    /// the previous provisioners set when reverting
+    pub prev_state_root: Hash,
+    /// The provisioners set as of the checkpoint block
+    pub provisioners: Vec<ProvisionerStake>,
+}
+
+/// The stake of a provisioner recorded in an [`EpochCheckpoint`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(any(feature = "faker", test), derive(Dummy))]
+pub struct ProvisionerStake {
+    pub provisioner: PublicKeyBytes,
+    pub value: u64,
+    pub reward: u64,
+    pub eligible_since: u64,
+}
+
 impl Transaction {
     pub fn hash(&self) -> [u8; 32] {
         Hasher::digest(self.inner.to_hash_input_bytes()).to_bytes()
@@ -133,6 +170,13 @@ pub struct Certificate {
 }
 
 impl Header {
+    /// Computes the block hash out of the hashable fields.
+    pub(crate) fn hashable_digest(&self) -> io::Result<Hash> {
+        let mut hasher = sha3::Sha3_256::new();
+        self.marshal_hashable(&mut hasher)?;
+        Ok(hasher.finalize().into())
+    }
+
     /// Marshal hashable fields.
     pub(crate) fn marshal_hashable<W: Write>(
         &self,
@@ -154,6 +198,12 @@ impl Header {
         self.prev_block_cert.write(w)?;
         self.failed_iterations.write(w)?;
 
+        let evidence_len = self.evidence.len() as u32;
+        w.write_all(&evidence_len.to_le_bytes())?;
+        for e in &self.evidence {
+            e.write(w)?;
+        }
+
         Ok(())
     }
 
@@ -174,6 +224,11 @@ impl Header {
         let prev_block_cert = Certificate::read(r)?;
         let failed_iterations = IterationsInfo::read(r)?;
 
+        let evidence_len = Self::read_u32_le(r)?;
+        let evidence = (0..evidence_len)
+            .map(|_| Evidence::read(r))
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Header {
             version,
             height,
@@ -190,6 +245,7 @@ impl Header {
             cert: Default::default(),
             prev_block_cert,
             failed_iterations,
+            evidence,
         })
     }
 }
@@ -208,10 +264,7 @@ impl Block {
             return Ok(());
         }
 
-        let mut hasher = sha3::Sha3_256::new();
-        self.header.marshal_hashable(&mut hasher)?;
-
-        self.header.hash = hasher.finalize().into();
+        self.header.hash = self.header.hashable_digest()?;
         Ok(())
     }
 
@@ -384,6 +437,148 @@ impl IterationsInfo {
     }
 }
 
+/// Proof that a provisioner signed two conflicting consensus messages for the
+/// same round, iteration and step.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Evidence {
+    /// Two different candidate blocks signed by the same generator.
+    ///
+    /// The full headers are needed since a candidate signature only covers
+    /// the block hash.
+    DoubleProposal {
+        first: (Box<Header>, Signature),
+        second: (Box<Header>, Signature),
+    },
+    /// Two different votes cast by the same committee member.
+    DoubleVote {
+        header: ConsensusHeader,
+        step: StepName,
+        signer: PublicKeyBytes,
+        first: (Vote, Signature),
+        second: (Vote, Signature),
+    },
+}
+
+impl Evidence {
+    /// Returns the provisioner that equivocated.
+    pub fn signer(&self) -> &PublicKeyBytes {
+        match self {
+            Self::DoubleProposal { first, .. } => &first.0.generator_bls_pubkey,
+            Self::DoubleVote { signer, .. } => signer,
+        }
+    }
+
+    /// Returns the round the conflicting messages belong to.
+    pub fn round(&self) -> u64 {
+        match self {
+            Self::DoubleProposal { first, .. } => first.0.height,
+            Self::DoubleVote { header, .. } => header.round,
+        }
+    }
+
+    /// Checks that both messages are signed by the same provisioner, belong
+    /// to the same round, iteration and step and actually conflict.
+    pub fn verify(&self) -> Result<(), io::Error> {
+        let invalid =
+            |desc| Err(io::Error::new(io::ErrorKind::InvalidData, desc));
+
+        let (pk, signed) = match self {
+            Self::DoubleProposal { first, second } => {
+                let (a, b) = (&first.0, &second.0);
+                if a.generator_bls_pubkey != b.generator_bls_pubkey {
+                    return invalid("different generators");
+                }
+                if a.height != b.height
+                    || a.iteration != b.iteration
+                    || a.prev_block_hash != b.prev_block_hash
+                {
+                    return invalid("candidates of different iterations");
+                }
+
+                let a_hash = a.hashable_digest()?;
+                let b_hash = b.hashable_digest()?;
+                if a_hash == b_hash {
+                    return invalid("candidates do not conflict");
+                }
+
+                (
+                    a.generator_bls_pubkey,
+                    [(a_hash.to_vec(), first.1), (b_hash.to_vec(), second.1)],
+                )
+            }
+            Self::DoubleVote {
+                header,
+                step,
+                signer,
+                first,
+                second,
+            } => {
+                if first.0 == second.0 {
+                    return invalid("votes do not conflict");
+                }
+
+                let seed: &[u8] = match step {
+                    StepName::Validation => &[1u8],
+                    StepName::Ratification => &[2u8],
+                    StepName::Proposal => return invalid("invalid step"),
+                };
+                let signable = |vote: &Vote| {
+                    let mut signable = header.signable();
+                    signable.extend_from_slice(seed);
+                    vote.write(&mut signable).map(|_| signable)
+                };
+
+                (
+                    *signer,
+                    [
+                        (signable(&first.0)?, first.1),
+                        (signable(&second.0)?, second.1),
+                    ],
+                )
+            }
+        };
+
+        let pk = dusk_bls12_381_sign::PublicKey::from_slice(pk.inner())
+            .map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid signer")
+            })?;
+        let apk = dusk_bls12_381_sign::APK::from(&pk);
+
+        for (msg, sig) in signed {
+            let sig = dusk_bls12_381_sign::Signature::from_bytes(sig.inner())
+                .map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "bad signature")
+            })?;
+            if apk.verify(&sig, &msg).is_err() {
+                return invalid("signature mismatch");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts the evidence included in a block into the list of provisioners to
+/// be hard-slashed.
+pub fn to_equivocators(
+    evidence: &[Evidence],
+) -> Result<Vec<dusk_bls12_381_sign::PublicKey>, io::Error> {
+    evidence
+        .iter()
+        .map(|e| {
+            dusk_bls12_381_sign::PublicKey::from_slice(e.signer().inner())
+                .map_err(|e| {
+                    tracing::error!("Unable to deserialize equivocator: {e:?}");
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Error in deserialize",
+                    )
+                })
+        })
+        .collect()
+}
+
 /// Encode a byte array into a shortened HEX representation.
 pub fn to_str<const N: usize>(bytes: &[u8; N]) -> String {
     let e = hex::encode(bytes);
@@ -437,6 +632,17 @@ pub mod faker {
         }
     }
 
+    impl<T> Dummy<T> for StakeEvent {
+        fn dummy_with_rng<R: Rng + ?Sized>(_config: &T, rng: &mut R) -> Self {
+            StakeEvent {
+                topic: "reward".to_string(),
+                provisioner: Faker.fake_with_rng(rng),
+                value: rng.gen(),
+                height: rng.gen(),
+            }
+        }
+    }
+
     impl<T> Dummy<T> for PublicKeyBytes {
         fn dummy_with_rng<R: Rng + ?Sized>(_config: &T, rng: &mut R) -> Self {
             let rand_val = rng.gen::<[u8; 32]>();
@@ -463,6 +669,20 @@ pub mod faker {
         }
     }
 
+    impl<T> Dummy<T> for Evidence {
+        /// Creates a double vote evidence, since a double proposal one would
+        /// recursively fake block headers.
+        fn dummy_with_rng<R: Rng + ?Sized>(_config: &T, rng: &mut R) -> Self {
+            Evidence::DoubleVote {
+                header: Faker.fake_with_rng(rng),
+                step: StepName::Validation,
+                signer: Faker.fake_with_rng(rng),
+                first: Faker.fake_with_rng(rng),
+                second: Faker.fake_with_rng(rng),
+            }
+        }
+    }
+
     impl<T> Dummy<T> for IterationsInfo {
         fn dummy_with_rng<R: Rng + ?Sized>(_config: &T, rng: &mut R) -> Self {
             let cert_list = vec![
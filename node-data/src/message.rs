@@ -107,6 +107,12 @@ impl Serializable for Message {
             Payload::GetBlocks(p) => p.write(w),
             Payload::GetData(p) => p.write(w),
             Payload::Ratification(p) => p.write(w),
+            Payload::Evidence(p) => p.write(w),
+            Payload::GetStateSnapshot(p) => p.write(w),
+            Payload::StateSnapshot(p) => p.write(w),
+            Payload::GetStateChunk(p) => p.write(w),
+            Payload::StateChunk(p) => p.write(w),
+            Payload::Handshake(p) => p.write(w),
             Payload::Empty | Payload::ValidationResult(_) => Ok(()), /* internal message, not sent on the wire */
         }
     }
@@ -148,6 +154,24 @@ impl Serializable for Message {
                 Message::new_get_mempool(payload::GetMempool::read(r)?)
             }
             Topics::GetInv => Message::new_inv(payload::Inv::read(r)?),
+            Topics::Evidence => {
+                Message::new_evidence(ledger::Evidence::read(r)?)
+            }
+            Topics::GetStateSnapshot => Message::new_get_state_snapshot(
+                payload::GetStateSnapshot::read(r)?,
+            ),
+            Topics::StateSnapshot => {
+                Message::new_state_snapshot(payload::StateSnapshot::read(r)?)
+            }
+            Topics::GetStateChunk => {
+                Message::new_get_state_chunk(payload::GetStateChunk::read(r)?)
+            }
+            Topics::StateChunk => {
+                Message::new_state_chunk(payload::StateChunk::read(r)?)
+            }
+            Topics::Handshake => {
+                Message::new_handshake(payload::Handshake::read(r)?)
+            }
             Topics::Unknown => {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
@@ -273,6 +297,60 @@ impl Message {
         }
     }
 
+    /// Creates topics.Evidence  message
+    pub fn new_evidence(evidence: ledger::Evidence) -> Message {
+        Self {
+            topic: Topics::Evidence,
+            payload: Payload::Evidence(Box::new(evidence)),
+            ..Default::default()
+        }
+    }
+
+    /// Creates topics.GetStateSnapshot message
+    pub fn new_get_state_snapshot(p: payload::GetStateSnapshot) -> Message {
+        Self {
+            topic: Topics::GetStateSnapshot,
+            payload: Payload::GetStateSnapshot(p),
+            ..Default::default()
+        }
+    }
+
+    /// Creates topics.StateSnapshot message
+    pub fn new_state_snapshot(p: payload::StateSnapshot) -> Message {
+        Self {
+            topic: Topics::StateSnapshot,
+            payload: Payload::StateSnapshot(Box::new(p)),
+            ..Default::default()
+        }
+    }
+
+    /// Creates topics.GetStateChunk message
+    pub fn new_get_state_chunk(p: payload::GetStateChunk) -> Message {
+        Self {
+            topic: Topics::GetStateChunk,
+            payload: Payload::GetStateChunk(p),
+            ..Default::default()
+        }
+    }
+
+    /// Creates topics.StateChunk message
+    pub fn new_state_chunk(p: payload::StateChunk) -> Message {
+        Self {
+            topic: Topics::StateChunk,
+            payload: Payload::StateChunk(Box::new(p)),
+            ..Default::default()
+        }
+    }
+
+    /// Creates topics.Handshake message
+    pub fn new_handshake(p: payload::Handshake) -> Message {
+        Self {
+            topic: Topics::Handshake,
+            payload: Payload::Handshake(p),
+            ..Default::default()
+        }
+    }
+
     /// Creates a message with a validation_result
     pub fn from_validation_result(p: payload::ValidationResult) -> Message {
         Self {
@@ -374,6 +452,12 @@ pub enum Payload {
     GetBlocks(payload::GetBlocks),
     GetData(payload::GetData),
     CandidateResp(Box<payload::GetCandidateResp>),
+    Evidence(Box<ledger::Evidence>),
+    GetStateSnapshot(payload::GetStateSnapshot),
+    StateSnapshot(Box<payload::StateSnapshot>),
+    GetStateChunk(payload::GetStateChunk),
+    StateChunk(Box<payload::StateChunk>),
+    Handshake(payload::Handshake),
 
     // Internal messages payload
     /// Result message passed from Validation step to Ratification step
@@ -386,6 +470,7 @@ pub enum Payload {
 pub mod payload {
     use crate::ledger::{self, to_str, Block, Certificate, Hash, StepVotes};
     use crate::Serializable;
+    use sha3::Digest;
     use std::fmt;
     use std::io::{self, Read, Write};
 
@@ -891,6 +976,226 @@ pub mod payload {
             })
         }
     }
+
+    /// Requests the snapshot of the VM state at the last final block
+    #[derive(Debug, Clone, Default)]
+    pub struct GetStateSnapshot {}
+
+    impl Serializable for GetStateSnapshot {
+        fn write<W: Write>(&self, _w: &mut W) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn read<R: Read>(_r: &mut R) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            Ok(GetStateSnapshot::default())
+        }
+    }
+
+    /// Describes a snapshot of the VM state at a final block.
+    ///
+    /// The state itself is served in chunks, each one to be checked against
+    /// its hash in `chunk_hashes`.
+    #[derive(Debug, Clone, Default)]
+    pub struct StateSnapshot {
+        /// Header of the final block the state belongs to
+        pub header: ledger::Header,
+        /// Transactions of the final block
+        pub txs: Vec<ledger::SpentTransaction>,
+        /// Header of the block following the final one, whose certificate
+        /// attests the final block
+        pub next: ledger::Header,
+        pub chunk_hashes: Vec<Hash>,
+    }
+
+    impl Serializable for StateSnapshot {
+        fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+            self.header.write(w)?;
+
+            let txs_len = self.txs.len() as u32;
+            w.write_all(&txs_len.to_le_bytes())?;
+            for tx in &self.txs {
+                tx.write(w)?;
+            }
+
+            self.next.write(w)?;
+
+            let chunks_len = self.chunk_hashes.len() as u32;
+            w.write_all(&chunks_len.to_le_bytes())?;
+            for hash in &self.chunk_hashes {
+                w.write_all(hash)?;
+            }
+
+            Ok(())
+        }
+
+        fn read<R: Read>(r: &mut R) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let header = ledger::Header::read(r)?;
+
+            let txs_len = Self::read_u32_le(r)?;
+            let txs = (0..txs_len)
+                .map(|_| ledger::SpentTransaction::read(r))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let next = ledger::Header::read(r)?;
+
+            let chunks_len = Self::read_u32_le(r)?;
+            let chunk_hashes = (0..chunks_len)
+                .map(|_| Self::read_bytes(r))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Self {
+                header,
+                txs,
+                next,
+                chunk_hashes,
+            })
+        }
+    }
+
+    /// Requests a chunk of the snapshot of the VM state with the given root
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct GetStateChunk {
+        pub state_root: Hash,
+        pub index: u32,
+    }
+
+    impl Serializable for GetStateChunk {
+        fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+            w.write_all(&self.state_root)?;
+            w.write_all(&self.index.to_le_bytes())?;
+
+            Ok(())
+        }
+
+        fn read<R: Read>(r: &mut R) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let state_root = Self::read_bytes(r)?;
+            let index = Self::read_u32_le(r)?;
+
+            Ok(Self { state_root, index })
+        }
+    }
+
+    #[derive(Clone, Default, PartialEq, Eq)]
+    pub struct StateChunk {
+        pub state_root: Hash,
+        pub index: u32,
+        pub data: Vec<u8>,
+    }
+
+    impl StateChunk {
+        /// Hash of the chunk data, as listed in the [`StateSnapshot`]
+        pub fn hash(&self) -> Hash {
+            sha3::Sha3_256::digest(&self.data).into()
+        }
+    }
+
+    impl fmt::Debug for StateChunk {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("StateChunk")
+                .field("state_root", &to_str(&self.state_root))
+                .field("index", &self.index)
+                .field("len", &self.data.len())
+                .finish()
+        }
+    }
+
+    impl Serializable for StateChunk {
+        fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+            w.write_all(&self.state_root)?;
+            w.write_all(&self.index.to_le_bytes())?;
+            Self::write_var_le_bytes32(w, &self.data)?;
+
+            Ok(())
+        }
+
+        fn read<R: Read>(r: &mut R) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let state_root = Self::read_bytes(r)?;
+            let index = Self::read_u32_le(r)?;
+            let data = Self::read_var_le_bytes32(r)?;
+
+            Ok(Self {
+                state_root,
+                index,
+                data,
+            })
+        }
+    }
+
+    /// Services a node provides to its peers, besides relaying blocks and
+    /// consensus messages.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Capabilities(pub u64);
+
+    impl Capabilities {
+        /// Serves state snapshots to nodes fast syncing
+        pub const FAST_SYNC: Self = Self(1);
+        /// Decodes frames compressed with zstd
+        pub const COMPRESSION: Self = Self(2);
+
+        const NAMES: [(Self, &'static str); 2] = [
+            (Self::FAST_SYNC, "fast_sync"),
+            (Self::COMPRESSION, "compression"),
+        ];
+
+        pub fn contains(self, other: Self) -> bool {
+            self.0 & other.0 == other.0
+        }
+
+        pub fn union(self, other: Self) -> Self {
+            Self(self.0 | other.0)
+        }
+
+        /// Returns the names of the known capabilities.
+        pub fn names(self) -> Vec<&'static str> {
+            Self::NAMES
+                .iter()
+                .filter(|(c, _)| self.contains(*c))
+                .map(|(_, name)| *name)
+                .collect()
+        }
+    }
+
+    /// Announces the capabilities of a node to a peer it has just met
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct Handshake {
+        pub capabilities: Capabilities,
+        /// Whether the peer is asked to announce its capabilities in turn
+        pub reply: bool,
+    }
+
+    impl Serializable for Handshake {
+        fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+            w.write_all(&self.capabilities.0.to_le_bytes())?;
+            w.write_all(&[self.reply as u8])?;
+
+            Ok(())
+        }
+
+        fn read<R: Read>(r: &mut R) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let capabilities = Capabilities(Self::read_u64_le(r)?);
+            let reply = Self::read_u8(r)? != 0;
+
+            Ok(Self {
+                capabilities,
+                reply,
+            })
+        }
+    }
 }
 
 macro_rules! map_topic {
@@ -911,9 +1216,19 @@ pub enum Topics {
     GetInv = 14,     // NB: This is aliased as Inv in the golang impl
     GetCandidate = 46,
 
+    // Fast sync topics
+    GetStateSnapshot = 21,
+    StateSnapshot = 22,
+    GetStateChunk = 23,
+    StateChunk = 24,
+
+    // Peer management topics
+    Handshake = 25,
+
     // Fire-and-forget messaging
     Tx = 10,
     Block = 11,
+    Evidence = 20,
 
     // Consensus main loop topics
     GetCandidateResp = 15,
@@ -954,6 +1269,12 @@ impl From<u8> for Topics {
         map_topic!(v, Topics::Validation);
         map_topic!(v, Topics::Ratification);
         map_topic!(v, Topics::Quorum);
+        map_topic!(v, Topics::Evidence);
+        map_topic!(v, Topics::GetStateSnapshot);
+        map_topic!(v, Topics::StateSnapshot);
+        map_topic!(v, Topics::GetStateChunk);
+        map_topic!(v, Topics::StateChunk);
+        map_topic!(v, Topics::Handshake);
 
         Topics::Unknown
     }
@@ -1177,6 +1498,7 @@ mod tests {
                 ..Default::default()
             },
             failed_iterations: Default::default(),
+            evidence: vec![],
         };
 
         let sample_block =
@@ -1223,6 +1545,23 @@ mod tests {
                 ratification: ledger::StepVotes::new([2; 48], 98765),
             },
         });
+
+        assert_serialize(payload::GetStateChunk {
+            state_root: [3; 32],
+            index: 7,
+        });
+
+        assert_serialize(payload::StateChunk {
+            state_root: [3; 32],
+            index: 7,
+            data: vec![1, 2, 3, 4],
+        });
+
+        assert_serialize(payload::Handshake {
+            capabilities: payload::Capabilities::FAST_SYNC
+                .union(payload::Capabilities(1 << 63)),
+            reply: true,
+        });
     }
 
     fn assert_serialize<S: Serializable + PartialEq + core::fmt::Debug>(v: S) {
@@ -9,6 +9,7 @@ use std::time::Duration;
 
 use dusk_consensus::commons::RoundUpdate;
 use node::chain;
+use node::network::Pdu;
 
 use criterion::async_executor::FuturesExecutor;
 use criterion::measurement::WallTime;
@@ -25,9 +26,11 @@ use dusk_consensus::user::{
     cluster::Cluster, committee::Committee, provisioners::Provisioners,
     sortition::Config as SortitionConfig,
 };
+use node_data::ledger::faker::gen_dummy_tx;
 use node_data::message::payload::{
     QuorumType, RatificationResult, ValidationResult, Vote,
 };
+use node_data::message::Message;
 use node_data::{
     bls::PublicKey,
     ledger::{Certificate, StepVotes},
@@ -110,49 +113,68 @@ where
     r
 }
 
+/// Creates a certificate for the block following `mrb_header`, signed by
+/// the given number of provisioners.
+fn create_certificate(
+    mrb_header: &ledger::Header,
+    block_hash: [u8; 32],
+    iteration: u8,
+    provisioners_count: usize,
+) -> (Provisioners, Certificate) {
+    let mut keys = vec![];
+    let mut provisioners = Provisioners::empty();
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    for _ in 0..provisioners_count {
+        let sk = BlsSecretKey::random(rng);
+        let pk = BlsPublicKey::from(&sk);
+        let pk = PublicKey::new(pk);
+        keys.push((pk.clone(), sk));
+        provisioners.add_member_with_value(pk, 1000000000000)
+    }
+    let vote = Vote::Valid(block_hash);
+
+    let validation = create_step_votes(
+        mrb_header,
+        &vote,
+        StepName::Validation,
+        iteration,
+        &provisioners,
+        &keys[..],
+    );
+    let ratification = create_step_votes(
+        mrb_header,
+        &vote,
+        StepName::Ratification,
+        iteration,
+        &provisioners,
+        &keys[..],
+    );
+    let cert = Certificate {
+        result: RatificationResult::Success(vote),
+        validation,
+        ratification,
+    };
+
+    (provisioners, cert)
+}
+
 pub fn verify_block_cert(c: &mut Criterion) {
     with_group("verify_block_cert", c, |group| {
         for input in INPUTS {
             group.measurement_time(Duration::from_secs(input.measurement_time));
-            let mut keys = vec![];
-            let mut provisioners = Provisioners::empty();
-            let rng = &mut StdRng::seed_from_u64(0xbeef);
-            for _ in 0..input.provisioners {
-                let sk = BlsSecretKey::random(rng);
-                let pk = BlsPublicKey::from(&sk);
-                let pk = PublicKey::new(pk);
-                keys.push((pk.clone(), sk));
-                provisioners.add_member_with_value(pk, 1000000000000)
-            }
             let mrb_header = ledger::Header {
                 seed: [5; 48].into(),
                 ..Default::default()
             };
             let block_hash = [1; 32];
-            let vote = Vote::Valid(block_hash);
             let iteration = 0;
 
-            let validation = create_step_votes(
+            let (provisioners, cert) = create_certificate(
                 &mrb_header,
-                &vote,
-                StepName::Validation,
+                block_hash,
                 iteration,
-                &provisioners,
-                &keys[..],
+                input.provisioners,
             );
-            let ratification = create_step_votes(
-                &mrb_header,
-                &vote,
-                StepName::Ratification,
-                iteration,
-                &provisioners,
-                &keys[..],
-            );
-            let cert = Certificate {
-                result: RatificationResult::Success(Vote::Valid(block_hash)),
-                validation,
-                ratification,
-            };
 
             group.bench_function(
                 BenchmarkId::new(
@@ -178,6 +200,47 @@ pub fn verify_block_cert(c: &mut Criterion) {
     })
 }
 
+/// Measures the time spent compressing blocks sent on the wire, and
+/// decompressing them on receipt.
+pub fn compress_block(c: &mut Criterion) {
+    with_group("compress_block", c, |group| {
+        for txs_count in [0, 10, 100] {
+            let mrb_header = ledger::Header {
+                seed: [5; 48].into(),
+                ..Default::default()
+            };
+            let iteration = 0;
+
+            let (_, prev_block_cert) =
+                create_certificate(&mrb_header, [1; 32], iteration, 64);
+            let (_, cert) =
+                create_certificate(&mrb_header, [2; 32], iteration, 64);
+
+            let header = ledger::Header {
+                height: mrb_header.height + 1,
+                prev_block_cert,
+                cert,
+                ..Default::default()
+            };
+            let txs = (0..txs_count).map(gen_dummy_tx).collect();
+            let blk = ledger::Block::new(header, txs).expect("valid block");
+            let msg = Message::new_block(blk);
+
+            let compressed =
+                Pdu::encode(&msg, 0, Some(0)).expect("encodable message");
+
+            group.bench_function(
+                BenchmarkId::new("encode", format!("{txs_count} txs")),
+                |b| b.iter(|| Pdu::encode(&msg, 0, Some(0))),
+            );
+            group.bench_function(
+                BenchmarkId::new("decode", format!("{txs_count} txs")),
+                |b| b.iter(|| Pdu::decode(&mut &compressed[..])),
+            );
+        }
+    })
+}
+
 struct Input {
     provisioners: usize,
     measurement_time: u64, // secs
@@ -205,5 +268,5 @@ const INPUTS: &[Input] = &[
         measurement_time: 15,
     },
 ];
-criterion_group!(benches, verify_block_cert);
+criterion_group!(benches, verify_block_cert, compress_block);
 criterion_main!(benches);
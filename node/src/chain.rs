@@ -5,8 +5,12 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod acceptor;
+mod admin;
 mod consensus;
+mod download;
+mod evidence;
 mod fallback;
+mod fastsync;
 mod fsm;
 mod genesis;
 
@@ -14,11 +18,15 @@ mod header_validation;
 mod metrics;
 
 use self::acceptor::Acceptor;
+use self::admin::AdminRequest;
 use self::fsm::SimpleFSM;
+use self::header_validation::HeaderVerificationErr;
 use crate::database::rocksdb::MD_HASH_KEY;
 use crate::database::{Ledger, Metadata};
+use crate::network::Misbehaviour;
 use crate::{database, vm, Network};
 use crate::{LongLivedService, Message};
+pub use admin::{ChainAdmin, Command, CommandOutput};
 use anyhow::Result;
 use async_trait::async_trait;
 use dusk_consensus::commons::ConsensusError;
@@ -28,7 +36,7 @@ use node_data::message::AsyncQueue;
 use node_data::message::{Payload, Topics};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 
 use tokio::time::{sleep_until, Instant};
 use tracing::{error, info, warn};
@@ -39,16 +47,23 @@ const TOPICS: &[u8] = &[
     Topics::Validation as u8,
     Topics::Ratification as u8,
     Topics::Quorum as u8,
+    Topics::Evidence as u8,
 ];
 
 const ACCEPT_BLOCK_TIMEOUT_SEC: Duration = Duration::from_secs(20);
 const HEARTBEAT_SEC: Duration = Duration::from_secs(1);
+const ADMIN_QUEUE_SIZE: usize = 16;
 
 pub struct ChainSrv<N: Network, DB: database::DB, VM: vm::VMExecution> {
     /// Inbound wire messages queue
     inbound: AsyncQueue<Message>,
     keys_path: String,
     acceptor: Option<Arc<RwLock<Acceptor<N, DB, VM>>>>,
+    /// Commands issued by operators through [`ChainAdmin`] handles
+    admin: mpsc::Receiver<AdminRequest>,
+    admin_sender: mpsc::Sender<AdminRequest>,
+    /// Whether a node starting from genesis syncs the state from a snapshot
+    fast_sync: bool,
 }
 
 #[async_trait]
@@ -61,7 +76,32 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         db: Arc<RwLock<DB>>,
         vm: Arc<RwLock<VM>>,
     ) -> anyhow::Result<()> {
-        let mrb = Self::load_most_recent_block(db.clone(), vm.clone()).await?;
+        let mut mrb =
+            Self::load_most_recent_block(db.clone(), vm.clone()).await?;
+
+        if self.fast_sync && mrb.inner().header().height == 0 {
+            LongLivedService::<N, DB, VM>::add_routes(
+                self,
+                fastsync::TOPICS,
+                self.inbound.clone(),
+                &network,
+            )
+            .await?;
+
+            let genesis_state_root = mrb.inner().header().state_hash;
+            match fastsync::fast_sync(
+                &self.inbound,
+                &network,
+                &db,
+                &vm,
+                genesis_state_root,
+            )
+            .await
+            {
+                Ok(blk) => mrb = blk,
+                Err(err) => warn!(event = "fast sync failed", ?err),
+            }
+        }
 
         let state_hash = mrb.inner().header().state_hash;
         let provisioners_list = vm.read().await.get_provisioners(state_hash)?;
@@ -166,6 +206,13 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
                             if let Err(e) = fsm.on_event(blk, &msg).await  {
                                  error!(event = "fsm::on_event failed", src = "wire", err = format!("{}",e));
+                                 // Only blocks failing verification are blamed on
+                                 // the peer, unlike ones out of order or local
+                                 // errors
+                                 let forged = HeaderVerificationErr::is_cause_of(&e);
+                                 if let (true, Some(metadata)) = (forged, &msg.metadata) {
+                                     network.read().await.report_peer(metadata.src_addr, Misbehaviour::InvalidBlock);
+                                 }
                             } else {
                                 timeout = Self::next_timeout();
                             }
@@ -180,12 +227,36 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                                 warn!("msg discarded: {e}");
                             }
                         }
+                        // Pool a valid evidence and propagate it
+                        Payload::Evidence(evidence) => {
+                            match acc.read().await.add_evidence(evidence).await {
+                                Ok(true) => {
+                                    if let Err(e) = network.read().await.broadcast(&msg).await {
+                                        warn!("Unable to broadcast evidence {e}");
+                                    }
+                                }
+                                Ok(false) => {}
+                                Err(e) => warn!("evidence discarded: {e}"),
+                            }
+                        }
+                        // Late responses to fast sync requests
+                        Payload::StateSnapshot(_) | Payload::StateChunk(_) => {}
                         _ => warn!("invalid inbound message"),
                     }
                 },
                 // Re-routes messages originated from Consensus (upper) layer to the network layer.
                 recv = &mut outbound_chan.recv() => {
                     let msg = recv?;
+
+                    // An evidence detected by the local consensus is pooled
+                    // as well, to be included in our own blocks
+                    if let Payload::Evidence(evidence) = &msg.payload {
+                        if let Err(e) = acc.read().await.add_evidence(evidence).await {
+                            warn!("evidence discarded: {e}");
+                            continue;
+                        }
+                    }
+
                     if let Err(e) = network.read().await.broadcast(&msg).await {
                         warn!("Unable to re-route message {e}");
                     }
@@ -194,6 +265,11 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                 _ = sleep_until(timeout) => {
                     fsm.on_idle(ACCEPT_BLOCK_TIMEOUT_SEC).await;
                     timeout = Self::next_timeout();
+                },
+                // Handles commands issued by operators
+                Some((cmd, reply)) = self.admin.recv() => {
+                    let output = admin::execute(&mut *acc.write().await, cmd).await;
+                    let _ = reply.send(output);
                 },
                  // Handles heartbeat event
                 _ = sleep_until(heartbeat) => {
@@ -215,13 +291,29 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
 impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
     pub fn new(keys_path: String) -> Self {
+        let (admin_sender, admin) = mpsc::channel(ADMIN_QUEUE_SIZE);
         Self {
             inbound: AsyncQueue::unbounded(),
             keys_path,
             acceptor: None,
+            admin,
+            admin_sender,
+            fast_sync: false,
         }
     }
 
+    /// Enables syncing the state from a snapshot served by peers, when
+    /// starting from genesis.
+    pub fn with_fast_sync(mut self, enabled: bool) -> Self {
+        self.fast_sync = enabled;
+        self
+    }
+
+    /// Returns a handle issuing operator commands to this service.
+    pub fn admin(&self) -> ChainAdmin {
+        ChainAdmin(self.admin_sender.clone())
+    }
+
     /// Load both most recent and last_finalized blocks from persisted ledger.
     ///
     /// Panics
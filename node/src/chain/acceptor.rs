@@ -12,31 +12,38 @@ use dusk_consensus::config::{
     CONSENSUS_ROLLING_FINALITY_THRESHOLD, MAX_STEP_TIMEOUT, MIN_STEP_TIMEOUT,
 };
 use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
+use dusk_consensus::IterationState;
 use node_data::bls::PublicKey;
 use node_data::ledger::{
-    self, to_str, Block, BlockWithLabel, Label, Seed, SpentTransaction,
+    self, to_str, Block, BlockWithLabel, EpochCheckpoint, Evidence, Label,
+    ProvisionerStake, Seed, SpentTransaction,
 };
 use node_data::message::AsyncQueue;
 use node_data::message::Payload;
 
 use node_data::{Serializable, StepName};
-use stake_contract_types::Unstake;
+use stake_contract_types::{Unstake, EPOCH};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 use super::consensus::Task;
+use crate::chain::evidence;
 use crate::chain::header_validation::Validator;
 use crate::chain::metrics::AverageElapsedTime;
 use crate::database::rocksdb::{
     MD_AVG_PROPOSAL, MD_AVG_RATIFICATION, MD_AVG_VALIDATION, MD_HASH_KEY,
     MD_STATE_ROOT_KEY,
 };
+use crate::metrics::metrics;
 
 const DUSK: u64 = 1_000_000_000;
 const MINIMUM_STAKE: u64 = 1_000 * DUSK;
 
+/// Maximum number of epoch checkpoints kept, the oldest being dropped first.
+const MAX_EPOCH_CHECKPOINTS: usize = 4;
+
 #[allow(dead_code)]
 pub(crate) enum RevertTarget {
     Commit([u8; 32]),
@@ -100,6 +107,10 @@ impl ProvisionerChange {
     fn is_stake(&self) -> bool {
         matches!(self, ProvisionerChange::Stake(_))
     }
+
+    fn is_unstake(&self) -> bool {
+        matches!(self, ProvisionerChange::Unstake(_))
+    }
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
@@ -120,20 +131,27 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         let mrb_height = mrb.inner().header().height;
         let mrb_state_hash = mrb.inner().header().state_hash;
 
+        metrics().chain_height.set(mrb_height as i64);
+
         let mut provisioners_list = ContextProvisioners::new(provisioners_list);
 
         if mrb.inner().header().height > 0 {
-            let (prev_header, _) = db
-                .read()
-                .await
-                .view(|t| {
-                    t.fetch_block_header(&mrb.inner().header().prev_block_hash)
-                })?
-                .expect("Previous block to be found");
+            let prev_header = db.read().await.view(|t| {
+                t.fetch_block_header(&mrb.inner().header().prev_block_hash)
+            })?;
 
-            let prev_provisioners =
-                vm.read().await.get_provisioners(prev_header.state_hash)?;
-            provisioners_list.set_previous(prev_provisioners);
+            // The ledger of a fast synced node starts at the snapshot block,
+            // so the block preceding it is not stored
+            match prev_header {
+                Some((prev_header, _)) => {
+                    let prev_provisioners = vm
+                        .read()
+                        .await
+                        .get_provisioners(prev_header.state_hash)?;
+                    provisioners_list.set_previous(prev_provisioners);
+                }
+                None => warn!("Previous block not found"),
+            }
         }
 
         let acc = Self {
@@ -154,6 +172,10 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             state_root = hex::encode(state_root),
         );
 
+        // Keep the VM commits of the stored checkpoints across restarts
+        let checkpoints = db.read().await.view(|t| t.fetch_checkpoints())?;
+        Self::retain_checkpoint_commits(&*vm.read().await, &checkpoints)?;
+
         // Detect a consistency issue between VM and Ledger states.
         if mrb_height > 0 && mrb_state_hash != state_root {
             info!("revert to last finalized state");
@@ -218,6 +240,28 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         Ok(())
     }
 
+    /// Verifies an equivocation evidence and adds it to the pool of the
+    /// evidence to be included in the next blocks.
+    ///
+    /// Returns false if the evidence was already known.
+    pub(crate) async fn add_evidence(
+        &self,
+        evidence: &Evidence,
+    ) -> anyhow::Result<bool> {
+        let next_height = self.get_curr_height().await + 1;
+        let provisioners_list = self.provisioners_list.read().await;
+
+        evidence::verify_inclusion(
+            evidence,
+            next_height,
+            provisioners_list.current(),
+        )?;
+
+        let task = self.task.read().await;
+        let added = task.evidence.write().await.insert(evidence.clone());
+        Ok(added)
+    }
+
     fn selective_update(
         blk: &Block,
         txs: &[SpentTransaction],
@@ -235,6 +279,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             let mut new_prov = provisioners_list.current().clone();
             for change in changed_prov {
                 let is_stake = change.is_stake();
+                let is_unstake = change.is_unstake();
                 info!(event = "provisioner_update", src, ?change);
                 let pk = change.into_public_key();
                 let prov = pk.to_bs58();
@@ -248,9 +293,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                         debug!(event = "old_stake", src, prov, ?replaced);
                     }
                     _ => {
-                        let removed = new_prov.remove_stake(&pk).ok_or(
-                            anyhow::anyhow!("Removed a not existing stake"),
-                        )?;
+                        let removed = new_prov.remove_stake(&pk);
+                        // An unstaked key may already have been removed by a
+                        // previous change in the same block, since its funds
+                        // are unbonding rather than staked.
+                        if removed.is_none() && !is_unstake {
+                            anyhow::bail!("Removed a not existing stake")
+                        };
                         debug!(event = "removed_stake", src, prov, ?removed);
                     }
                 }
@@ -276,9 +325,31 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             changed_provisioners.push(ProvisionerChange::Slash(slashed));
         }
 
+        // Update provisioners if an equivocator has been hard-slashed, unless
+        // already slashed as a missed generator
+        for e in &blk.header().evidence {
+            let bytes = e.signer();
+            if blk
+                .header()
+                .failed_iterations
+                .to_missed_generators_bytes()
+                .any(|pk| pk == bytes)
+            {
+                continue;
+            }
+
+            let slashed = bytes.0.try_into().map_err(|e| {
+                anyhow::anyhow!("Cannot deserialize bytes {e:?}")
+            })?;
+            changed_provisioners.push(ProvisionerChange::Slash(slashed));
+        }
+
         // FIX_ME: This relies on the stake contract being called only by the
         // transfer contract. We should change this once third-party contracts
         // hit the chain.
+        //
+        // Claiming unstaked funds is not a provisioner change: the funds left
+        // the provisioner set when `unstake` moved them to unbonding.
         let stake_calls =
             txs.iter().filter(|t| t.err.is_none()).filter_map(|t| {
                 match &t.inner.inner.call {
@@ -372,6 +443,11 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
 
         *mrb = BlockWithLabel::new_with_label(blk.clone(), label);
 
+        metrics().chain_height.set(blk.header().height as i64);
+        if mrb.is_final() {
+            metrics().finalized_height.set(blk.header().height as i64);
+        }
+
         Ok(())
     }
 
@@ -458,10 +534,12 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         {
             let vm = self.vm.write().await;
             let txs = self.db.read().await.update(|t| {
-                let (txs, verification_output) = if blk.is_final() {
-                    vm.finalize(blk.inner())?
+                let (txs, verification_output, stake_events) = if blk.is_final()
+                {
+                    metrics()
+                        .vm_call("finalize", || vm.finalize(blk.inner()))?
                 } else {
-                    vm.accept(blk.inner())?
+                    metrics().vm_call("accept", || vm.accept(blk.inner()))?
                 };
 
                 assert_eq!(header.state_hash, verification_output.state_root);
@@ -469,6 +547,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
 
                 // Store block with updated transactions with Error and GasSpent
                 t.store_block(header, &txs, blk.label())?;
+                t.store_stake_events(header.height, &stake_events)?;
 
                 Ok(txs)
             })?;
@@ -491,6 +570,19 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                 info!("Slashed {}", slashed.to_base58())
             }
 
+            for e in &header.evidence {
+                info!(
+                    "Hard-slashed {} for equivocation",
+                    e.signer().to_base58()
+                )
+            }
+
+            // Included evidence are not to be proposed anymore
+            task.evidence
+                .write()
+                .await
+                .on_accepted(&header.evidence, header.height + 1);
+
             let selective_update = Self::selective_update(
                 blk.inner(),
                 &txs,
@@ -505,6 +597,16 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                 provisioners_list.update_and_swap(new_prov)
             }
 
+            if header.height > 0 && header.height % EPOCH == 0 {
+                self.store_checkpoint(
+                    header,
+                    mrb.inner().header().state_hash,
+                    provisioners_list.current(),
+                    &vm,
+                )
+                .await?;
+            }
+
             // Update most_recent_block
             *mrb = blk;
 
@@ -523,6 +625,17 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             Ok(())
         })?;
 
+        let metrics = metrics();
+        metrics.chain_height.set(mrb.inner().header().height as i64);
+        if mrb.is_final() {
+            metrics
+                .finalized_height
+                .set(mrb.inner().header().height as i64);
+        }
+        metrics
+            .block_iteration
+            .observe(mrb.inner().header().iteration as f64);
+
         let fsv_bitset = mrb.inner().header().cert.validation.bitset;
         let ssv_bitset = mrb.inner().header().cert.ratification.bitset;
 
@@ -591,7 +704,28 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
 
                 anyhow::Ok(state_hash)
             }
-            RevertTarget::LastEpoch => unimplemented!(),
+            RevertTarget::LastEpoch => {
+                let checkpoint = self
+                    .db
+                    .read()
+                    .await
+                    .view(|t| t.fetch_checkpoints())?
+                    .into_iter()
+                    .rev()
+                    .find(|c| c.height <= curr_height)
+                    .ok_or_else(|| anyhow!("no epoch checkpoint found"))?;
+
+                let vm = self.vm.read().await;
+                let state_hash = vm.revert(checkpoint.state_root)?;
+
+                info!(
+                    event = "vm reverted",
+                    state_root = hex::encode(state_hash),
+                    epoch_height = checkpoint.height,
+                );
+
+                anyhow::Ok(state_hash)
+            }
         }?;
 
         // Delete any block until we reach the target_state_hash, the
@@ -642,6 +776,15 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             return Err(anyhow!("Failed to revert to proper state"));
         }
 
+        // Drop the checkpoints of the deleted blocks
+        let checkpoints = self.db.read().await.update(|t| {
+            let mut checkpoints = t.fetch_checkpoints()?;
+            checkpoints.retain(|c| c.height <= blk.header().height);
+            t.store_checkpoints(&checkpoints)?;
+            anyhow::Ok(checkpoints)
+        })?;
+        Self::retain_checkpoint_commits(&*self.vm.read().await, &checkpoints)?;
+
         // Update blockchain tip to be the one we reverted to.
         info!(
             event = "updating blockchain tip",
@@ -653,6 +796,69 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         self.update_most_recent_block(&blk, label).await
     }
 
+    /// Records the block as an epoch checkpoint, keeping the VM commits
+    /// needed to revert to it.
+    async fn store_checkpoint(
+        &self,
+        header: &ledger::Header,
+        prev_state_root: [u8; 32],
+        provisioners: &Provisioners,
+        vm: &VM,
+    ) -> Result<()> {
+        let provisioners = provisioners
+            .iter()
+            .map(|(pk, stake)| ProvisionerStake {
+                provisioner: *pk.bytes(),
+                value: stake.value(),
+                reward: stake.reward,
+                eligible_since: stake.eligible_since,
+            })
+            .collect();
+
+        let checkpoint = EpochCheckpoint {
+            height: header.height,
+            block_hash: header.hash,
+            state_root: header.state_hash,
+            prev_state_root,
+            provisioners,
+        };
+
+        let checkpoints = self.db.read().await.update(|t| {
+            let mut checkpoints = t.fetch_checkpoints()?;
+            checkpoints.retain(|c| c.height < checkpoint.height);
+            checkpoints.push(checkpoint);
+
+            let excess =
+                checkpoints.len().saturating_sub(MAX_EPOCH_CHECKPOINTS);
+            checkpoints.drain(..excess);
+
+            t.store_checkpoints(&checkpoints)?;
+            anyhow::Ok(checkpoints)
+        })?;
+
+        Self::retain_checkpoint_commits(vm, &checkpoints)?;
+
+        info!(
+            event = "epoch checkpoint",
+            height = header.height,
+            state_root = hex::encode(header.state_hash),
+        );
+
+        Ok(())
+    }
+
+    fn retain_checkpoint_commits(
+        vm: &VM,
+        checkpoints: &[EpochCheckpoint],
+    ) -> Result<()> {
+        let state_roots = checkpoints
+            .iter()
+            .flat_map(|c| [c.state_root, c.prev_state_root])
+            .collect();
+
+        vm.retain_commits(state_roots)
+    }
+
     /// Spawns consensus algorithm after aborting currently running one
     pub(crate) async fn restart_consensus(&mut self) {
         let mut task = self.task.write().await;
@@ -678,6 +884,26 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         );
     }
 
+    /// Aborts the running consensus task, and stops spawning new ones until
+    /// resumed.
+    pub(crate) async fn pause_consensus(&self) {
+        let mut task = self.task.write().await;
+        task.set_paused(true);
+        task.abort_with_wait().await;
+        info!(event = "consensus paused");
+    }
+
+    /// Spawns consensus again after it has been paused.
+    pub(crate) async fn resume_consensus(&mut self) {
+        self.task.write().await.set_paused(false);
+        self.restart_consensus().await;
+    }
+
+    /// Returns a snapshot of the iteration being executed by consensus.
+    pub(crate) async fn iteration_state(&self) -> Option<IterationState> {
+        self.task.read().await.iteration_state()
+    }
+
     pub(crate) async fn get_curr_height(&self) -> u64 {
         self.mrb.read().await.inner().header().height
     }
@@ -687,10 +913,6 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         self.mrb.read().await.inner().header().clone()
     }
 
-    pub(crate) async fn get_curr_hash(&self) -> [u8; 32] {
-        self.mrb.read().await.inner().header().hash
-    }
-
     pub(crate) async fn get_latest_final_block(&self) -> Result<Block> {
         let mrb = self.mrb.read().await;
         if mrb.is_final() {
@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::acceptor::{Acceptor, RevertTarget};
+use crate::{database, vm, Network};
+use anyhow::{anyhow, Result};
+use dusk_consensus::IterationState;
+use tokio::sync::{mpsc, oneshot};
+use tracing::info;
+
+/// Commands operators may issue to a running chain service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Reverts the chain to the last final block
+    RevertToFinal,
+    /// Reverts the chain to the last epoch checkpoint
+    RevertToEpoch,
+    /// Stops the consensus task until it is resumed
+    PauseConsensus,
+    /// Spawns the consensus task again after it has been paused
+    ResumeConsensus,
+    /// Reports the state of the iteration being executed
+    IterationState,
+}
+
+/// The outcome of a [`Command`].
+#[derive(Debug)]
+pub enum CommandOutput {
+    /// The chain has been reverted to the block at the given height
+    Reverted { height: u64 },
+    /// The consensus task has been paused or resumed
+    Done,
+    /// The state of the iteration being executed, if any
+    IterationState(Option<IterationState>),
+}
+
+pub(crate) type AdminRequest =
+    (Command, oneshot::Sender<Result<CommandOutput>>);
+
+/// Handle issuing [`Command`]s to a running chain service.
+#[derive(Debug, Clone)]
+pub struct ChainAdmin(pub(crate) mpsc::Sender<AdminRequest>);
+
+impl ChainAdmin {
+    /// Executes the command, waiting for its outcome.
+    pub async fn execute(&self, cmd: Command) -> Result<CommandOutput> {
+        let (sender, receiver) = oneshot::channel();
+        self.0
+            .send((cmd, sender))
+            .await
+            .map_err(|_| anyhow!("chain service is not running"))?;
+
+        receiver
+            .await
+            .map_err(|_| anyhow!("chain service dropped the command"))?
+    }
+}
+
+/// Executes an operator command on the acceptor.
+pub(crate) async fn execute<N, DB, VM>(
+    acc: &mut Acceptor<N, DB, VM>,
+    cmd: Command,
+) -> Result<CommandOutput>
+where
+    N: Network,
+    DB: database::DB,
+    VM: vm::VMExecution,
+{
+    info!(event = "admin command", ?cmd);
+
+    match cmd {
+        Command::RevertToFinal => {
+            acc.try_revert(RevertTarget::LastFinalizedState).await?;
+            acc.restart_consensus().await;
+            let height = acc.get_curr_height().await;
+            Ok(CommandOutput::Reverted { height })
+        }
+        Command::RevertToEpoch => {
+            acc.try_revert(RevertTarget::LastEpoch).await?;
+            acc.restart_consensus().await;
+            let height = acc.get_curr_height().await;
+            Ok(CommandOutput::Reverted { height })
+        }
+        Command::PauseConsensus => {
+            acc.pause_consensus().await;
+            Ok(CommandOutput::Done)
+        }
+        Command::ResumeConsensus => {
+            acc.resume_consensus().await;
+            Ok(CommandOutput::Done)
+        }
+        Command::IterationState => {
+            let state = acc.iteration_state().await;
+            Ok(CommandOutput::IterationState(state))
+        }
+    }
+}
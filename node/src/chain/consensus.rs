@@ -13,6 +13,7 @@ use dusk_consensus::operations::{
     CallParams, Error, Operations, Output, VerificationOutput,
 };
 use dusk_consensus::user::provisioners::ContextProvisioners;
+use dusk_consensus::IterationState;
 use node_data::ledger::{Block, Hash, Header};
 use node_data::message::payload::GetCandidate;
 use node_data::message::AsyncQueue;
@@ -21,11 +22,14 @@ use tokio::sync::{oneshot, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, trace, warn};
 
+use crate::chain::evidence::EvidencePool;
 use crate::chain::header_validation::Validator;
 use crate::chain::metrics::AverageElapsedTime;
 use crate::database::rocksdb::{
     MD_AVG_PROPOSAL, MD_AVG_RATIFICATION, MD_AVG_VALIDATION,
 };
+use crate::metrics::metrics;
+use crate::network::Misbehaviour;
 use node_data::{ledger, Serializable, StepName};
 use std::sync::Arc;
 use std::time::Duration;
@@ -39,6 +43,9 @@ pub(crate) struct Task {
     pub(crate) outbound: AsyncQueue<Message>,
     pub(crate) result: AsyncQueue<Result<Block, ConsensusError>>,
 
+    /// Equivocation evidence to be included in the next blocks
+    pub(crate) evidence: Arc<RwLock<EvidencePool>>,
+
     /// a pair of join_handle and cancel_chan of the running consensus task.
     ///
     /// None means no consensus is running,
@@ -47,6 +54,12 @@ pub(crate) struct Task {
     /// task id a counter to track consensus tasks
     task_id: u64,
 
+    /// Whether spawning consensus tasks has been paused by the operator
+    paused: bool,
+
+    /// Snapshot of the iteration executed by the last spawned task
+    iteration_state: Arc<std::sync::Mutex<Option<IterationState>>>,
+
     /// Loaded Consensus keys
     pub keys: (dusk_bls12_381_sign::SecretKey, node_data::bls::PublicKey),
 }
@@ -70,8 +83,11 @@ impl Task {
             main_inbound: AsyncQueue::unbounded(),
             outbound: AsyncQueue::unbounded(),
             result: AsyncQueue::unbounded(),
+            evidence: Default::default(),
             running_task: None,
             task_id: 0,
+            paused: false,
+            iteration_state: Default::default(),
             keys,
         })
     }
@@ -85,6 +101,11 @@ impl Task {
         network: &Arc<RwLock<N>>,
         base_timeout: TimeoutSet,
     ) {
+        if self.paused {
+            info!(event = "consensus paused, not spawning");
+            return;
+        }
+
         let current = provisioners_list.to_current();
         let c = Consensus::new(
             self.main_inbound.clone(),
@@ -94,12 +115,16 @@ impl Task {
             Arc::new(Mutex::new(Executor::new(
                 db,
                 vm,
+                network,
                 most_recent_block.header().clone(),
                 provisioners_list, // TODO: Avoid cloning
+                self.evidence.clone(),
             ))),
             Arc::new(Mutex::new(CandidateDB::new(db.clone(), network.clone()))),
         );
 
+        self.iteration_state = c.iteration_state();
+
         let ru = RoundUpdate::new(
             self.keys.1.clone(),
             self.keys.0,
@@ -161,6 +186,19 @@ impl Task {
     pub(crate) fn is_running(&self) -> bool {
         self.running_task.is_some()
     }
+
+    /// Sets whether spawning consensus tasks is paused.
+    pub(crate) fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns a snapshot of the iteration being executed, if any.
+    pub(crate) fn iteration_state(&self) -> Option<IterationState> {
+        if !self.is_running() {
+            return None;
+        }
+        self.iteration_state.lock().ok()?.clone()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -266,31 +304,39 @@ impl<DB: database::DB, N: Network> dusk_consensus::commons::Database
 }
 
 /// Implements Executor trait to mock Contract Storage calls.
-pub struct Executor<DB: database::DB, VM: vm::VMExecution> {
+pub struct Executor<DB: database::DB, VM: vm::VMExecution, N: Network> {
     db: Arc<RwLock<DB>>,
     vm: Arc<RwLock<VM>>,
+    network: Arc<RwLock<N>>,
     mrb_header: ledger::Header,
     provisioners: ContextProvisioners,
+    evidence: Arc<RwLock<EvidencePool>>,
 }
 
-impl<DB: database::DB, VM: vm::VMExecution> Executor<DB, VM> {
+impl<DB: database::DB, VM: vm::VMExecution, N: Network> Executor<DB, VM, N> {
     fn new(
         db: &Arc<RwLock<DB>>,
         vm: &Arc<RwLock<VM>>,
+        network: &Arc<RwLock<N>>,
         mrb_header: ledger::Header,
         provisioners: ContextProvisioners,
+        evidence: Arc<RwLock<EvidencePool>>,
     ) -> Self {
         Executor {
             db: db.clone(),
             vm: vm.clone(),
+            network: network.clone(),
             mrb_header,
             provisioners,
+            evidence,
         }
     }
 }
 
 #[async_trait::async_trait]
-impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
+impl<DB: database::DB, VM: vm::VMExecution, N: Network> Operations
+    for Executor<DB, VM, N>
+{
     async fn verify_block_header(
         &self,
         candidate_header: &Header,
@@ -321,7 +367,9 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
 
         let vm = self.vm.read().await;
 
-        Ok(vm.verify_state_transition(blk).map_err(|err| {
+        let vst =
+            metrics().vm_call("verify", || vm.verify_state_transition(blk));
+        Ok(vst.map_err(|err| {
             error!("failed to call VST {}", err);
             Error::Failed
         })?)
@@ -329,9 +377,22 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
 
     async fn execute_state_transition(
         &self,
-        params: CallParams,
+        mut params: CallParams,
     ) -> Result<Output, Error> {
         info!("executing state transition");
+
+        // Hard-slash any provisioner with a pending equivocation evidence
+        let evidence = self
+            .evidence
+            .read()
+            .await
+            .to_include(params.round, self.provisioners.current());
+        params.equivocators =
+            ledger::to_equivocators(&evidence).map_err(|err| {
+                error!("invalid evidence {err}");
+                Error::Failed
+            })?;
+
         let vm = self.vm.read().await;
 
         let db = self.db.read().await;
@@ -340,9 +401,13 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
                 let txs = view.get_txs_sorted_by_fee().map_err(|err| {
                     anyhow::anyhow!("failed to get mempool txs: {}", err)
                 })?;
-                let ret = vm.execute_state_transition(&params, txs).map_err(
-                    |err| anyhow::anyhow!("failed to call EST {}", err),
-                )?;
+                let ret = metrics()
+                    .vm_call("execute", || {
+                        vm.execute_state_transition(&params, txs)
+                    })
+                    .map_err(|err| {
+                        anyhow::anyhow!("failed to call EST {}", err)
+                    })?;
                 Ok(ret)
             })
             .map_err(|err: anyhow::Error| {
@@ -360,6 +425,7 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
             txs: executed_txs,
             verification_output,
             discarded_txs,
+            evidence,
         })
     }
 
@@ -369,6 +435,8 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
         step_name: StepName,
         elapsed: Duration,
     ) -> Result<(), Error> {
+        metrics().step_elapsed(step_name, elapsed);
+
         let db_key = match step_name {
             StepName::Proposal => MD_AVG_PROPOSAL,
             StepName::Validation => MD_AVG_VALIDATION,
@@ -399,4 +467,13 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
 
         Ok(())
     }
+
+    async fn report_invalid_msg(&self, msg: &Message) {
+        if let Some(metadata) = &msg.metadata {
+            self.network
+                .read()
+                .await
+                .report_peer(metadata.src_addr, Misbehaviour::InvalidMessage);
+        }
+    }
 }
@@ -0,0 +1,453 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use node_data::ledger::Block;
+
+/// Number of consecutive blocks requested to a peer at once.
+const RANGE_LEN: u64 = 16;
+
+/// Number of ranges a single peer may be downloading at the same time.
+const MAX_RANGES_PER_PEER: usize = 2;
+
+/// Time given to a peer to deliver all the blocks of a range.
+const RANGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum distance from the tip of the blocks being downloaded, bounding the
+/// number of blocks waiting to be accepted.
+const MAX_BLOCKS_AHEAD: u64 = 500;
+
+/// Score below which a peer is no longer requested any block.
+const MIN_PEER_SCORE: i32 = -10;
+
+const ACCEPTED_BLOCK_SCORE: i32 = 1;
+const INVALID_BLOCK_SCORE: i32 = -5;
+const EXPIRED_RANGE_SCORE: i32 = -2;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PeerStats {
+    score: i32,
+    /// Moving average of the time taken to deliver a whole range
+    latency: Option<Duration>,
+}
+
+impl PeerStats {
+    fn record_latency(&mut self, sample: Duration) {
+        self.latency = Some(match self.latency {
+            Some(latency) => (latency * 3 + sample) / 4,
+            None => sample,
+        });
+    }
+}
+
+/// Blocks requested to a peer.
+#[derive(Debug)]
+struct Request {
+    heights: Vec<u64>,
+    peer: SocketAddr,
+    sent_at: Instant,
+}
+
+/// Blocks to be requested, preferably not to the given peer.
+#[derive(Debug)]
+struct Retry {
+    heights: Vec<u64>,
+    excluded: Option<SocketAddr>,
+}
+
+/// Schedules the download of a range of blocks across multiple peers.
+///
+/// Heights are split into ranges that are requested in parallel to the best
+/// scoring peers. Ranges that are not delivered in time, as well as invalid
+/// blocks, are requested again to other peers. Downloaded blocks are handed
+/// over in order of height, as they have to be accepted sequentially.
+#[derive(Debug, Default)]
+pub(super) struct Downloader {
+    /// Height of the next block to be accepted
+    next: u64,
+    /// Height of the highest block known to the network
+    target: u64,
+    /// Height of the highest block requested for the first time
+    scheduled: u64,
+
+    retries: VecDeque<Retry>,
+    in_flight: Vec<Request>,
+    pool: BTreeMap<u64, (Block, Option<SocketAddr>)>,
+    peers: HashMap<SocketAddr, PeerStats>,
+}
+
+impl Downloader {
+    /// Creates a downloader of the blocks following `tip`, up to `target`.
+    pub fn new(tip: u64, target: u64) -> Self {
+        Self {
+            next: tip + 1,
+            target,
+            scheduled: tip,
+            ..Default::default()
+        }
+    }
+
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+
+    pub fn peers_count(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Returns true once all blocks up to the target have been accepted.
+    pub fn is_done(&self) -> bool {
+        self.next > self.target
+    }
+
+    /// Adds peers blocks may be requested to.
+    pub fn add_peers(&mut self, peers: impl IntoIterator<Item = SocketAddr>) {
+        for peer in peers {
+            self.peers.entry(peer).or_default();
+        }
+    }
+
+    /// Stores the block the download was started for, at the target height,
+    /// until it can be accepted.
+    pub fn on_target_block(&mut self, blk: Block, src: SocketAddr) {
+        let height = blk.header().height;
+        if height == self.target && height >= self.next {
+            self.pool.entry(height).or_insert((blk, Some(src)));
+        }
+    }
+
+    /// Stores a downloaded block until it can be accepted.
+    ///
+    /// Blocks not requested to the peer delivering them are dropped, so that
+    /// peers can neither fill the pool nor move the target.
+    pub fn on_block(
+        &mut self,
+        blk: Block,
+        src: Option<SocketAddr>,
+        now: Instant,
+    ) {
+        let height = blk.header().height;
+        let Some(src) = src else {
+            return;
+        };
+
+        let requested = self
+            .in_flight
+            .iter()
+            .any(|r| r.peer == src && r.heights.contains(&height));
+        if height < self.next || !requested {
+            return;
+        }
+
+        self.pool.entry(height).or_insert((blk, Some(src)));
+
+        // Complete the request of the peer once all blocks are downloaded
+        let pos = self.in_flight.iter().position(|r| {
+            r.peer == src
+                && r.heights.contains(&height)
+                && r.heights.iter().all(|h| self.is_downloaded(*h))
+        });
+
+        if let Some(pos) = pos {
+            let req = self.in_flight.swap_remove(pos);
+            if let Some(stats) = self.peers.get_mut(&src) {
+                stats
+                    .record_latency(now.saturating_duration_since(req.sent_at));
+            }
+        }
+    }
+
+    /// Takes the next block to be accepted, if downloaded, along with the
+    /// peer that delivered it.
+    pub fn pop_next(&mut self) -> Option<(Block, Option<SocketAddr>)> {
+        self.pool.remove(&self.next)
+    }
+
+    /// Records the acceptance of the block popped last.
+    pub fn on_accepted(&mut self, peer: Option<SocketAddr>) {
+        self.next += 1;
+        self.update_score(peer, ACCEPTED_BLOCK_SCORE);
+    }
+
+    /// Records the rejection of the block popped last, requesting it again to
+    /// another peer.
+    pub fn on_invalid(&mut self, peer: Option<SocketAddr>) {
+        self.update_score(peer, INVALID_BLOCK_SCORE);
+        self.retries.push_front(Retry {
+            heights: vec![self.next],
+            excluded: peer,
+        });
+    }
+
+    /// Cancels the requests not fulfilled in time, penalizing the peers and
+    /// scheduling the missing blocks to be requested again.
+    pub fn expire(&mut self, now: Instant) {
+        let (expired, in_flight) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition(|r| {
+                now.saturating_duration_since(r.sent_at) >= RANGE_TIMEOUT
+            });
+
+        self.in_flight = in_flight;
+
+        for req in expired {
+            self.update_score(Some(req.peer), EXPIRED_RANGE_SCORE);
+
+            let heights: Vec<_> = req
+                .heights
+                .into_iter()
+                .filter(|h| !self.is_downloaded(*h))
+                .collect();
+
+            if !heights.is_empty() {
+                self.retries.push_back(Retry {
+                    heights,
+                    excluded: Some(req.peer),
+                });
+            }
+        }
+    }
+
+    /// Assigns the blocks to be downloaded to the available peers, returning
+    /// the heights to request to each of them.
+    pub fn schedule(&mut self, now: Instant) -> Vec<(SocketAddr, Vec<u64>)> {
+        let mut requests = vec![];
+
+        while let Some(heights) = self.next_heights() {
+            let excluded = heights.excluded;
+
+            let Some(peer) = self.select_peer(excluded) else {
+                self.retries.push_front(heights);
+                break;
+            };
+
+            self.in_flight.push(Request {
+                heights: heights.heights.clone(),
+                peer,
+                sent_at: now,
+            });
+            requests.push((peer, heights.heights));
+        }
+
+        requests
+    }
+
+    /// Returns the next heights to be requested, retries coming first.
+    fn next_heights(&mut self) -> Option<Retry> {
+        while let Some(mut retry) = self.retries.pop_front() {
+            retry.heights.retain(|h| !self.is_downloaded(*h));
+            if !retry.heights.is_empty() {
+                return Some(retry);
+            }
+        }
+
+        let from = self.scheduled + 1;
+        let to = (from + RANGE_LEN - 1)
+            .min(self.target)
+            .min(self.next + MAX_BLOCKS_AHEAD);
+
+        if from > to {
+            return None;
+        }
+
+        self.scheduled = to;
+        Some(Retry {
+            heights: (from..=to).collect(),
+            excluded: None,
+        })
+    }
+
+    /// Selects the best scoring peer having room for another request,
+    /// avoiding the excluded one unless it is the only one available.
+    fn select_peer(&self, excluded: Option<SocketAddr>) -> Option<SocketAddr> {
+        let mut available: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, stats)| stats.score >= MIN_PEER_SCORE)
+            .filter(|(peer, _)| {
+                self.in_flight.iter().filter(|r| &r.peer == *peer).count()
+                    < MAX_RANGES_PER_PEER
+            })
+            .collect();
+
+        available.sort_by_key(|(peer, stats)| {
+            (
+                Some(**peer) == excluded,
+                -stats.score,
+                stats.latency.unwrap_or_default(),
+            )
+        });
+
+        available.first().map(|(peer, _)| **peer)
+    }
+
+    fn is_downloaded(&self, height: u64) -> bool {
+        height < self.next || self.pool.contains_key(&height)
+    }
+
+    fn update_score(&mut self, peer: Option<SocketAddr>, delta: i32) {
+        if let Some(stats) = peer.and_then(|p| self.peers.get_mut(&p)) {
+            stats.score += delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+    use node_data::ledger::Header;
+
+    fn block(height: u64) -> Block {
+        let header = Header {
+            height,
+            ..Faker.fake()
+        };
+        Block::new(header, vec![]).expect("block to be created")
+    }
+
+    fn peer(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn test_split_ranges_across_peers() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(10, 100);
+        downloader.add_peers([peer(1), peer(2)]);
+
+        let requests = downloader.schedule(now);
+
+        // Each peer is assigned the maximum number of ranges
+        assert_eq!(requests.len(), 2 * MAX_RANGES_PER_PEER);
+        let heights: Vec<_> =
+            requests.iter().flat_map(|(_, h)| h.clone()).collect();
+        assert_eq!(heights, (11..11 + 4 * RANGE_LEN).collect::<Vec<_>>());
+
+        for p in [peer(1), peer(2)] {
+            let count = requests.iter().filter(|(r, _)| *r == p).count();
+            assert_eq!(count, MAX_RANGES_PER_PEER);
+        }
+
+        // No peer has room for another range
+        assert!(downloader.schedule(now).is_empty());
+    }
+
+    #[test]
+    fn test_blocks_handed_over_in_order() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(0, 3);
+        downloader.add_peers([peer(1)]);
+        downloader.schedule(now);
+
+        downloader.on_block(block(2), Some(peer(1)), now);
+        downloader.on_block(block(3), Some(peer(1)), now);
+        assert!(downloader.pop_next().is_none());
+
+        downloader.on_block(block(1), Some(peer(1)), now);
+        for height in 1..=3 {
+            let (blk, src) = downloader.pop_next().expect("block available");
+            assert_eq!(blk.header().height, height);
+            downloader.on_accepted(src);
+        }
+
+        assert!(downloader.is_done());
+        assert!(downloader.in_flight.is_empty());
+        assert_eq!(downloader.peers[&peer(1)].score, 3);
+    }
+
+    #[test]
+    fn test_expired_range_requested_to_other_peer() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(0, RANGE_LEN);
+        downloader.add_peers([peer(1)]);
+
+        let requests = downloader.schedule(now);
+        assert_eq!(requests, vec![(peer(1), (1..=RANGE_LEN).collect())]);
+
+        // Only the first block is delivered in time
+        downloader.on_block(block(1), Some(peer(1)), now);
+        downloader.add_peers([peer(2)]);
+        downloader.expire(now + RANGE_TIMEOUT);
+
+        assert_eq!(downloader.peers[&peer(1)].score, EXPIRED_RANGE_SCORE);
+
+        let requests = downloader.schedule(now + RANGE_TIMEOUT);
+        assert_eq!(requests, vec![(peer(2), (2..=RANGE_LEN).collect())]);
+    }
+
+    #[test]
+    fn test_invalid_block_requested_again() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(0, 1);
+        downloader.add_peers([peer(1), peer(2)]);
+
+        let requests = downloader.schedule(now);
+        let (first, _) = requests[0];
+        let other = if first == peer(1) { peer(2) } else { peer(1) };
+
+        downloader.on_block(block(1), Some(first), now);
+        let (_, src) = downloader.pop_next().expect("block available");
+        downloader.on_invalid(src);
+
+        assert_eq!(downloader.peers[&first].score, INVALID_BLOCK_SCORE);
+        assert!(!downloader.is_done());
+
+        let requests = downloader.schedule(now);
+        assert_eq!(requests, vec![(other, vec![1])]);
+    }
+
+    #[test]
+    fn test_peers_ordered_by_score_and_latency() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(0, 1000);
+        downloader.add_peers([peer(1), peer(2), peer(3)]);
+
+        downloader.peers.get_mut(&peer(1)).unwrap().score = 5;
+        downloader.peers.get_mut(&peer(2)).unwrap().score = 5;
+        downloader.peers.get_mut(&peer(3)).unwrap().score = MIN_PEER_SCORE - 1;
+        downloader
+            .peers
+            .get_mut(&peer(1))
+            .unwrap()
+            .record_latency(Duration::from_secs(2));
+        downloader
+            .peers
+            .get_mut(&peer(2))
+            .unwrap()
+            .record_latency(Duration::from_secs(1));
+
+        let requests = downloader.schedule(now);
+        let peers: Vec<_> = requests.iter().map(|(p, _)| *p).collect();
+
+        // The banned peer is not requested any block
+        assert_eq!(peers, vec![peer(2), peer(2), peer(1), peer(1)]);
+    }
+
+    #[test]
+    fn test_unrequested_blocks_dropped() {
+        let now = Instant::now();
+        let mut downloader = Downloader::new(0, 2);
+        downloader.add_peers([peer(1)]);
+        downloader.schedule(now);
+
+        // Neither blocks above the target nor blocks from other peers are
+        // stored
+        downloader.on_block(block(20), Some(peer(1)), now);
+        downloader.on_block(block(1), Some(peer(2)), now);
+        downloader.on_block(block(1), None, now);
+        assert_eq!(downloader.target(), 2);
+        assert!(downloader.pool.is_empty());
+
+        // The block the download was started for is kept
+        downloader.on_target_block(block(2), peer(2));
+        downloader.on_block(block(1), Some(peer(1)), now);
+        assert_eq!(downloader.pool.len(), 2);
+    }
+}
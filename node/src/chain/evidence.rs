@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use anyhow::anyhow;
+use dusk_consensus::user::provisioners::Provisioners;
+use node_data::bls::{PublicKey, PublicKeyBytes};
+use node_data::ledger::Evidence;
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of blocks an evidence can be included after the round of
+/// the equivocation. It matches the stake contract epoch.
+pub(crate) const MAX_EVIDENCE_AGE: u64 = 2160;
+
+/// Pool of verified equivocation evidence waiting to be included in a block.
+///
+/// A single evidence per provisioner is kept, since any of them is enough to
+/// hard-slash it.
+#[derive(Debug, Default)]
+pub(crate) struct EvidencePool {
+    evidence: HashMap<PublicKeyBytes, Evidence>,
+}
+
+impl EvidencePool {
+    /// Adds an evidence to the pool.
+    ///
+    /// Returns false if an evidence against the same provisioner is already
+    /// pending.
+    pub(crate) fn insert(&mut self, evidence: Evidence) -> bool {
+        let signer = *evidence.signer();
+        if self.evidence.contains_key(&signer) {
+            return false;
+        }
+
+        self.evidence.insert(signer, evidence);
+        true
+    }
+
+    /// Returns the pending evidence that can be included in a block at the
+    /// given height.
+    pub(crate) fn to_include(
+        &self,
+        height: u64,
+        provisioners: &Provisioners,
+    ) -> Vec<Evidence> {
+        self.evidence
+            .values()
+            .filter(|e| verify_inclusion(e, height, provisioners).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Drops the evidence included in an accepted block, along with any
+    /// evidence that cannot be included anymore.
+    pub(crate) fn on_accepted(
+        &mut self,
+        included: &[Evidence],
+        next_height: u64,
+    ) {
+        for e in included {
+            self.evidence.remove(e.signer());
+        }
+
+        self.evidence.retain(|_, e| {
+            next_height.saturating_sub(e.round()) <= MAX_EVIDENCE_AGE
+        });
+    }
+}
+
+/// Verifies an evidence can be included in a block at the given height.
+///
+/// The evidence should be valid, not older than [`MAX_EVIDENCE_AGE`] and
+/// against a current provisioner.
+pub(crate) fn verify_inclusion(
+    evidence: &Evidence,
+    height: u64,
+    provisioners: &Provisioners,
+) -> anyhow::Result<()> {
+    evidence
+        .verify()
+        .map_err(|e| anyhow!("invalid evidence: {e}"))?;
+
+    let round = evidence.round();
+    if round >= height || height - round > MAX_EVIDENCE_AGE {
+        return Err(anyhow!("evidence out of range round: {round}"));
+    }
+
+    let signer: PublicKey = evidence
+        .signer()
+        .0
+        .try_into()
+        .map_err(|e| anyhow!("invalid evidence signer {e:?}"))?;
+
+    if provisioners.get_member(&signer).is_none() {
+        return Err(anyhow!("evidence signer is not a provisioner"));
+    }
+
+    Ok(())
+}
+
+/// Verifies all the evidence included in a block at the given height.
+pub(crate) fn verify_all(
+    evidence: &[Evidence],
+    height: u64,
+    provisioners: &Provisioners,
+) -> anyhow::Result<()> {
+    let mut signers = HashSet::new();
+    for e in evidence {
+        if !signers.insert(e.signer()) {
+            return Err(anyhow!("duplicated evidence"));
+        }
+        verify_inclusion(e, height, provisioners)?;
+    }
+
+    Ok(())
+}
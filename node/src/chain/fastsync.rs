@@ -0,0 +1,284 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure, Result};
+use dusk_consensus::merkle::merkle_root;
+use dusk_consensus::user::provisioners::Provisioners;
+use node_data::ledger::{self, Block, BlockWithLabel, Hash, Label};
+use node_data::message::payload::{
+    GetStateChunk, GetStateSnapshot, StateChunk, StateSnapshot, Vote,
+};
+use node_data::message::{AsyncQueue, Message, Payload, Topics};
+use tokio::sync::RwLock;
+use tokio::time::{timeout_at, Instant};
+use tracing::{info, warn};
+
+use super::fsm::REDUNDANCY_PEER_FACTOR;
+use super::header_validation::verify_block_cert;
+use crate::database::rocksdb::{
+    MD_FAST_SYNC_HEIGHT_KEY, MD_HASH_KEY, MD_STATE_ROOT_KEY,
+};
+use crate::database::{self, Ledger, Metadata};
+use crate::{vm, Network};
+
+/// Topics of the responses to fast sync requests.
+pub(super) const TOPICS: &[u8] =
+    &[Topics::StateSnapshot as u8, Topics::StateChunk as u8];
+
+/// Time given to peers to serve a valid state snapshot, before falling back to
+/// accepting every block.
+const FAST_SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Time to wait for the response to a single request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of times a chunk is requested before giving up on the peer.
+const MAX_CHUNK_ATTEMPTS: usize = 3;
+
+/// Syncs the VM state from a snapshot served by peers, instead of accepting
+/// every block since genesis.
+///
+/// The snapshot is taken at a final block, whose successor certificate must
+/// be signed by a committee drawn from the provisioners of a trusted local
+/// state: the last epoch checkpoint if any, or else the given genesis state.
+/// Snapshots whose committee can't be derived this way, because the
+/// provisioners changed in the meantime, are rejected before any download.
+///
+/// The certified state root is then used to verify the archived state before
+/// it is installed as the finalized state. The final block becomes the chain
+/// tip, the blocks following it being synced as usual, while the blocks below
+/// it are never fetched: the ledger starts at the snapshot height, which is
+/// recorded under [`MD_FAST_SYNC_HEIGHT_KEY`].
+pub(super) async fn fast_sync<N, DB, VM>(
+    inbound: &AsyncQueue<Message>,
+    network: &Arc<RwLock<N>>,
+    db: &Arc<RwLock<DB>>,
+    vm: &Arc<RwLock<VM>>,
+    genesis_state_root: Hash,
+) -> Result<BlockWithLabel>
+where
+    N: Network,
+    DB: database::DB,
+    VM: vm::VMExecution,
+{
+    let deadline = Instant::now() + FAST_SYNC_TIMEOUT;
+
+    let trusted_root = db
+        .read()
+        .await
+        .view(|t| t.fetch_checkpoints())?
+        .last()
+        .map_or(genesis_state_root, |c| c.state_root);
+    let provisioners = vm.read().await.get_provisioners(trusted_root)?;
+
+    info!(
+        event = "fast sync started",
+        trusted_root = hex::encode(trusted_root),
+    );
+
+    while Instant::now() < deadline {
+        match try_fast_sync(inbound, network, db, vm, &provisioners).await {
+            Ok(blk) => return Ok(blk),
+            Err(err) => warn!(event = "fast sync attempt failed", ?err),
+        }
+    }
+
+    Err(anyhow!("no peer served a valid state snapshot"))
+}
+
+async fn try_fast_sync<N, DB, VM>(
+    inbound: &AsyncQueue<Message>,
+    network: &Arc<RwLock<N>>,
+    db: &Arc<RwLock<DB>>,
+    vm: &Arc<RwLock<VM>>,
+    provisioners: &Provisioners,
+) -> Result<BlockWithLabel>
+where
+    N: Network,
+    DB: database::DB,
+    VM: vm::VMExecution,
+{
+    let req = Message::new_get_state_snapshot(GetStateSnapshot::default());
+    network
+        .read()
+        .await
+        .send_to_alive_peers(&req, REDUNDANCY_PEER_FACTOR)
+        .await?;
+
+    let (snapshot, peer_addr) = recv_response(inbound, None, |p| match p {
+        Payload::StateSnapshot(s) => Some(*s.clone()),
+        _ => None,
+    })
+    .await?;
+
+    let header = &snapshot.header;
+    let state_root = header.state_hash;
+
+    info!(
+        event = "state snapshot found",
+        height = header.height,
+        state_root = hex::encode(state_root),
+        chunks = snapshot.chunk_hashes.len(),
+        peer = ?peer_addr,
+    );
+
+    let blk = verify_snapshot_blocks(&snapshot)?;
+
+    // The certificate of the following block must come from a committee we
+    // can derive locally, for the state root not to be taken on trust
+    let next = &snapshot.next;
+    verify_block_cert(
+        header.hash,
+        header.seed,
+        provisioners,
+        next.height,
+        &next.cert,
+        next.iteration,
+    )
+    .await
+    .map_err(|err| anyhow!("untrusted snapshot certificate: {err}"))?;
+
+    let mut archive = vec![];
+    for (index, hash) in snapshot.chunk_hashes.iter().enumerate() {
+        let chunk = download_chunk(
+            inbound,
+            network,
+            peer_addr,
+            state_root,
+            index as u32,
+            hash,
+        )
+        .await?;
+        archive.extend(chunk.data);
+    }
+
+    let vm = vm.read().await;
+    vm.import_snapshot(state_root, &archive)?;
+    vm.install_snapshot(state_root)?;
+
+    db.read().await.update(|t| {
+        t.store_block(header, &snapshot.txs, Label::Final)?;
+        t.op_write(MD_HASH_KEY, header.hash)?;
+        t.op_write(MD_STATE_ROOT_KEY, header.state_hash)?;
+        t.op_write(MD_FAST_SYNC_HEIGHT_KEY, header.height.to_le_bytes())
+    })?;
+
+    info!(
+        event = "fast sync completed",
+        height = header.height,
+        state_root = hex::encode(state_root),
+    );
+
+    Ok(BlockWithLabel::new_with_label(blk, Label::Final))
+}
+
+/// Checks the snapshot blocks are consistent with each other, returning the
+/// block the state belongs to.
+fn verify_snapshot_blocks(snapshot: &StateSnapshot) -> Result<Block> {
+    let header = &snapshot.header;
+    let next = &snapshot.next;
+
+    ensure!(header.height > 0, "snapshot of the genesis state");
+    ensure!(!snapshot.chunk_hashes.is_empty(), "empty snapshot");
+    ensure!(compute_hash(header)? == header.hash, "invalid block hash");
+    ensure!(compute_hash(next)? == next.hash, "invalid next block hash");
+    ensure!(
+        next.height == header.height + 1 && next.prev_block_hash == header.hash,
+        "next block does not follow the snapshot block"
+    );
+    ensure!(
+        next.cert.result.vote() == &Vote::Valid(next.hash),
+        "next block is not certified"
+    );
+
+    let txs: Vec<_> = snapshot.txs.iter().map(|t| t.inner.clone()).collect();
+    let tx_hashes: Vec<_> = txs.iter().map(|t| t.hash()).collect();
+    ensure!(
+        merkle_root(&tx_hashes[..]) == header.txroot,
+        "invalid block transactions"
+    );
+
+    Ok(Block::new(header.clone(), txs)?)
+}
+
+/// Computes the hash of a header, regardless of the one it carries.
+fn compute_hash(header: &ledger::Header) -> Result<Hash> {
+    let header = ledger::Header {
+        hash: Hash::default(),
+        ..header.clone()
+    };
+    Ok(Block::new(header, vec![])?.header().hash)
+}
+
+async fn download_chunk<N: Network>(
+    inbound: &AsyncQueue<Message>,
+    network: &Arc<RwLock<N>>,
+    peer_addr: SocketAddr,
+    state_root: Hash,
+    index: u32,
+    hash: &Hash,
+) -> Result<StateChunk> {
+    let req = GetStateChunk { state_root, index };
+    let msg = Message::new_get_state_chunk(req.clone());
+
+    for _ in 0..MAX_CHUNK_ATTEMPTS {
+        network.read().await.send_to_peer(&msg, peer_addr).await?;
+
+        let chunk = recv_response(inbound, Some(peer_addr), |p| match p {
+            Payload::StateChunk(c)
+                if c.state_root == req.state_root && c.index == req.index =>
+            {
+                Some(*c.clone())
+            }
+            _ => None,
+        })
+        .await;
+
+        match chunk {
+            Ok((chunk, _)) if &chunk.hash() == hash => return Ok(chunk),
+            Ok(_) => warn!(event = "invalid state chunk", index),
+            Err(err) => warn!(event = "state chunk not received", index, ?err),
+        }
+    }
+
+    Err(anyhow!("could not download state chunk {index}"))
+}
+
+/// Waits for a response, from the given peer if any, discarding any other
+/// message.
+async fn recv_response<T, F>(
+    inbound: &AsyncQueue<Message>,
+    peer_addr: Option<SocketAddr>,
+    f: F,
+) -> Result<(T, SocketAddr)>
+where
+    F: Fn(&Payload) -> Option<T>,
+{
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+
+    loop {
+        let msg = timeout_at(deadline, inbound.recv())
+            .await
+            .map_err(|_| anyhow!("request timed out"))??;
+
+        let src_addr = match &msg.metadata {
+            Some(metadata) => metadata.src_addr,
+            None => continue,
+        };
+
+        if peer_addr.is_some_and(|addr| addr != src_addr) {
+            continue;
+        }
+
+        if let Some(response) = f(&msg.payload) {
+            return Ok((response, src_addr));
+        }
+    }
+}
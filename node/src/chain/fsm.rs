@@ -5,25 +5,31 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use super::acceptor::{Acceptor, RevertTarget};
+use super::download::Downloader;
+use super::header_validation::HeaderVerificationErr;
 use crate::chain::fallback;
 use crate::database;
+use crate::network::Misbehaviour;
 use crate::{vm, Network};
 
 use crate::database::Ledger;
 use node_data::ledger::{to_str, Block, Label};
 use node_data::message::payload::{GetBlocks, GetData};
 use node_data::message::Message;
-use std::collections::{HashMap, HashSet};
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::collections::HashSet;
+use std::net::SocketAddr;
 use std::ops::Deref;
+use std::sync::Arc;
 use std::time::Duration;
-use std::{sync::Arc, time::SystemTime};
 use tokio::sync::RwLock;
 use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
-const MAX_BLOCKS_TO_REQUEST: i16 = 50;
-const EXPIRY_TIMEOUT_MILLIS: i16 = 5000;
+/// Time after which the sync-up is abandoned if no block has been accepted.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Number of peers blocks are downloaded from in parallel.
+const SYNC_PEERS: usize = 8;
 
 pub(crate) const REDUNDANCY_PEER_FACTOR: usize = 5;
 
@@ -527,10 +533,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
 }
 
 struct OutOfSyncImpl<DB: database::DB, VM: vm::VMExecution, N: Network> {
-    range: (u64, u64),
-    start_time: SystemTime,
-    pool: HashMap<u64, Block>,
-    peer_addr: SocketAddr,
+    downloader: Downloader,
+    last_progress: Instant,
 
     acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
     network: Arc<RwLock<N>>,
@@ -544,62 +548,39 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         network: Arc<RwLock<N>>,
     ) -> Self {
         Self {
-            start_time: SystemTime::now(),
-            range: (0, 0),
-            pool: HashMap::new(),
+            downloader: Downloader::default(),
+            last_progress: Instant::now(),
             acc,
             network,
-            peer_addr: SocketAddr::V4(SocketAddrV4::new(
-                Ipv4Addr::new(127, 0, 0, 1),
-                8000,
-            )),
         }
     }
     /// performed when entering the OutOfSync state
     async fn on_entering(&mut self, blk: &Block, dest_addr: SocketAddr) {
-        let (curr_height, locator) = {
-            let acc = self.acc.read().await;
-            (acc.get_curr_height().await, acc.get_curr_hash().await)
-        };
-
-        self.range = (
-            curr_height,
-            std::cmp::min(
-                curr_height + MAX_BLOCKS_TO_REQUEST as u64,
-                blk.header().height,
-            ),
-        );
-
-        // Request missing blocks from source peer
-        let gb_msg = Message::new_get_blocks(GetBlocks { locator });
+        let curr_height = self.acc.read().await.get_curr_height().await;
 
-        if let Err(e) = self
-            .network
-            .read()
-            .await
-            .send_to_peer(&gb_msg, dest_addr)
-            .await
-        {
-            warn!("Unable to send GetBlocks: {e}")
-        };
+        self.downloader = Downloader::new(curr_height, blk.header().height);
+        self.downloader.add_peers([dest_addr]);
+        self.downloader.on_target_block(blk.clone(), dest_addr);
+        self.last_progress = Instant::now();
 
-        // add to the pool
-        let key = blk.header().height;
-        self.pool.clear();
-        self.pool.insert(key, blk.clone());
-        self.peer_addr = dest_addr;
+        // Download missing blocks from the source peer along with other
+        // alive peers
+        let peers = self.network.read().await.alive_nodes(SYNC_PEERS).await;
+        self.downloader.add_peers(peers);
+        self.request_blocks().await;
 
         info!(
             event = "entering out-of-sync",
-            from = self.range.0,
-            to = self.range.1,
+            from = curr_height,
+            to = self.downloader.target(),
             peer = format!("{:?}", dest_addr),
+            peers = self.downloader.peers_count(),
         );
     }
 
     /// performed when exiting the state
     async fn on_exiting(&mut self) {
-        self.pool.clear();
+        self.downloader = Downloader::default();
     }
 
     pub async fn on_event(
@@ -607,78 +588,60 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         blk: &Block,
         msg: &Message,
     ) -> anyhow::Result<bool> {
-        let mut acc = self.acc.write().await;
-        let h = blk.header().height;
-
-        if self
-            .start_time
-            .checked_add(Duration::from_millis(EXPIRY_TIMEOUT_MILLIS as u64))
-            .unwrap()
-            <= SystemTime::now()
-        {
-            acc.restart_consensus().await;
-            // Timeout-ed sync-up
-            // Transit back to InSync mode
-            return Ok(true);
-        }
-
-        if h <= acc.get_curr_height().await {
-            return Ok(false);
-        }
+        let src_addr = msg.metadata.as_ref().map(|m| m.src_addr);
+        self.downloader.on_block(
+            blk.clone(),
+            src_addr,
+            Instant::now().into_std(),
+        );
 
-        // Try accepting consecutive block
-        if h == acc.get_curr_height().await + 1 {
-            acc.try_accept_block(blk, None, false).await?;
+        let mut acc = self.acc.write().await;
 
-            if let Some(metadata) = &msg.metadata {
-                if metadata.src_addr == self.peer_addr {
-                    // reset expiry_time only if we receive a valid block from
-                    // the syncing peer.
-                    self.start_time = SystemTime::now();
+        // Accept downloaded blocks in order of height
+        while let Some((blk, peer)) = self.downloader.pop_next() {
+            match acc.try_accept_block(&blk, None, false).await {
+                Ok(_) => {
+                    self.downloader.on_accepted(peer);
+                    self.last_progress = Instant::now();
                 }
-            }
-
-            // Try to accept other consecutive blocks from the pool, if
-            // available
-            for height in (h + 1)..(self.range.1 + 1) {
-                if let Some(blk) = self.pool.get(&height) {
-                    acc.try_accept_block(blk, None, false).await?;
-                } else {
+                Err(err) => {
+                    warn!(
+                        event = "downloaded block rejected",
+                        height = blk.header().height,
+                        ?peer,
+                        ?err,
+                    );
+                    self.downloader.on_invalid(peer);
+                    let forged = HeaderVerificationErr::is_cause_of(&err);
+                    if let (true, Some(peer)) = (forged, peer) {
+                        self.network
+                            .read()
+                            .await
+                            .report_peer(peer, Misbehaviour::InvalidBlock);
+                    }
                     break;
                 }
             }
-
-            // Check target height is reached
-            if acc.get_curr_height().await == self.range.1 {
-                // Block sync-up procedure manages to download all requested
-                // blocks
-                acc.restart_consensus().await;
-
-                // Transit to InSync mode
-                return Ok(true);
-            }
-
-            return Ok(false);
         }
 
-        // add block to the pool
-        if self.pool.len() < MAX_BLOCKS_TO_REQUEST as usize {
-            let key = blk.header().height;
-            self.pool.insert(key, blk.clone());
+        // Check target height is reached
+        if self.downloader.is_done() {
+            // Block sync-up procedure manages to download all requested
+            // blocks
+            acc.restart_consensus().await;
+
+            // Transit to InSync mode
+            return Ok(true);
         }
 
-        debug!(event = "block saved", len = self.pool.len());
+        drop(acc);
+        self.request_blocks().await;
 
         Ok(false)
     }
 
     async fn on_heartbeat(&mut self) -> anyhow::Result<bool> {
-        if self
-            .start_time
-            .checked_add(Duration::from_millis(EXPIRY_TIMEOUT_MILLIS as u64))
-            .unwrap()
-            <= SystemTime::now()
-        {
+        if self.last_progress + SYNC_TIMEOUT <= Instant::now() {
             // sync-up has timed out, recover consensus task
             self.acc.write().await.restart_consensus().await;
 
@@ -686,6 +649,37 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
             return Ok(true);
         }
 
+        // Request again the blocks not delivered in time, possibly to newly
+        // discovered peers
+        self.downloader.expire(Instant::now().into_std());
+        let peers = self.network.read().await.alive_nodes(SYNC_PEERS).await;
+        self.downloader.add_peers(peers);
+        self.request_blocks().await;
+
         Ok(false)
     }
+
+    /// Requests the scheduled blocks to the peers they are assigned to
+    async fn request_blocks(&mut self) {
+        let requests = self.downloader.schedule(Instant::now().into_std());
+
+        for (peer_addr, heights) in requests {
+            let mut get_data = GetData::default();
+            for height in &heights {
+                get_data.inner.add_block_from_height(*height);
+            }
+
+            debug!(event = "request blocks", ?heights, ?peer_addr);
+
+            if let Err(err) = self
+                .network
+                .read()
+                .await
+                .send_to_peer(&Message::new_get_data(get_data), peer_addr)
+                .await
+            {
+                warn!("could not request blocks {err}")
+            };
+        }
+    }
 }
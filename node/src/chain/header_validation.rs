@@ -4,8 +4,10 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::chain::evidence;
 use crate::database;
-use crate::database::Ledger;
+use crate::database::rocksdb::MD_FAST_SYNC_HEIGHT_KEY;
+use crate::database::{Ledger, Metadata};
 use anyhow::anyhow;
 use dusk_bytes::Serializable;
 use dusk_consensus::quorum::verifiers;
@@ -24,8 +26,23 @@ use tracing::info;
 
 // TODO: Use thiserror instead of anyhow
 
+/// Failures of the checks that no honest node can produce a header failing,
+/// as opposed to the ones depending on the local state, such as a block being
+/// out of order or the ledger being unreadable.
 #[derive(Debug, Error)]
-enum HeaderVerificationErr {}
+pub(crate) enum HeaderVerificationErr {
+    #[error("invalid seed: {0}")]
+    InvalidSeed(String),
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+}
+
+impl HeaderVerificationErr {
+    /// Returns whether the error is due to a header failing verification.
+    pub(crate) fn is_cause_of(err: &anyhow::Error) -> bool {
+        err.chain().any(|cause| cause.is::<Self>())
+    }
+}
 
 /// An implementation of the all validation checks of a candidate block header
 /// according to current context
@@ -64,6 +81,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
     ) -> anyhow::Result<bool> {
         self.verify_basic_fields(candidate_block).await?;
         self.verify_prev_block_cert(candidate_block).await?;
+        self.verify_evidence(candidate_block)?;
 
         if !disable_winner_cert_check {
             self.verify_winning_cert(candidate_block).await?;
@@ -115,20 +133,37 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         Ok(())
     }
 
+    /// Verifies the equivocation evidence included in the candidate
+    pub fn verify_evidence(
+        &self,
+        candidate_block: &'a ledger::Header,
+    ) -> anyhow::Result<()> {
+        evidence::verify_all(
+            &candidate_block.evidence,
+            candidate_block.height,
+            self.provisioners.current(),
+        )
+    }
+
     fn verify_seed_field(
         &self,
         seed: &[u8; 48],
         pk_bytes: &[u8; 96],
     ) -> anyhow::Result<()> {
-        let pk = dusk_bls12_381_sign::PublicKey::from_bytes(pk_bytes)
-            .map_err(|err| anyhow!("invalid pk bytes: {:?}", err))?;
+        let pk = dusk_bls12_381_sign::PublicKey::from_bytes(pk_bytes).map_err(
+            |err| HeaderVerificationErr::InvalidSeed(format!("{err:?}")),
+        )?;
 
         let signature = dusk_bls12_381_sign::Signature::from_bytes(seed)
-            .map_err(|err| anyhow!("invalid signature bytes: {}", err))?;
+            .map_err(|err| {
+                HeaderVerificationErr::InvalidSeed(format!("{err:?}"))
+            })?;
 
         dusk_bls12_381_sign::APK::from(&pk)
             .verify(&signature, &self.prev_header.seed.inner()[..])
-            .map_err(|err| anyhow!("invalid seed: {:?}", err))?;
+            .map_err(|err| {
+                HeaderVerificationErr::InvalidSeed(format!("{err:?}"))
+            })?;
 
         Ok(())
     }
@@ -142,13 +177,26 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         }
 
         let prev_block_seed = self.db.read().await.view(|v| {
+            // The block preceding a fast synced state is not stored, the
+            // state having been certified when syncing
+            let fast_sync_height = v.op_read(MD_FAST_SYNC_HEIGHT_KEY)?;
+            if fast_sync_height.as_deref()
+                == Some(&self.prev_header.height.to_le_bytes()[..])
+            {
+                return Ok(None);
+            }
+
             let prior_tip =
                 Ledger::fetch_block_by_height(&v, self.prev_header.height - 1)?
                     .ok_or_else(|| anyhow::anyhow!("could not fetch block"))?;
 
-            Ok::<_, anyhow::Error>(prior_tip.header().seed)
+            Ok::<_, anyhow::Error>(Some(prior_tip.header().seed))
         })?;
 
+        let Some(prev_block_seed) = prev_block_seed else {
+            return Ok(());
+        };
+
         verify_block_cert(
             self.prev_header.prev_block_hash,
             prev_block_seed,
@@ -183,7 +231,9 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
                 info!(event = "verify_cert", cert_type = "failed_cert", iter);
 
                 if let RatificationResult::Success(_) = cert.result {
-                    anyhow::bail!("Failed iterations should not contains a RatificationResult::Success");
+                    return Err(HeaderVerificationErr::InvalidCertificate(
+                        "Failed iterations should not contains a RatificationResult::Success".into(),
+                    ).into());
                 }
 
                 let expected_pk = self.provisioners.current().get_generator(
@@ -192,7 +242,11 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
                     candidate_block.height,
                 );
 
-                anyhow::ensure!(pk == &expected_pk, "Invalid generator. Expected {expected_pk:?}, actual {pk:?}");
+                if pk != &expected_pk {
+                    return Err(HeaderVerificationErr::InvalidCertificate(
+                        format!("Invalid generator. Expected {expected_pk:?}, actual {pk:?}"),
+                    ).into());
+                }
 
                 let quorums = verify_block_cert(
                     self.prev_header.hash,
@@ -266,7 +320,7 @@ pub async fn verify_block_cert(
             result.0 = validation_quorum_result;
         }
         Err(e) => {
-            return Err(anyhow!(
+            return Err(HeaderVerificationErr::InvalidCertificate(format!(
                 "invalid validation, vote = {:?}, round = {}, iter = {}, seed = {},  sv = {:?}, err = {}",
                 vote,
                 round,
@@ -274,7 +328,8 @@ pub async fn verify_block_cert(
                 to_str(curr_seed.inner()),
                 cert.validation,
                 e
-            ));
+            ))
+            .into());
         }
     };
 
@@ -293,7 +348,7 @@ pub async fn verify_block_cert(
             result.1 = ratification_quorum_result;
         }
         Err(e) => {
-            return Err(anyhow!(
+            return Err(HeaderVerificationErr::InvalidCertificate(format!(
                 "invalid ratification, vote = {:?}, round = {}, iter = {}, seed = {},  sv = {:?}, err = {}",
                 vote,
                 round,
@@ -301,7 +356,8 @@ pub async fn verify_block_cert(
                 to_str(curr_seed.inner()),
                 cert.ratification,
                 e,
-            ));
+            ))
+            .into());
         }
     }
 
@@ -10,6 +10,7 @@ use std::path::Path;
 pub mod rocksdb;
 
 use anyhow::Result;
+use node_data::bls::PublicKeyBytes;
 use node_data::ledger;
 use node_data::ledger::{Label, SpentTransaction};
 
@@ -76,10 +77,41 @@ pub trait Ledger {
         tx_hash: &[u8],
     ) -> Result<Option<ledger::SpentTransaction>>;
 
+    /// Returns the transaction that spent a nullifier, if any.
+    fn get_ledger_tx_by_nullifier(
+        &self,
+        nullifier: &[u8],
+    ) -> Result<Option<ledger::SpentTransaction>>;
+
     fn get_ledger_tx_exists(&self, tx_hash: &[u8]) -> Result<bool>;
 
     fn fetch_block_label_by_height(&self, height: u64)
         -> Result<Option<Label>>;
+
+    /// Stores the stake events emitted while accepting the block at `height`.
+    fn store_stake_events(
+        &self,
+        height: u64,
+        events: &[ledger::StakeEvent],
+    ) -> Result<()>;
+
+    /// Returns the stake events of a provisioner emitted within the inclusive
+    /// range of heights `from..=to`, in chronological order.
+    fn fetch_stake_events(
+        &self,
+        provisioner: &PublicKeyBytes,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<ledger::StakeEvent>>;
+
+    /// Returns the hashes of the blocks generated by a provisioner within the
+    /// inclusive range of heights `from..=to`, in chronological order.
+    fn fetch_block_hashes_by_generator(
+        &self,
+        generator: &PublicKeyBytes,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<[u8; 32]>>;
 }
 
 pub trait Candidate {
@@ -128,6 +160,15 @@ pub trait Metadata {
 
     /// Reads an value of a key from the Metadata CF
     fn op_read(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Replaces the stored epoch checkpoints
+    fn store_checkpoints(
+        &self,
+        checkpoints: &[ledger::EpochCheckpoint],
+    ) -> Result<()>;
+
+    /// Reads the stored epoch checkpoints, ordered by height
+    fn fetch_checkpoints(&self) -> Result<Vec<ledger::EpochCheckpoint>>;
 }
 
 pub trait Persist:
@@ -4,13 +4,15 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use super::{Candidate, Ledger, Metadata, Persist, DB};
+use super::{into_array, Candidate, Ledger, Metadata, Persist, DB};
 use anyhow::Result;
 
+use node_data::bls::{PublicKeyBytes, PUBLIC_BLS_SIZE};
 use node_data::ledger::{self, Label, SpentTransaction};
 use node_data::Serializable;
 
 use crate::database::Mempool;
+use crate::metrics::metrics;
 
 use rocksdb_lib::{
     ColumnFamily, ColumnFamilyDescriptor, DBAccess,
@@ -19,6 +21,7 @@ use rocksdb_lib::{
     WriteOptions,
 };
 
+use std::cell::Cell;
 use std::collections::HashSet;
 use std::io;
 use std::io::Read;
@@ -29,9 +32,15 @@ use std::vec;
 
 use tracing::info;
 
+pub mod migration;
+
 const CF_LEDGER_HEADER: &str = "cf_ledger_header";
 const CF_LEDGER_TXS: &str = "cf_ledger_txs";
 const CF_LEDGER_HEIGHT: &str = "cf_ledger_height";
+const CF_LEDGER_STAKE_EVENTS: &str = "cf_ledger_stake_events";
+const CF_LEDGER_STAKE_HEIGHTS: &str = "cf_ledger_stake_heights";
+const CF_LEDGER_BY_GENERATOR: &str = "cf_ledger_by_generator";
+const CF_LEDGER_NULLIFIERS: &str = "cf_ledger_nullifiers";
 const CF_CANDIDATES: &str = "cf_candidates";
 const CF_MEMPOOL: &str = "cf_mempool";
 const CF_MEMPOOL_NULLIFIERS: &str = "cf_mempool_nullifiers";
@@ -47,6 +56,9 @@ pub const MD_STATE_ROOT_KEY: &[u8] = b"state_hash_key";
 pub const MD_AVG_VALIDATION: &[u8] = b"avg_validation_time";
 pub const MD_AVG_RATIFICATION: &[u8] = b"avg_ratification_time";
 pub const MD_AVG_PROPOSAL: &[u8] = b"avg_proposal_time";
+pub const MD_EPOCH_CHECKPOINTS: &[u8] = b"epoch_checkpoints";
+pub const MD_SCHEMA_VERSION: &[u8] = b"schema_version";
+pub const MD_FAST_SYNC_HEIGHT_KEY: &[u8] = b"fast_sync_height";
 
 #[derive(Clone)]
 pub struct Backend {
@@ -97,6 +109,26 @@ impl Backend {
             .cf_handle(CF_LEDGER_HEIGHT)
             .expect("CF_LEDGER_HEIGHT column family must exist");
 
+        let stake_events_cf = self
+            .rocksdb
+            .cf_handle(CF_LEDGER_STAKE_EVENTS)
+            .expect("CF_LEDGER_STAKE_EVENTS column family must exist");
+
+        let stake_heights_cf = self
+            .rocksdb
+            .cf_handle(CF_LEDGER_STAKE_HEIGHTS)
+            .expect("CF_LEDGER_STAKE_HEIGHTS column family must exist");
+
+        let by_generator_cf = self
+            .rocksdb
+            .cf_handle(CF_LEDGER_BY_GENERATOR)
+            .expect("CF_LEDGER_BY_GENERATOR column family must exist");
+
+        let ledger_nullifiers_cf = self
+            .rocksdb
+            .cf_handle(CF_LEDGER_NULLIFIERS)
+            .expect("CF_LEDGER_NULLIFIERS column family must exist");
+
         let metadata_cf = self
             .rocksdb
             .cf_handle(CF_METADATA)
@@ -113,8 +145,13 @@ impl Backend {
             nullifiers_cf,
             fees_cf,
             ledger_height_cf,
+            stake_events_cf,
+            stake_heights_cf,
+            by_generator_cf,
+            ledger_nullifiers_cf,
             metadata_cf,
             snapshot,
+            mempool_change: Cell::default(),
         }
     }
 }
@@ -129,40 +166,29 @@ impl DB for Backend {
         let path = path.as_ref().join(DB_FOLDER_NAME);
         info!("Opening database in {path:?}");
 
-        let mut opts = Options::default();
-        opts.create_if_missing(true);
-        opts.create_missing_column_families(true);
-        opts.set_level_compaction_dynamic_level_bytes(true);
-
-        // Configure CF_MEMPOOL column family so it benefits from low
-        // write-latency of L0
-        let mut mp_opts = Options::default();
-        mp_opts.set_write_buffer_size(MAX_MEMPOOL_SIZE);
-
-        // Disable WAL by default
-        mp_opts.set_manual_wal_flush(true);
-
-        // Disable flush-to-disk by default
-        mp_opts.set_disable_auto_compactions(true);
-
-        let cfs = vec![
-            ColumnFamilyDescriptor::new(CF_LEDGER_HEADER, Options::default()),
-            ColumnFamilyDescriptor::new(CF_LEDGER_TXS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_LEDGER_HEIGHT, Options::default()),
-            ColumnFamilyDescriptor::new(CF_CANDIDATES, Options::default()),
-            ColumnFamilyDescriptor::new(CF_MEMPOOL, mp_opts.clone()),
-            ColumnFamilyDescriptor::new(CF_MEMPOOL_NULLIFIERS, mp_opts.clone()),
-            ColumnFamilyDescriptor::new(CF_MEMPOOL_FEES, mp_opts.clone()),
-            ColumnFamilyDescriptor::new(CF_METADATA, mp_opts),
-        ];
+        let rocksdb =
+            open_db(&path, true).expect("should be a valid database in {path}");
+
+        // Databases of an older schema have to be migrated before being used
+        if let Err(e) = migration::check_schema(&rocksdb) {
+            panic!("{e}");
+        }
+
+        // The mempool metrics are kept up to date on each change, starting
+        // from the transactions persisted by a previous run
+        let mempool_cf = rocksdb
+            .cf_handle(CF_MEMPOOL)
+            .expect("mempool column family must exist");
+        let (txs, bytes) = rocksdb
+            .iterator_cf(mempool_cf, IteratorMode::Start)
+            .filter_map(Result::ok)
+            .fold((0, 0), |(txs, bytes), (_, tx)| {
+                (txs + 1, bytes + tx.len() as i64)
+            });
+        metrics().mempool_persisted(txs, bytes);
 
         Self {
-            rocksdb: Arc::new(
-                rocksdb_lib::OptimisticTransactionDB::open_cf_descriptors(
-                    &opts, path, cfs,
-                )
-                .expect("should be a valid database in {path}"),
-            ),
+            rocksdb: Arc::new(rocksdb),
         }
     }
 
@@ -188,9 +214,13 @@ impl DB for Backend {
         // storage
         let ret = execute(&tx)?;
 
+        let (txs, bytes) = tx.mempool_change.get();
+
         // Apply changes in atomic way
         tx.commit()?;
 
+        metrics().mempool_changed(txs, bytes);
+
         Ok(ret)
     }
 
@@ -208,6 +238,10 @@ pub struct DBTransaction<'db, DB: DBAccess> {
     ledger_cf: &'db ColumnFamily,
     ledger_txs_cf: &'db ColumnFamily,
     ledger_height_cf: &'db ColumnFamily,
+    stake_events_cf: &'db ColumnFamily,
+    stake_heights_cf: &'db ColumnFamily,
+    by_generator_cf: &'db ColumnFamily,
+    ledger_nullifiers_cf: &'db ColumnFamily,
 
     // Mempool column families
     mempool_cf: &'db ColumnFamily,
@@ -217,6 +251,10 @@ pub struct DBTransaction<'db, DB: DBAccess> {
     metadata_cf: &'db ColumnFamily,
 
     snapshot: SnapshotWithThreadMode<'db, DB>,
+
+    // Change in the number and size of the mempool transactions, applied to
+    // the metrics once committed
+    mempool_change: Cell<(i64, i64)>,
 }
 
 impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
@@ -261,6 +299,28 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
             }
         }
 
+        // CF: NULLIFIER -> TX_HASH
+        for tx in txs {
+            let hash = tx.inner.hash();
+            for n in tx.inner.inner.nullifiers().iter() {
+                self.inner.put_cf(
+                    self.ledger_nullifiers_cf,
+                    n.to_bytes(),
+                    hash,
+                )?;
+            }
+        }
+
+        // CF: (GENERATOR, HEIGHT) -> BLOCK_HASH
+        self.inner.put_cf(
+            self.by_generator_cf,
+            serialize_generator_key(
+                &header.generator_bls_pubkey,
+                header.height,
+            ),
+            header.hash,
+        )?;
+
         // CF: HEIGHT -> (BLOCK_HASH, BLOCK_LABEL)
         let mut buf = vec![];
         buf.write_all(&header.hash[..])?;
@@ -283,10 +343,31 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
 
         for tx in b.txs() {
             self.inner.delete_cf(self.ledger_txs_cf, tx.hash())?;
+
+            for n in tx.inner.nullifiers().iter() {
+                self.inner
+                    .delete_cf(self.ledger_nullifiers_cf, n.to_bytes())?;
+            }
         }
 
+        self.inner.delete_cf(
+            self.by_generator_cf,
+            serialize_generator_key(
+                &b.header().generator_bls_pubkey,
+                b.header().height,
+            ),
+        )?;
+
         self.inner.delete_cf(self.ledger_cf, b.header().hash)?;
 
+        let height = b.header().height.to_be_bytes();
+        if let Some(keys) = self.inner.get_cf(self.stake_heights_cf, height)? {
+            for key in keys.chunks(STAKE_EVENT_KEY_LEN) {
+                self.inner.delete_cf(self.stake_events_cf, key)?;
+            }
+            self.inner.delete_cf(self.stake_heights_cf, height)?;
+        }
+
         Ok(())
     }
 
@@ -366,6 +447,16 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
         Ok(tx)
     }
 
+    fn get_ledger_tx_by_nullifier(
+        &self,
+        nullifier: &[u8],
+    ) -> Result<Option<ledger::SpentTransaction>> {
+        match self.snapshot.get_cf(self.ledger_nullifiers_cf, nullifier)? {
+            Some(tx_hash) => self.get_ledger_tx_by_hash(&tx_hash),
+            None => Ok(None),
+        }
+    }
+
     /// Returns true if the transaction exists in the
     /// ledger
     ///
@@ -398,6 +489,112 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
             .filter(|v| v.len() == LEN)
             .map(|h| Label::from(h[LEN - 1])))
     }
+
+    /// COLUMN FAMILY: CF_LEDGER_STAKE_EVENTS
+    /// Events are keyed by (PROVISIONER, HEIGHT, INDEX), the height and index
+    /// being big-endian, so that the events of a provisioner are sorted
+    /// chronologically.
+    ///
+    /// COLUMN FAMILY: CF_LEDGER_STAKE_HEIGHTS
+    /// The keys of the events emitted at a height are concatenated under the
+    /// big-endian height, so that they can be removed along with their block.
+    fn store_stake_events(
+        &self,
+        height: u64,
+        events: &[ledger::StakeEvent],
+    ) -> Result<()> {
+        let mut keys = Vec::with_capacity(events.len() * STAKE_EVENT_KEY_LEN);
+        for (index, event) in events.iter().enumerate() {
+            let key =
+                serialize_stake_event_key(&event.provisioner, height, index);
+
+            let mut buf = vec![];
+            event.write(&mut buf)?;
+
+            self.inner.put_cf(self.stake_events_cf, &key, buf)?;
+            keys.extend_from_slice(&key);
+        }
+
+        if !keys.is_empty() {
+            self.inner.put_cf(
+                self.stake_heights_cf,
+                height.to_be_bytes(),
+                keys,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn fetch_stake_events(
+        &self,
+        provisioner: &PublicKeyBytes,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<ledger::StakeEvent>> {
+        let mut iter = self.snapshot.raw_iterator_cf(self.stake_events_cf);
+        iter.seek(serialize_stake_event_key(provisioner, from, 0));
+
+        let mut events = vec![];
+        while iter.valid() {
+            let (Some(key), Some(blob)) = (iter.key(), iter.value()) else {
+                break;
+            };
+            if key.len() != STAKE_EVENT_KEY_LEN
+                || !key.starts_with(provisioner.inner())
+            {
+                break;
+            }
+
+            let event = ledger::StakeEvent::read(&mut &blob[..])?;
+            if event.height > to {
+                break;
+            }
+
+            events.push(event);
+            iter.next();
+        }
+
+        Ok(events)
+    }
+
+    /// COLUMN FAMILY: CF_LEDGER_BY_GENERATOR
+    /// Block hashes are keyed by (GENERATOR, HEIGHT), the height being
+    /// big-endian, so that the blocks of a generator are sorted
+    /// chronologically.
+    fn fetch_block_hashes_by_generator(
+        &self,
+        generator: &PublicKeyBytes,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<[u8; 32]>> {
+        let mut iter = self.snapshot.raw_iterator_cf(self.by_generator_cf);
+        iter.seek(serialize_generator_key(generator, from));
+
+        let mut hashes = vec![];
+        while iter.valid() {
+            let (Some(key), Some(hash)) = (iter.key(), iter.value()) else {
+                break;
+            };
+            if key.len() != GENERATOR_KEY_LEN
+                || !key.starts_with(generator.inner())
+            {
+                break;
+            }
+
+            let height = u64::from_be_bytes(
+                key[PUBLIC_BLS_SIZE..].try_into().expect("8 bytes height"),
+            );
+            if height > to {
+                break;
+            }
+
+            hashes.push(into_array(hash));
+            iter.next();
+        }
+
+        Ok(hashes)
+    }
 }
 
 impl<'db, DB: DBAccess> Candidate for DBTransaction<'db, DB> {
@@ -470,6 +667,7 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
         tx.write(&mut tx_data)?;
 
         let hash = tx.hash();
+        let size = tx_data.len() as i64;
         self.inner.put_cf(self.mempool_cf, hash, tx_data)?;
 
         // Add Secondary indexes //
@@ -486,6 +684,8 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
             vec![0],
         )?;
 
+        self.change_mempool(1, size);
+
         Ok(())
     }
 
@@ -506,8 +706,9 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
     }
 
     fn delete_tx(&self, h: [u8; 32]) -> Result<bool> {
-        let tx = self.get_tx(h)?;
-        if let Some(tx) = tx {
+        let data = self.inner.get_cf(self.mempool_cf, h)?;
+        if let Some(blob) = data {
+            let tx = ledger::Transaction::read(&mut &blob[..])?;
             let hash = tx.hash();
 
             self.inner.delete_cf(self.mempool_cf, hash)?;
@@ -525,6 +726,8 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
                 serialize_fee_key(tx.gas_price(), hash)?,
             )?;
 
+            self.change_mempool(-1, -(blob.len() as i64));
+
             return Ok(true);
         }
 
@@ -633,6 +836,14 @@ impl<DB: DBAccess> Iterator for MemPoolFeeIterator<'_, DB> {
     }
 }
 
+impl<'db, DB: DBAccess> DBTransaction<'db, DB> {
+    /// Records a change in the number and size of the mempool transactions.
+    fn change_mempool(&self, txs: i64, bytes: i64) {
+        let (t, b) = self.mempool_change.get();
+        self.mempool_change.set((t + txs, b + bytes));
+    }
+}
+
 impl<'db, DB: DBAccess> std::fmt::Debug for DBTransaction<'db, DB> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         //  Print ledger blocks
@@ -685,6 +896,102 @@ impl<'db, DB: DBAccess> Metadata for DBTransaction<'db, DB> {
     fn op_read(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         self.inner.get_cf(self.metadata_cf, key).map_err(Into::into)
     }
+
+    fn store_checkpoints(
+        &self,
+        checkpoints: &[ledger::EpochCheckpoint],
+    ) -> Result<()> {
+        let mut buf = vec![];
+        buf.write_all(&(checkpoints.len() as u32).to_le_bytes())?;
+        for checkpoint in checkpoints {
+            checkpoint.write(&mut buf)?;
+        }
+
+        self.op_write(MD_EPOCH_CHECKPOINTS, buf)
+    }
+
+    fn fetch_checkpoints(&self) -> Result<Vec<ledger::EpochCheckpoint>> {
+        let buf = match self.op_read(MD_EPOCH_CHECKPOINTS)? {
+            Some(buf) => buf,
+            None => return Ok(vec![]),
+        };
+
+        let mut r = &buf[..];
+        let len = ledger::EpochCheckpoint::read_u32_le(&mut r)?;
+        let checkpoints = (0..len)
+            .map(|_| ledger::EpochCheckpoint::read(&mut r))
+            .collect::<io::Result<_>>()?;
+
+        Ok(checkpoints)
+    }
+}
+
+/// Opens the chain database at `path` with all its column families.
+///
+/// If `create` is false, opening fails on a missing database instead of
+/// creating an empty one.
+fn open_db(
+    path: &Path,
+    create: bool,
+) -> std::result::Result<OptimisticTransactionDB, rocksdb_lib::Error> {
+    let mut opts = Options::default();
+    opts.create_if_missing(create);
+    opts.create_missing_column_families(true);
+    opts.set_level_compaction_dynamic_level_bytes(true);
+
+    // Configure CF_MEMPOOL column family so it benefits from low
+    // write-latency of L0
+    let mut mp_opts = Options::default();
+    mp_opts.set_write_buffer_size(MAX_MEMPOOL_SIZE);
+
+    // Disable WAL by default
+    mp_opts.set_manual_wal_flush(true);
+
+    // Disable flush-to-disk by default
+    mp_opts.set_disable_auto_compactions(true);
+
+    let cfs = vec![
+        ColumnFamilyDescriptor::new(CF_LEDGER_HEADER, Options::default()),
+        ColumnFamilyDescriptor::new(CF_LEDGER_TXS, Options::default()),
+        ColumnFamilyDescriptor::new(CF_LEDGER_HEIGHT, Options::default()),
+        ColumnFamilyDescriptor::new(CF_LEDGER_STAKE_EVENTS, Options::default()),
+        ColumnFamilyDescriptor::new(
+            CF_LEDGER_STAKE_HEIGHTS,
+            Options::default(),
+        ),
+        ColumnFamilyDescriptor::new(CF_LEDGER_BY_GENERATOR, Options::default()),
+        ColumnFamilyDescriptor::new(CF_LEDGER_NULLIFIERS, Options::default()),
+        ColumnFamilyDescriptor::new(CF_CANDIDATES, Options::default()),
+        ColumnFamilyDescriptor::new(CF_MEMPOOL, mp_opts.clone()),
+        ColumnFamilyDescriptor::new(CF_MEMPOOL_NULLIFIERS, mp_opts.clone()),
+        ColumnFamilyDescriptor::new(CF_MEMPOOL_FEES, mp_opts.clone()),
+        ColumnFamilyDescriptor::new(CF_METADATA, mp_opts),
+    ];
+
+    OptimisticTransactionDB::open_cf_descriptors(&opts, path, cfs)
+}
+
+const GENERATOR_KEY_LEN: usize = PUBLIC_BLS_SIZE + 8;
+
+fn serialize_generator_key(generator: &PublicKeyBytes, height: u64) -> Vec<u8> {
+    let mut key = Vec::with_capacity(GENERATOR_KEY_LEN);
+    key.extend_from_slice(generator.inner());
+    key.extend_from_slice(&height.to_be_bytes());
+    key
+}
+
+const STAKE_EVENT_KEY_LEN: usize = PUBLIC_BLS_SIZE + 8 + 4;
+
+fn serialize_stake_event_key(
+    provisioner: &PublicKeyBytes,
+    height: u64,
+    index: usize,
+) -> Vec<u8> {
+    let mut key = Vec::with_capacity(STAKE_EVENT_KEY_LEN);
+    key.extend_from_slice(provisioner.inner());
+    key.extend_from_slice(&height.to_be_bytes());
+    key.extend_from_slice(&(index as u32).to_be_bytes());
+    key
 }
 
 fn serialize_fee_key(fee: u64, hash: [u8; 32]) -> std::io::Result<Vec<u8>> {
@@ -1081,6 +1388,185 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_stake_events() {
+        TestWrapper::new("test_stake_events").run(|path| {
+            let db: Backend = Backend::create_or_open(path);
+            let b: ledger::Block = Faker.fake();
+            let height = b.header().height;
+
+            let provisioner: PublicKeyBytes = Faker.fake();
+            let event = |topic: &str, height| ledger::StakeEvent {
+                topic: topic.to_string(),
+                provisioner,
+                value: 1000,
+                height,
+            };
+            let other = ledger::StakeEvent {
+                provisioner: Faker.fake(),
+                ..event("reward", height)
+            };
+
+            assert!(db
+                .update(|txn| {
+                    txn.store_block(
+                        b.header(),
+                        &to_spent_txs(b.txs()),
+                        Label::Final,
+                    )?;
+                    txn.store_stake_events(
+                        height,
+                        &[event("staked", height), other.clone()],
+                    )?;
+                    txn.store_stake_events(
+                        height + 1,
+                        &[event("reward", height + 1)],
+                    )?;
+                    Ok(())
+                })
+                .is_ok());
+
+            db.view(|txn| {
+                let events = txn
+                    .fetch_stake_events(&provisioner, height, height + 1)
+                    .expect("events to be fetched");
+                assert_eq!(
+                    events,
+                    vec![event("staked", height), event("reward", height + 1)]
+                );
+
+                let events = txn
+                    .fetch_stake_events(&provisioner, height + 1, height + 1)
+                    .expect("events to be fetched");
+                assert_eq!(events, vec![event("reward", height + 1)]);
+
+                let events = txn
+                    .fetch_stake_events(&other.provisioner, 0, u64::MAX)
+                    .expect("events to be fetched");
+                assert_eq!(events, vec![other.clone()]);
+            });
+
+            // Events are removed along with their block
+            assert!(db.update(|txn| txn.delete_block(&b)).is_ok());
+
+            db.view(|txn| {
+                let events = txn
+                    .fetch_stake_events(&provisioner, 0, u64::MAX)
+                    .expect("events to be fetched");
+                assert_eq!(events, vec![event("reward", height + 1)]);
+
+                let events = txn
+                    .fetch_stake_events(&other.provisioner, 0, u64::MAX)
+                    .expect("events to be fetched");
+                assert!(events.is_empty());
+            });
+        });
+    }
+
+    #[test]
+    fn test_ledger_indexes() {
+        TestWrapper::new("test_ledger_indexes").run(|path| {
+            let db: Backend = Backend::create_or_open(path);
+
+            let generator: PublicKeyBytes = Faker.fake();
+            let block = |height| {
+                let b: ledger::Block = Faker.fake();
+                let header = ledger::Header {
+                    height,
+                    generator_bls_pubkey: generator,
+                    hash: [height as u8; 32],
+                    ..b.header().clone()
+                };
+                ledger::Block::new(header, vec![]).expect("block to be valid")
+            };
+            let blocks = [block(1), block(2), block(5)];
+
+            // Fake transactions share their nullifiers, thus only one is
+            // stored
+            let other: ledger::Block = Faker.fake();
+            let other = ledger::Block::new(
+                other.header().clone(),
+                vec![other.txs()[0].clone()],
+            )
+            .expect("block to be valid");
+
+            assert!(db
+                .update(|txn| {
+                    for b in blocks.iter().chain([&other]) {
+                        txn.store_block(
+                            b.header(),
+                            &to_spent_txs(b.txs()),
+                            Label::Final,
+                        )?;
+                    }
+                    Ok(())
+                })
+                .is_ok());
+
+            db.view(|txn| {
+                let hashes = txn
+                    .fetch_block_hashes_by_generator(&generator, 2, 5)
+                    .expect("hashes to be fetched");
+                assert_eq!(hashes, vec![[2; 32], [5; 32]]);
+
+                let hashes = txn
+                    .fetch_block_hashes_by_generator(&generator, 0, u64::MAX)
+                    .expect("hashes to be fetched");
+                assert_eq!(hashes.len(), 3);
+
+                let tx = &other.txs()[0];
+                let nullifier = tx.inner.nullifiers()[0].to_bytes();
+                let spent = txn
+                    .get_ledger_tx_by_nullifier(&nullifier)
+                    .expect("tx to be fetched")
+                    .expect("tx to exist");
+                assert_eq!(spent.inner.hash(), tx.hash());
+            });
+
+            // Indexes are removed along with their block
+            assert!(db.update(|txn| txn.delete_block(&blocks[1])).is_ok());
+            assert!(db.update(|txn| txn.delete_block(&other)).is_ok());
+
+            db.view(|txn| {
+                let hashes = txn
+                    .fetch_block_hashes_by_generator(&generator, 0, u64::MAX)
+                    .expect("hashes to be fetched");
+                assert_eq!(hashes, vec![[1; 32], [5; 32]]);
+
+                let nullifier = other.txs()[0].inner.nullifiers()[0].to_bytes();
+                assert!(txn
+                    .get_ledger_tx_by_nullifier(&nullifier)
+                    .expect("tx to be fetched")
+                    .is_none());
+            });
+        });
+    }
+
+    #[test]
+    fn test_epoch_checkpoints() {
+        TestWrapper::new("test_epoch_checkpoints").run(|path| {
+            let db: Backend = Backend::create_or_open(path);
+
+            db.view(|txn| {
+                let checkpoints =
+                    txn.fetch_checkpoints().expect("should not return error");
+                assert!(checkpoints.is_empty());
+            });
+
+            let checkpoints: Vec<ledger::EpochCheckpoint> =
+                vec![Faker.fake(), Faker.fake()];
+            assert!(db
+                .update(|txn| txn.store_checkpoints(&checkpoints))
+                .is_ok());
+
+            db.view(|txn| {
+                let stored =
+                    txn.fetch_checkpoints().expect("should not return error");
+                assert_eq!(stored, checkpoints);
+            });
+        });
+    }
+
     #[test]
     /// Ensures delete_block fn removes all keys of a single block
     fn test_delete_block() {
@@ -0,0 +1,540 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Versioning of the chain database schema.
+//!
+//! The schema version is stored in `CF_METADATA` and checked whenever the
+//! database is opened. Databases of an older schema are upgraded by the
+//! [`Migrator`], applying in order the [`MIGRATIONS`] rewriting their column
+//! families.
+
+use super::{
+    open_db, serialize_generator_key, HeaderRecord, CF_CANDIDATES,
+    CF_LEDGER_BY_GENERATOR, CF_LEDGER_HEADER, CF_LEDGER_HEIGHT,
+    CF_LEDGER_NULLIFIERS, CF_LEDGER_TXS, CF_METADATA, DB_FOLDER_NAME,
+    MD_SCHEMA_VERSION,
+};
+
+use anyhow::{anyhow, bail, Result};
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::{
+    Certificate, Header, IterationsInfo, Seed, SpentTransaction,
+};
+use node_data::Serializable;
+use rocksdb_lib::checkpoint::Checkpoint;
+use rocksdb_lib::{
+    ColumnFamily, IteratorMode, OptimisticTransactionDB,
+    WriteBatchWithTransaction,
+};
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+/// Schema version of the databases created by this node.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Schema version of the databases created before the version was stored,
+/// whose block headers carry no equivocation evidence and whose ledger isn't
+/// indexed by generator and nullifier.
+const UNVERSIONED: u32 = 1;
+
+/// Number of writes a migration batches together.
+const BATCH_SIZE: usize = 10_000;
+
+/// A migration upgrading a database from a schema version to the next one.
+pub struct Migration {
+    pub from: u32,
+    pub description: &'static str,
+    /// Rewrites the column families, returning the number of records
+    /// written. Nothing is written if `dry_run` is set.
+    run: fn(&OptimisticTransactionDB, dry_run: bool) -> Result<usize>,
+}
+
+/// List of migrations, ordered by the version they upgrade from.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "add the equivocation evidence to ledger headers, index \
+                  ledger blocks by generator and transactions by nullifier, \
+                  and drop candidate blocks",
+    run: upgrade_ledger,
+}];
+
+/// The outcome of a migration.
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    /// Number of records written, or to be written on a dry run
+    pub records: usize,
+}
+
+/// Checks the schema version of a database being opened, stamping it on
+/// databases being created.
+pub(super) fn check_schema(db: &OptimisticTransactionDB) -> Result<()> {
+    let version = match read_version(db)? {
+        Some(version) => version,
+        None if is_empty(db)? => {
+            write_version(db, SCHEMA_VERSION)?;
+            SCHEMA_VERSION
+        }
+        None => UNVERSIONED,
+    };
+
+    match version {
+        SCHEMA_VERSION => Ok(()),
+        v if v < SCHEMA_VERSION => bail!(
+            "chain database schema version {v} is outdated, it has to be \
+             migrated to version {SCHEMA_VERSION} first"
+        ),
+        v => bail!(
+            "chain database schema version {v} is newer than the supported \
+             version {SCHEMA_VERSION}"
+        ),
+    }
+}
+
+/// Upgrades a chain database to the [`SCHEMA_VERSION`] of this node.
+pub struct Migrator {
+    path: PathBuf,
+    db: OptimisticTransactionDB,
+}
+
+impl Migrator {
+    /// Opens the chain database in the `path` directory, failing if it
+    /// doesn't exist.
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let path = path.as_ref().join(DB_FOLDER_NAME);
+        info!("Opening database in {path:?}");
+
+        let db = open_db(&path, false)?;
+        Ok(Self { path, db })
+    }
+
+    /// Returns the schema version of the database.
+    pub fn version(&self) -> Result<u32> {
+        match read_version(&self.db)? {
+            Some(version) => Ok(version),
+            None if is_empty(&self.db)? => Ok(SCHEMA_VERSION),
+            None => Ok(UNVERSIONED),
+        }
+    }
+
+    /// Lists the migrations to be applied to the database.
+    pub fn pending(&self) -> Result<Vec<&'static Migration>> {
+        let version = self.version()?;
+        if version > SCHEMA_VERSION {
+            bail!(
+                "chain database schema version {version} is newer than the \
+                 supported version {SCHEMA_VERSION}"
+            );
+        }
+
+        (version..SCHEMA_VERSION)
+            .map(|from| {
+                MIGRATIONS.iter().find(|m| m.from == from).ok_or_else(|| {
+                    anyhow!("no migration from schema version {from}")
+                })
+            })
+            .collect()
+    }
+
+    /// Creates a copy of the database next to it, returning its path.
+    ///
+    /// The copy is named after the schema version of the database, and is
+    /// never overwritten.
+    pub fn backup(&self) -> Result<PathBuf> {
+        let version = self.version()?;
+        let mut name = self.path.file_name().unwrap_or_default().to_owned();
+        name.push(format!(".backup-v{version}"));
+        let backup = self.path.with_file_name(name);
+
+        if backup.exists() {
+            bail!("backup {backup:?} already exists");
+        }
+
+        // Metadata writes are not flushed to the WAL automatically
+        self.db.flush_wal(true)?;
+        Checkpoint::new(&self.db)?.create_checkpoint(&backup)?;
+
+        Ok(backup)
+    }
+
+    /// Applies the pending migrations in order, storing the schema version
+    /// reached after each of them.
+    ///
+    /// On a dry run, the migrations are only reported without writing
+    /// anything.
+    pub fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationReport>> {
+        let mut reports = vec![];
+
+        for migration in self.pending()? {
+            let to = migration.from + 1;
+            info!(
+                event = "migrating chain database",
+                from = migration.from,
+                to,
+                dry_run,
+            );
+
+            let records = (migration.run)(&self.db, dry_run)?;
+            if !dry_run {
+                write_version(&self.db, to)?;
+            }
+
+            reports.push(MigrationReport {
+                from: migration.from,
+                to,
+                description: migration.description,
+                records,
+            });
+        }
+
+        Ok(reports)
+    }
+}
+
+fn cf<'db>(
+    db: &'db OptimisticTransactionDB,
+    name: &str,
+) -> Result<&'db ColumnFamily> {
+    db.cf_handle(name)
+        .ok_or_else(|| anyhow!("{name} column family must exist"))
+}
+
+fn read_version(db: &OptimisticTransactionDB) -> Result<Option<u32>> {
+    let Some(value) = db.get_cf(cf(db, CF_METADATA)?, MD_SCHEMA_VERSION)?
+    else {
+        return Ok(None);
+    };
+
+    let version = value
+        .try_into()
+        .map_err(|_| anyhow!("invalid schema version"))?;
+    Ok(Some(u32::from_le_bytes(version)))
+}
+
+fn write_version(db: &OptimisticTransactionDB, version: u32) -> Result<()> {
+    db.put_cf(
+        cf(db, CF_METADATA)?,
+        MD_SCHEMA_VERSION,
+        version.to_le_bytes(),
+    )?;
+
+    // CF_METADATA is written with manual WAL flush
+    db.flush_wal(true)?;
+    Ok(())
+}
+
+/// Returns true if no block has been stored in the ledger yet.
+fn is_empty(db: &OptimisticTransactionDB) -> Result<bool> {
+    let mut iter = db.raw_iterator_cf(cf(db, CF_LEDGER_HEIGHT)?);
+    iter.seek_to_first();
+    iter.status()?;
+    Ok(!iter.valid())
+}
+
+/// Version 1 to 2:
+/// - rewrites the `CF_LEDGER_HEADER` records in the header encoding having the
+///   equivocation evidence, with none;
+/// - fills `CF_LEDGER_BY_GENERATOR` and `CF_LEDGER_NULLIFIERS` from the blocks
+///   already in the ledger;
+/// - deletes the `CF_CANDIDATES` blocks, only kept until their round is over.
+///
+/// Header records already in the new encoding, written by an interrupted run
+/// of the migration, are indexed without being rewritten.
+fn upgrade_ledger(
+    db: &OptimisticTransactionDB,
+    dry_run: bool,
+) -> Result<usize> {
+    let headers_cf = cf(db, CF_LEDGER_HEADER)?;
+    let txs_cf = cf(db, CF_LEDGER_TXS)?;
+    let by_generator_cf = cf(db, CF_LEDGER_BY_GENERATOR)?;
+    let nullifiers_cf = cf(db, CF_LEDGER_NULLIFIERS)?;
+    let candidates_cf = cf(db, CF_CANDIDATES)?;
+
+    let mut batch = WriteBatchWithTransaction::<true>::default();
+    let mut records = 0;
+
+    for item in db.iterator_cf(headers_cf, IteratorMode::Start) {
+        let (key, value) = item?;
+
+        let record = match read_v1_header_record(&value) {
+            Ok(record) => {
+                let mut buf = vec![];
+                record.write(&mut buf)?;
+                batch.put_cf(headers_cf, &key, buf);
+                record
+            }
+            Err(err) => read_all::<HeaderRecord>(&value)
+                .map_err(|_| anyhow!("invalid ledger header {key:?}: {err}"))?,
+        };
+        let header = record.header;
+
+        batch.put_cf(
+            by_generator_cf,
+            serialize_generator_key(
+                &header.generator_bls_pubkey,
+                header.height,
+            ),
+            header.hash,
+        );
+
+        for tx_id in record.transactions_ids {
+            let tx = db
+                .get_cf(txs_cf, tx_id)?
+                .ok_or_else(|| anyhow!("missing ledger tx {tx_id:?}"))?;
+            let tx = SpentTransaction::read(&mut &tx[..])?;
+
+            for n in tx.inner.inner.nullifiers().iter() {
+                batch.put_cf(nullifiers_cf, n.to_bytes(), tx_id);
+            }
+        }
+
+        if batch.len() >= BATCH_SIZE {
+            records += batch.len();
+            let batch = std::mem::take(&mut batch);
+            if !dry_run {
+                db.write(batch)?;
+            }
+        }
+    }
+
+    for item in db.iterator_cf(candidates_cf, IteratorMode::Start) {
+        let (key, _) = item?;
+        batch.delete_cf(candidates_cf, key);
+    }
+
+    records += batch.len();
+    if !dry_run {
+        db.write(batch)?;
+    }
+
+    Ok(records)
+}
+
+/// Reads a `CF_LEDGER_HEADER` record of schema version 1, requiring all the
+/// bytes to be consumed.
+fn read_v1_header_record(mut buf: &[u8]) -> io::Result<HeaderRecord> {
+    let r = &mut buf;
+    let header = read_v1_header(r)?;
+
+    let len = HeaderRecord::read_u32_le(r)?;
+    let transactions_ids = (0..len)
+        .map(|_| HeaderRecord::read_bytes(r))
+        .collect::<io::Result<_>>()?;
+
+    if !r.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "trailing bytes after the header record",
+        ));
+    }
+
+    Ok(HeaderRecord {
+        header,
+        transactions_ids,
+    })
+}
+
+/// Reads a header in the encoding of schema version 1, which has no
+/// equivocation evidence.
+fn read_v1_header<R: Read>(r: &mut R) -> io::Result<Header> {
+    let version = Header::read_u8(r)?;
+    let height = Header::read_u64_le(r)?;
+    let timestamp = Header::read_u64_le(r)?;
+    let prev_block_hash = Header::read_bytes(r)?;
+    let seed = Header::read_bytes(r)?;
+    let state_hash = Header::read_bytes(r)?;
+    let event_hash = Header::read_bytes(r)?;
+    let generator_bls_pubkey = Header::read_bytes(r)?;
+    let txroot = Header::read_bytes(r)?;
+    let gas_limit = Header::read_u64_le(r)?;
+    let iteration = Header::read_u8(r)?;
+    let prev_block_cert = Certificate::read(r)?;
+    let failed_iterations = IterationsInfo::read(r)?;
+    let cert = Certificate::read(r)?;
+    let hash = Header::read_bytes(r)?;
+
+    Ok(Header {
+        version,
+        height,
+        timestamp,
+        prev_block_hash,
+        seed: Seed::from(seed),
+        state_hash,
+        event_hash,
+        generator_bls_pubkey: PublicKeyBytes(generator_bls_pubkey),
+        txroot,
+        gas_limit,
+        iteration,
+        prev_block_cert,
+        failed_iterations,
+        evidence: vec![],
+        hash,
+        cert,
+    })
+}
+
+/// Reads a value, requiring all the bytes to be consumed.
+fn read_all<T: Serializable>(mut buf: &[u8]) -> io::Result<T> {
+    let value = T::read(&mut buf)?;
+    if !buf.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "trailing bytes after the value",
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Backend;
+    use super::*;
+
+    use crate::database::{Candidate, Ledger, DB};
+
+    use fake::{Fake, Faker};
+    use node_data::ledger::{self, Label};
+
+    #[test]
+    fn test_migrations_are_complete() {
+        for (from, migration) in (UNVERSIONED..SCHEMA_VERSION).zip(MIGRATIONS) {
+            assert_eq!(migration.from, from);
+        }
+        assert_eq!(
+            MIGRATIONS.len() as u32,
+            SCHEMA_VERSION - UNVERSIONED,
+            "every schema version needs a migration"
+        );
+    }
+
+    /// Writes a header in the encoding of schema version 1.
+    fn write_v1_header(header: &Header, w: &mut Vec<u8>) {
+        w.push(header.version);
+        w.extend_from_slice(&header.height.to_le_bytes());
+        w.extend_from_slice(&header.timestamp.to_le_bytes());
+        w.extend_from_slice(&header.prev_block_hash);
+        w.extend_from_slice(header.seed.inner());
+        w.extend_from_slice(&header.state_hash);
+        w.extend_from_slice(&header.event_hash);
+        w.extend_from_slice(header.generator_bls_pubkey.inner());
+        w.extend_from_slice(&header.txroot);
+        w.extend_from_slice(&header.gas_limit.to_le_bytes());
+        w.push(header.iteration);
+        header.prev_block_cert.write(w).unwrap();
+        header.failed_iterations.write(w).unwrap();
+        header.cert.write(w).unwrap();
+        w.extend_from_slice(&header.hash);
+    }
+
+    #[test]
+    fn test_migrate_unversioned() {
+        let dir = tempdir::TempDir::new("test_migrate_unversioned")
+            .expect("Temp directory to be created");
+        let path = dir.path();
+
+        // Fake transactions share their nullifiers, thus only one is stored
+        let b: ledger::Block = Faker.fake();
+        let tx = b.txs()[0].clone();
+        let mut header = b.header().clone();
+        header.evidence = vec![];
+        let spent = SpentTransaction {
+            inner: tx.clone(),
+            block_height: header.height,
+            gas_spent: 0,
+            err: None,
+        };
+
+        // Write the ledger of a node predating the schema versioning
+        {
+            let db = open_db(&path.join(DB_FOLDER_NAME), true)
+                .expect("database to be created");
+
+            let mut record = vec![];
+            write_v1_header(&header, &mut record);
+            record.extend_from_slice(&1u32.to_le_bytes());
+            record.extend_from_slice(&tx.hash());
+            db.put_cf(cf(&db, CF_LEDGER_HEADER).unwrap(), header.hash, record)
+                .unwrap();
+
+            let mut buf = vec![];
+            spent.write(&mut buf).unwrap();
+            db.put_cf(cf(&db, CF_LEDGER_TXS).unwrap(), tx.hash(), buf)
+                .unwrap();
+
+            let mut buf = header.hash.to_vec();
+            Label::Final.write(&mut buf).unwrap();
+            db.put_cf(
+                cf(&db, CF_LEDGER_HEIGHT).unwrap(),
+                header.height.to_le_bytes(),
+                buf,
+            )
+            .unwrap();
+
+            let mut candidate = vec![];
+            write_v1_header(&header, &mut candidate);
+            candidate.extend_from_slice(&0u32.to_le_bytes());
+            db.put_cf(cf(&db, CF_CANDIDATES).unwrap(), [1; 32], candidate)
+                .unwrap();
+        }
+
+        let migrator = Migrator::open(path).expect("database to be opened");
+        assert_eq!(migrator.version().unwrap(), UNVERSIONED);
+
+        // A dry run reports the migrations without applying them. Records
+        // are the header, its generator index, the nullifiers and the
+        // candidate.
+        let reports = migrator.migrate(true).expect("dry run to succeed");
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].records, 3 + tx.inner.nullifiers().len());
+        assert_eq!(migrator.version().unwrap(), UNVERSIONED);
+
+        let backup = migrator.backup().expect("backup to be created");
+        assert!(backup.exists());
+        assert!(migrator.backup().is_err());
+
+        let reports = migrator.migrate(false).expect("migration to succeed");
+        assert_eq!(reports[0].to, SCHEMA_VERSION);
+        assert_eq!(migrator.version().unwrap(), SCHEMA_VERSION);
+        assert!(migrator.pending().unwrap().is_empty());
+        drop(migrator);
+
+        let db: Backend = Backend::create_or_open(path);
+        db.view(|txn| {
+            let (migrated, tx_ids) = txn
+                .fetch_block_header(&header.hash)
+                .expect("header to be fetched")
+                .expect("header to exist");
+            assert_eq!(migrated, header);
+            assert_eq!(tx_ids, vec![tx.hash()]);
+
+            let hashes = txn
+                .fetch_block_hashes_by_generator(
+                    &header.generator_bls_pubkey,
+                    0,
+                    u64::MAX,
+                )
+                .expect("hashes to be fetched");
+            assert_eq!(hashes, vec![header.hash]);
+
+            let nullifier = tx.inner.nullifiers()[0].to_bytes();
+            let spent = txn
+                .get_ledger_tx_by_nullifier(&nullifier)
+                .expect("tx to be fetched")
+                .expect("tx to exist");
+            assert_eq!(spent.inner.hash(), tx.hash());
+
+            let candidate = txn
+                .fetch_candidate_block(&[1; 32])
+                .expect("candidates to be readable");
+            assert!(candidate.is_none());
+        });
+    }
+}
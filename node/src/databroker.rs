@@ -5,14 +5,17 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 pub mod conf;
+mod snapshot;
 
 use crate::database::{Candidate, Ledger, Mempool};
+use crate::metrics::metrics;
 use crate::{database, vm, Network};
 use crate::{LongLivedService, Message};
 use anyhow::{anyhow, Result};
 
 use node_data::message::payload::{GetData, InvParam, InvType};
 use smallvec::SmallVec;
+use snapshot::SnapshotCache;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -28,6 +31,8 @@ const TOPICS: &[u8] = &[
     Topics::GetInv as u8,
     Topics::GetData as u8,
     Topics::GetCandidate as u8,
+    Topics::GetStateSnapshot as u8,
+    Topics::GetStateChunk as u8,
 ];
 
 struct Response {
@@ -74,6 +79,9 @@ pub struct DataBrokerSrv {
     /// Limits the number of ongoing requests.
     limit_ongoing_requests: Arc<Semaphore>,
 
+    /// State snapshot served to fast syncing peers
+    snapshots: SnapshotCache,
+
     conf: conf::Params,
 }
 
@@ -85,6 +93,7 @@ impl DataBrokerSrv {
             conf,
             requests: AsyncQueue::unbounded(),
             limit_ongoing_requests: Arc::new(Semaphore::new(permits)),
+            snapshots: SnapshotCache::default(),
         }
     }
 }
@@ -106,7 +115,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         &mut self,
         network: Arc<RwLock<N>>,
         db: Arc<RwLock<DB>>,
-        _vm: Arc<RwLock<VM>>,
+        vm: Arc<RwLock<VM>>,
     ) -> anyhow::Result<usize> {
         if self.conf.max_ongoing_requests == 0 {
             return Err(anyhow!("max_ongoing_requests must be greater than 0"));
@@ -134,11 +143,15 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
             let network = network.clone();
             let db = db.clone();
+            let vm = vm.clone();
+            let snapshots = self.snapshots.clone();
             let conf = self.conf.clone();
 
             // Spawn a task to handle the request asynchronously.
             tokio::spawn(async move {
-                match Self::handle_request(&db, &msg, &conf).await {
+                let resp =
+                    Self::handle_request(&db, &vm, &snapshots, &msg, &conf);
+                match resp.await {
                     Ok(resp) => {
                         // Send response
                         let net = network.read().await;
@@ -177,8 +190,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
 impl DataBrokerSrv {
     /// Handles inbound messages.
-    async fn handle_request<DB: database::DB>(
+    async fn handle_request<DB: database::DB, VM: vm::VMExecution>(
         db: &Arc<RwLock<DB>>,
+        vm: &Arc<RwLock<VM>>,
+        snapshots: &SnapshotCache,
         msg: &Message,
         conf: &conf::Params,
     ) -> anyhow::Result<Response> {
@@ -191,6 +206,10 @@ impl DataBrokerSrv {
             .ok_or_else(|| anyhow::anyhow!("invalid metadata src_addr"))?;
 
         debug!(event = "handle_request", ?msg);
+        metrics()
+            .databroker_requests
+            .with_label_values(&[&format!("{:?}", msg.topic())])
+            .inc();
 
         match &msg.payload {
             // Handle GetCandidate requests
@@ -220,6 +239,18 @@ impl DataBrokerSrv {
                     Self::handle_get_data(db, m, conf.max_inv_entries).await?;
                 Ok(Response::new(msgs, recv_peer))
             }
+            // Handle GetStateSnapshot requests
+            Payload::GetStateSnapshot(_) => {
+                let snapshot = snapshots.latest(db, vm).await?;
+                let msg = Message::new_state_snapshot(snapshot.info.clone());
+                Ok(Response::new_from_msg(msg, recv_peer))
+            }
+            // Handle GetStateChunk requests
+            Payload::GetStateChunk(m) => {
+                let chunk = snapshots.chunk(m).await?;
+                let msg = Message::new_state_chunk(chunk);
+                Ok(Response::new_from_msg(msg, recv_peer))
+            }
             _ => Err(anyhow::anyhow!("unhandled message payload")),
         }
     }
@@ -0,0 +1,190 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use node_data::ledger::{self, Label, SpentTransaction};
+use node_data::message::payload::{GetStateChunk, StateChunk, StateSnapshot};
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::database::rocksdb::MD_HASH_KEY;
+use crate::database::{self, Ledger, Metadata};
+use crate::vm;
+
+/// Size of the chunks a state snapshot is served in.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Time a snapshot is served for before a more recent one is archived, which
+/// bounds how often peers requesting snapshots can have the state archived.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// A snapshot of the VM state at a final block, split into chunks.
+pub(super) struct Snapshot {
+    pub info: StateSnapshot,
+    chunks: Vec<StateChunk>,
+}
+
+impl Snapshot {
+    fn new(
+        header: ledger::Header,
+        txs: Vec<SpentTransaction>,
+        next: ledger::Header,
+        data: Vec<u8>,
+    ) -> Self {
+        let state_root = header.state_hash;
+        let chunks: Vec<_> = data
+            .chunks(CHUNK_SIZE)
+            .enumerate()
+            .map(|(index, data)| StateChunk {
+                state_root,
+                index: index as u32,
+                data: data.to_vec(),
+            })
+            .collect();
+
+        let info = StateSnapshot {
+            header,
+            txs,
+            next,
+            chunk_hashes: chunks.iter().map(StateChunk::hash).collect(),
+        };
+
+        Self { info, chunks }
+    }
+}
+
+/// Keeps a recent state snapshot in memory, along with the time it was last
+/// refreshed, so that it is archived only once for all the peers downloading
+/// it.
+#[derive(Clone, Default)]
+pub(super) struct SnapshotCache(Arc<RwLock<Option<CachedSnapshot>>>);
+
+type CachedSnapshot = (Instant, Arc<Snapshot>);
+
+impl SnapshotCache {
+    /// Returns the snapshot of the state at the most recent final block
+    /// having a successor.
+    ///
+    /// The cached snapshot is served until it is [`REFRESH_INTERVAL`] old,
+    /// however many final blocks were accepted since, and is only archived
+    /// again if the final block changed by then.
+    pub async fn latest<DB: database::DB, VM: vm::VMExecution>(
+        &self,
+        db: &Arc<RwLock<DB>>,
+        vm: &Arc<RwLock<VM>>,
+    ) -> Result<Arc<Snapshot>> {
+        let is_fresh = |cache: &Option<CachedSnapshot>| {
+            cache
+                .as_ref()
+                .filter(|(refreshed, _)| refreshed.elapsed() < REFRESH_INTERVAL)
+                .map(|(_, snapshot)| snapshot.clone())
+        };
+
+        if let Some(snapshot) = is_fresh(&*self.0.read().await) {
+            return Ok(snapshot);
+        }
+
+        let mut cache = self.0.write().await;
+
+        // The snapshot may have been refreshed while waiting for the lock
+        if let Some(snapshot) = is_fresh(&cache) {
+            return Ok(snapshot);
+        }
+
+        let (header, txs, next) =
+            db.read().await.view(|t| Self::snapshot_blocks(&t))?;
+
+        if let Some((refreshed, snapshot)) = cache.as_mut() {
+            if snapshot.info.header.hash == header.hash {
+                *refreshed = Instant::now();
+                return Ok(snapshot.clone());
+            }
+        }
+
+        let data = vm.read().await.state_snapshot(header.state_hash)?;
+
+        info!(
+            event = "state snapshot archived",
+            height = header.height,
+            state_root = hex::encode(header.state_hash),
+            size = data.len(),
+        );
+
+        let snapshot = Arc::new(Snapshot::new(header, txs, next, data));
+        *cache = Some((Instant::now(), snapshot.clone()));
+
+        Ok(snapshot)
+    }
+
+    /// Returns the requested chunk of the cached snapshot.
+    pub async fn chunk(&self, req: &GetStateChunk) -> Result<StateChunk> {
+        self.0
+            .read()
+            .await
+            .as_ref()
+            .map(|(_, snapshot)| snapshot)
+            .filter(|s| s.info.header.state_hash == req.state_root)
+            .and_then(|s| s.chunks.get(req.index as usize))
+            .cloned()
+            .ok_or_else(|| anyhow!("state chunk not available"))
+    }
+
+    /// Returns the header and transactions of the most recent final block
+    /// having a successor, along with the header of the successor.
+    fn snapshot_blocks<T: Ledger + Metadata>(
+        t: &T,
+    ) -> Result<(ledger::Header, Vec<SpentTransaction>, ledger::Header)> {
+        let tip_hash = t
+            .op_read(MD_HASH_KEY)?
+            .ok_or_else(|| anyhow!("could not find tip"))?;
+        let (tip, _) = t
+            .fetch_block_header(&tip_hash)?
+            .ok_or_else(|| anyhow!("could not find tip header"))?;
+
+        let mut final_height = tip.height;
+        while t.fetch_block_label_by_height(final_height)? != Some(Label::Final)
+        {
+            final_height = final_height
+                .checked_sub(1)
+                .ok_or_else(|| anyhow!("could not find a final block"))?;
+        }
+
+        // Blocks preceding a final one are final as well
+        let height = if final_height == tip.height {
+            final_height.saturating_sub(1)
+        } else {
+            final_height
+        };
+
+        if height == 0 {
+            return Err(anyhow!("no state to snapshot"));
+        }
+
+        let fetch_header = |height| {
+            let hash = t
+                .fetch_block_hash_by_height(height)?
+                .ok_or_else(|| anyhow!("could not find block {height}"))?;
+            t.fetch_block_header(&hash)?
+                .ok_or_else(|| anyhow!("could not find block {height}"))
+        };
+
+        let (header, tx_hashes) = fetch_header(height)?;
+        let (next, _) = fetch_header(height + 1)?;
+
+        let txs = tx_hashes
+            .iter()
+            .map(|hash| {
+                t.get_ledger_tx_by_hash(hash)?
+                    .ok_or_else(|| anyhow!("could not find transaction"))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok((header, txs, next))
+    }
+}
@@ -8,6 +8,7 @@ pub mod chain;
 pub mod database;
 pub mod databroker;
 pub mod mempool;
+pub mod metrics;
 pub mod network;
 pub mod vm;
 
@@ -52,6 +53,16 @@ pub trait Network: Send + Sync + 'static {
         amount: usize,
     ) -> anyhow::Result<()>;
 
+    /// Returns a random set of alive peers.
+    async fn alive_nodes(&self, amount: usize) -> Vec<std::net::SocketAddr>;
+
+    /// Penalizes a peer for a misbehaviour, possibly banning it.
+    fn report_peer(
+        &self,
+        peer_addr: std::net::SocketAddr,
+        misbehaviour: network::Misbehaviour,
+    );
+
     /// Sends a Get message and waits for a response with timeout
     async fn send_and_wait(
         &mut self,
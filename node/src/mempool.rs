@@ -5,6 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::database::{Ledger, Mempool};
+use crate::metrics::metrics;
 use crate::{database, vm, LongLivedService, Message, Network};
 use async_trait::async_trait;
 use node_data::ledger::Transaction;
@@ -30,6 +31,19 @@ enum TxAcceptanceError {
     Generic(anyhow::Error),
 }
 
+impl TxAcceptanceError {
+    /// The reason of the rejection, as reported in the metrics.
+    fn reason(&self) -> &'static str {
+        match self {
+            Self::AlreadyExistsInMempool => "already_in_mempool",
+            Self::AlreadyExistsInLedger => "already_in_ledger",
+            Self::NullifierExistsInMempool => "nullifier_in_mempool",
+            Self::VerificationFailed(_) => "verification_failed",
+            Self::Generic(_) => "generic",
+        }
+    }
+}
+
 impl From<anyhow::Error> for TxAcceptanceError {
     fn from(err: anyhow::Error) -> Self {
         Self::Generic(err)
@@ -102,6 +116,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                         let accept = self.accept_tx::<DB, VM>(&db, &vm, tx);
                         if let Err(e) = accept.await {
                             error!("{}", e);
+                            metrics()
+                                .txs_rejected
+                                .with_label_values(&[e.reason()])
+                                .inc();
                             continue;
                         }
 
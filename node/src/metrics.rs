@@ -0,0 +1,143 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Prometheus metrics of the node, registered in the default registry.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use dusk_consensus::metrics::step_label;
+use node_data::message::Topics;
+use node_data::StepName;
+use prometheus::{
+    register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge,
+};
+
+pub struct Metrics {
+    /// Height of the most recent block.
+    pub chain_height: IntGauge,
+    /// Height of the most recent final block.
+    pub finalized_height: IntGauge,
+    /// Iteration at which the accepted blocks were produced.
+    pub block_iteration: Histogram,
+    /// Duration of the consensus steps, by step.
+    pub step_duration: HistogramVec,
+    /// Number of transactions in the mempool.
+    pub mempool_txs: IntGauge,
+    /// Size of the transactions in the mempool, in bytes.
+    pub mempool_bytes: IntGauge,
+    /// Transactions rejected by the mempool, by reason.
+    pub txs_rejected: IntCounterVec,
+    /// Latency of the calls to the VM, by operation.
+    pub vm_latency: HistogramVec,
+    /// State commits created while accepting and finalizing blocks.
+    pub state_commits: IntCounter,
+    /// Network messages, by topic and direction.
+    pub network_messages: IntCounterVec,
+    /// Requests handled by the data broker, by type.
+    pub databroker_requests: IntCounterVec,
+}
+
+/// Returns the node metrics, registering them on first use.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics::register().expect("valid metrics"))
+}
+
+impl Metrics {
+    fn register() -> prometheus::Result<Self> {
+        Ok(Self {
+            chain_height: register_int_gauge!(
+                "dusk_chain_height",
+                "Height of the most recent block"
+            )?,
+            finalized_height: register_int_gauge!(
+                "dusk_chain_finalized_height",
+                "Height of the most recent final block"
+            )?,
+            block_iteration: register_histogram!(
+                "dusk_chain_block_iteration",
+                "Iteration at which the accepted blocks were produced",
+                vec![0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0]
+            )?,
+            step_duration: register_histogram_vec!(
+                "dusk_consensus_step_duration_seconds",
+                "Duration of the consensus steps",
+                &["step"]
+            )?,
+            mempool_txs: register_int_gauge!(
+                "dusk_mempool_txs",
+                "Number of transactions in the mempool"
+            )?,
+            mempool_bytes: register_int_gauge!(
+                "dusk_mempool_bytes",
+                "Size of the transactions in the mempool"
+            )?,
+            txs_rejected: register_int_counter_vec!(
+                "dusk_mempool_txs_rejected_total",
+                "Transactions rejected by the mempool, by reason",
+                &["reason"]
+            )?,
+            vm_latency: register_histogram_vec!(
+                "dusk_vm_latency_seconds",
+                "Latency of the calls to the VM",
+                &["op"]
+            )?,
+            state_commits: register_int_counter!(
+                "dusk_state_commits_total",
+                "State commits created while accepting and finalizing blocks"
+            )?,
+            network_messages: register_int_counter_vec!(
+                "dusk_network_messages_total",
+                "Network messages, by topic and direction",
+                &["topic", "direction"]
+            )?,
+            databroker_requests: register_int_counter_vec!(
+                "dusk_databroker_requests_total",
+                "Requests handled by the data broker, by type",
+                &["request"]
+            )?,
+        })
+    }
+
+    pub(crate) fn step_elapsed(&self, step: StepName, elapsed: Duration) {
+        self.step_duration
+            .with_label_values(&[step_label(step)])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Calls `f`, recording its latency as the given VM operation.
+    pub(crate) fn vm_call<T>(&self, op: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let ret = f();
+        self.vm_latency
+            .with_label_values(&[op])
+            .observe(start.elapsed().as_secs_f64());
+        ret
+    }
+
+    pub(crate) fn message(&self, topic: Topics, direction: &str) {
+        self.network_messages
+            .with_label_values(&[&format!("{topic:?}"), direction])
+            .inc();
+    }
+
+    /// Sets the mempool gauges to the number and size of the transactions
+    /// persisted in the mempool.
+    pub(crate) fn mempool_persisted(&self, txs: i64, bytes: i64) {
+        self.mempool_txs.set(txs);
+        self.mempool_bytes.set(bytes);
+    }
+
+    /// Adds the given change in the number and size of the transactions to
+    /// the mempool gauges.
+    pub(crate) fn mempool_changed(&self, txs: i64, bytes: i64) {
+        self.mempool_txs.add(txs);
+        self.mempool_bytes.add(bytes);
+    }
+}
@@ -4,32 +4,75 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::collections::HashMap;
 use std::net::{AddrParseError, SocketAddr};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::{BoxedFilter, Message};
+use crate::metrics::metrics;
+use crate::{BoxedFilter, Message, Network};
 use async_trait::async_trait;
 use kadcast::config::Config;
 use kadcast::{MessageInfo, Peer};
-use node_data::message::Metadata;
+use node_data::message::payload::{Capabilities, Handshake};
 use node_data::message::{AsyncQueue, Topics};
+use node_data::message::{Metadata, Payload};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
 use tokio::time::{self, Instant};
 use tracing::{error, info, trace, warn};
 
 mod frame;
+mod local;
+mod reputation;
+
+pub use frame::{Compression, Pdu, Version, PROTOCOL_VERSION};
+pub use local::{Interceptor, LocalHub, LocalNetwork, Verdict};
+pub use reputation::{Misbehaviour, PeerScore, Reputation};
 
 const MAX_PENDING_SENDERS: u64 = 1000;
 
+/// Number of peers whose protocol version is tracked.
+const MAX_KNOWN_PEERS: usize = 10_000;
+
+/// Number of handshakes waiting to be sent, beyond which any further one is
+/// dropped.
+const MAX_PENDING_HANDSHAKES: usize = 1000;
+
 type RoutesList<const N: usize> = [Option<AsyncQueue<Message>>; N];
 type FilterList<const N: usize> = [Option<BoxedFilter>; N];
+type LastSeen = Arc<Mutex<HashMap<SocketAddr, SystemTime>>>;
+type PeerVersions = Arc<Mutex<HashMap<SocketAddr, PeerVersion>>>;
+
+/// The protocol version and capabilities a peer communicates with.
+#[derive(Debug, Clone, Copy)]
+pub struct PeerVersion {
+    pub version: Version,
+    /// Capabilities announced by the peer, if it has sent a handshake
+    pub capabilities: Option<Capabilities>,
+}
+
+/// Capabilities of every node, regardless of its configuration.
+const NATIVE_CAPABILITIES: Capabilities = Capabilities::COMPRESSION;
+
+/// A handshake to be sent to a peer.
+type HandshakeRequest = (SocketAddr, bool);
 
 pub struct Listener<const N: usize> {
     routes: Arc<RwLock<RoutesList<N>>>,
     filters: Arc<RwLock<FilterList<N>>>,
 
+    /// Time the last message was received from each peer.
+    last_seen: LastSeen,
+
+    reputation: Reputation,
+
+    /// Version of the protocol spoken by each peer.
+    peer_versions: PeerVersions,
+
+    /// Peers to send a handshake to.
+    handshakes: AsyncQueue<HandshakeRequest>,
+
     /// Number of awaiting senders.
     pending_senders: Arc<AtomicU64>,
 }
@@ -62,6 +105,48 @@ impl<const N: usize> Listener<N> {
         Ok(())
     }
 
+    /// Records the protocol version of a peer, returning true if it is the
+    /// first message received from it.
+    ///
+    /// Once [`MAX_KNOWN_PEERS`] are known, new peers are not recorded and
+    /// false is returned, for them not to be handshaken on every message.
+    fn record_version(&self, peer: SocketAddr, version: Version) -> bool {
+        let Ok(mut peers) = self.peer_versions.lock() else {
+            return false;
+        };
+
+        if let Some(known) = peers.get_mut(&peer) {
+            known.version = version;
+            return false;
+        }
+
+        if peers.len() >= MAX_KNOWN_PEERS {
+            return false;
+        }
+
+        peers.insert(
+            peer,
+            PeerVersion {
+                version,
+                capabilities: None,
+            },
+        );
+
+        true
+    }
+
+    fn on_handshake(&self, peer: SocketAddr, handshake: &Handshake) {
+        if let Ok(mut peers) = self.peer_versions.lock() {
+            if let Some(known) = peers.get_mut(&peer) {
+                known.capabilities = Some(handshake.capabilities);
+            }
+        }
+
+        if handshake.reply {
+            let _ = self.handshakes.try_send((peer, false));
+        }
+    }
+
     fn call_filters(
         &self,
         topic: impl Into<u8>,
@@ -78,8 +163,15 @@ impl<const N: usize> Listener<N> {
 
 impl<const N: usize> kadcast::NetworkListen for Listener<N> {
     fn on_message(&self, blob: Vec<u8>, md: MessageInfo) {
+        // Discard messages of banned or flooding peers
+        if !self.reputation.on_message(md.src()) {
+            trace!("discard message from peer {}", md.src());
+            return;
+        }
+
         match frame::Pdu::decode(&mut &blob.to_vec()[..]) {
             Ok(d) => {
+                let is_new = self.record_version(md.src(), d.header.version);
                 let mut msg = d.payload;
 
                 // Update Transport Data
@@ -88,6 +180,21 @@ impl<const N: usize> kadcast::NetworkListen for Listener<N> {
                     src_addr: md.src(),
                 });
 
+                metrics().message(msg.topic(), "inbound");
+                if let Ok(mut last_seen) = self.last_seen.lock() {
+                    last_seen.insert(md.src(), SystemTime::now());
+                }
+
+                // Handshakes are answered by the network layer itself, while
+                // any other peer is asked for one on first contact
+                if let Payload::Handshake(handshake) = &msg.payload {
+                    self.on_handshake(md.src(), handshake);
+                    return;
+                }
+                if is_new {
+                    let _ = self.handshakes.try_send((md.src(), true));
+                }
+
                 // Allow upper layers to fast-discard a message before queueing
                 if let Err(e) = self.call_filters(msg.topic(), &msg) {
                     info!("discard message due to {e}");
@@ -99,21 +206,40 @@ impl<const N: usize> kadcast::NetworkListen for Listener<N> {
                     error!("could not reroute due to {e}");
                 }
             }
+            Err(frame::DecodeError::IncompatibleVersion(version)) => {
+                if self.record_version(md.src(), version) {
+                    warn!(
+                        event = "incompatible peer",
+                        peer = %md.src(),
+                        %version,
+                        local_version = %PROTOCOL_VERSION,
+                    );
+                }
+            }
             Err(err) => {
                 // Dump message blob and topic number
                 let topic = blob.get(node_data::message::TOPIC_FIELD_POS);
                 error!("err: {err}, msg_topic: {topic:?}",);
+                self.reputation.report(md.src(), Misbehaviour::InvalidFrame);
             }
         };
     }
 }
 
 pub struct Kadcast<const N: usize> {
-    peer: Peer,
+    peer: Arc<Peer>,
     routes: Arc<RwLock<RoutesList<N>>>,
     filters: Arc<RwLock<FilterList<N>>>,
+    last_seen: LastSeen,
+    reputation: Reputation,
+    peer_versions: PeerVersions,
+    capabilities: Arc<AtomicU64>,
     conf: Config,
 
+    /// Size above which messages sent to peers decoding compressed frames
+    /// are compressed, if enabled.
+    compression_threshold: Option<usize>,
+
     counter: AtomicU64,
 }
 
@@ -129,22 +255,120 @@ impl<const N: usize> Kadcast<N> {
             "Loading network with public_address {} and private_address {:?}",
             &conf.public_address, &conf.listen_address
         );
+        let last_seen = LastSeen::default();
+        let reputation = Reputation::default();
+        let peer_versions = PeerVersions::default();
+        let handshakes = AsyncQueue::bounded(MAX_PENDING_HANDSHAKES);
         let listener = Listener {
             routes: routes.clone(),
             filters: filters.clone(),
+            last_seen: last_seen.clone(),
+            reputation: reputation.clone(),
+            peer_versions: peer_versions.clone(),
+            handshakes: handshakes.clone(),
             pending_senders: Arc::new(AtomicU64::new(0)),
         };
-        let peer = Peer::new(conf.clone(), listener)?;
+        let peer = Arc::new(Peer::new(conf.clone(), listener)?);
+
+        let capabilities = Arc::new(AtomicU64::new(NATIVE_CAPABILITIES.0));
+        tokio::spawn(Self::send_handshakes(
+            peer.clone(),
+            handshakes,
+            capabilities.clone(),
+        ));
 
         Ok(Kadcast {
             routes,
             filters,
+            last_seen,
+            reputation,
+            peer_versions,
+            capabilities,
             peer,
             conf,
+            compression_threshold: None,
             counter: AtomicU64::new(0),
         })
     }
 
+    /// Enables the compression of messages whose size is at least the given
+    /// threshold.
+    ///
+    /// Only the messages sent to a single peer having announced
+    /// [`Capabilities::COMPRESSION`] are compressed, since broadcast ones
+    /// are relayed to peers whose capabilities are unknown.
+    pub fn with_compression(mut self, threshold: Option<usize>) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
+    /// Sets the capabilities announced to peers, along with the ones every
+    /// node has.
+    pub fn with_capabilities(self, capabilities: Capabilities) -> Self {
+        let capabilities = capabilities.union(NATIVE_CAPABILITIES);
+        self.capabilities.store(capabilities.0, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the compression threshold of the messages sent to a peer,
+    /// if it announced decoding compressed frames.
+    fn compression_threshold(&self, peer_addr: &SocketAddr) -> Option<usize> {
+        let compresses = self
+            .peer_versions
+            .lock()
+            .ok()
+            .and_then(|peers| peers.get(peer_addr)?.capabilities)
+            .is_some_and(|c| c.contains(Capabilities::COMPRESSION));
+
+        self.compression_threshold.filter(|_| compresses)
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities(self.capabilities.load(Ordering::Relaxed))
+    }
+
+    /// Sends the handshakes requested by the listener.
+    async fn send_handshakes(
+        peer: Arc<Peer>,
+        handshakes: AsyncQueue<HandshakeRequest>,
+        capabilities: Arc<AtomicU64>,
+    ) {
+        // A handshake has to bypass the kadcast dupemap of peers having
+        // received one before a restart of this node
+        let mut counter = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+
+        while let Ok((peer_addr, reply)) = handshakes.recv().await {
+            let capabilities =
+                Capabilities(capabilities.load(Ordering::Relaxed));
+            let msg = Message::new_handshake(Handshake {
+                capabilities,
+                reply,
+            });
+
+            counter = counter.wrapping_add(1);
+            match frame::Pdu::encode(&msg, counter, None) {
+                Ok(encoded) => {
+                    trace!("sending handshake to peer {peer_addr}");
+                    peer.send(&encoded, peer_addr).await;
+                    metrics().message(msg.topic(), "outbound");
+                }
+                Err(err) => error!("could not encode handshake: {err}"),
+            }
+        }
+    }
+
+    /// Returns the protocol version of the peers a message was received
+    /// from.
+    pub fn peer_versions(&self) -> Vec<(SocketAddr, PeerVersion)> {
+        self.peer_versions
+            .lock()
+            .map(|peers| peers.iter().map(|(a, v)| (*a, *v)).collect())
+            .unwrap_or_default()
+    }
+
     pub fn route_internal(&self, msg: Message) {
         let topic = msg.topic() as usize;
         let routes = self.routes.clone();
@@ -167,8 +391,27 @@ impl<const N: usize> Kadcast<N> {
         }
     }
 
-    pub async fn alive_nodes(&self, amount: usize) -> Vec<SocketAddr> {
-        self.peer.alive_nodes(amount).await
+    /// Returns the alive peers, along with the time a message was last
+    /// received from each of them.
+    pub async fn alive_peers(
+        &self,
+        amount: usize,
+    ) -> Vec<(SocketAddr, Option<SystemTime>)> {
+        let peers = self.alive_nodes(amount).await;
+        let last_seen = match self.last_seen.lock() {
+            Ok(last_seen) => last_seen,
+            Err(_) => return peers.into_iter().map(|p| (p, None)).collect(),
+        };
+
+        peers
+            .into_iter()
+            .map(|peer| (peer, last_seen.get(&peer).copied()))
+            .collect()
+    }
+
+    /// Lists the scores of the peers having misbehaved.
+    pub fn peer_scores(&self) -> Vec<PeerScore> {
+        self.reputation.scores()
     }
 
     pub fn conf(&self) -> &Config {
@@ -185,13 +428,14 @@ impl<const N: usize> crate::Network for Kadcast<N> {
             None => None,
         };
 
-        let encoded = frame::Pdu::encode(msg, 0).map_err(|err| {
+        let encoded = frame::Pdu::encode(msg, 0, None).map_err(|err| {
             error!("could not encode message {msg:?}: {err}");
             anyhow::anyhow!("failed to broadcast: {err}")
         })?;
 
         trace!("broadcasting msg ({:?})", msg.topic());
         self.peer.broadcast(&encoded, height).await;
+        metrics().message(msg.topic(), "outbound");
 
         Ok(())
     }
@@ -204,13 +448,18 @@ impl<const N: usize> crate::Network for Kadcast<N> {
     ) -> anyhow::Result<()> {
         // rnd_count is added to bypass kadcast dupemap
         let rnd_count = self.counter.fetch_add(1, Ordering::SeqCst);
-        let encoded = frame::Pdu::encode(msg, rnd_count)
-            .map_err(|err| anyhow::anyhow!("failed to send_to_peer: {err}"))?;
+        let encoded = frame::Pdu::encode(
+            msg,
+            rnd_count,
+            self.compression_threshold(&recv_addr),
+        )
+        .map_err(|err| anyhow::anyhow!("failed to send_to_peer: {err}"))?;
         let topic = msg.topic();
 
         info!("sending msg ({topic:?}) to peer {recv_addr}");
 
         self.peer.send(&encoded, recv_addr).await;
+        metrics().message(topic, "outbound");
 
         Ok(())
     }
@@ -221,19 +470,30 @@ impl<const N: usize> crate::Network for Kadcast<N> {
         msg: &Message,
         amount: usize,
     ) -> anyhow::Result<()> {
-        let encoded = frame::Pdu::encode(msg, 0)
+        let encoded = frame::Pdu::encode(msg, 0, None)
             .map_err(|err| anyhow::anyhow!("failed to encode: {err}"))?;
         let topic = msg.topic();
 
-        for recv_addr in self.peer.alive_nodes(amount).await {
+        for recv_addr in self.alive_nodes(amount).await {
             trace!("sending msg ({topic:?}) to peer {recv_addr}");
 
             self.peer.send(&encoded, recv_addr).await;
+            metrics().message(topic, "outbound");
         }
 
         Ok(())
     }
 
+    async fn alive_nodes(&self, amount: usize) -> Vec<SocketAddr> {
+        let mut peers = self.peer.alive_nodes(amount).await;
+        peers.retain(|peer| !self.reputation.is_banned(peer));
+        peers
+    }
+
+    fn report_peer(&self, peer_addr: SocketAddr, misbehaviour: Misbehaviour) {
+        self.reputation.report(peer_addr, misbehaviour);
+    }
+
     /// Route any message of the specified type to this queue.
     async fn add_route(
         &mut self,
@@ -6,9 +6,66 @@
 
 use node_data::message::Message;
 use node_data::Serializable;
+use std::fmt;
 use std::io::{self, Read, Write};
+use thiserror::Error;
 
-const PROTOCOL_VERSION: [u8; 8] = [0, 0, 0, 0, 1, 0, 0, 0];
+/// Version of the wire protocol spoken by this node.
+pub const PROTOCOL_VERSION: Version = Version { major: 0, minor: 3 };
+
+/// Bit of the reserved header field flagging a payload compressed with zstd,
+/// which is only sent to peers announcing [`Capabilities::COMPRESSION`].
+///
+/// [`Capabilities::COMPRESSION`]: node_data::message::payload::Capabilities::COMPRESSION
+const ZSTD_FLAG: u64 = 1 << 63;
+
+/// Maximum size of a decompressed payload, bounding the memory a malicious
+/// frame can make a node allocate.
+const MAX_PAYLOAD_SIZE: usize = 64 * 1024 * 1024;
+
+/// Compression level of zstd, favouring speed as messages are compressed on
+/// the hot path.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Version of the wire protocol.
+///
+/// Nodes on different major versions cannot decode each other's messages,
+/// while a minor version only adds new messages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    pub fn is_compatible(&self, other: &Version) -> bool {
+        self.major == other.major
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Algorithm the payload of a frame is compressed with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+}
+
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("incompatible protocol version {0}")]
+    IncompatibleVersion(Version),
+    #[error("checksum mismatch")]
+    InvalidChecksum,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
 
 /// Defines PDU (Protocol Data Unit) structure.
 #[derive(Debug, Default)]
@@ -20,33 +77,72 @@ pub struct Pdu {
 /// Frame Header definition.
 #[derive(Debug, Default)]
 pub struct Header {
-    version: [u8; 8],
+    pub version: Version,
     reserved: u64,
+    pub compression: Compression,
     checksum: [u8; 4],
 }
 
 impl Pdu {
-    pub fn encode(msg: &Message, reserved: u64) -> io::Result<Vec<u8>> {
+    /// Encodes a message, compressing it if its size is at least the given
+    /// threshold.
+    ///
+    /// The receiver must have announced [`Capabilities::COMPRESSION`] for a
+    /// threshold to be given.
+    ///
+    /// [`Capabilities::COMPRESSION`]: node_data::message::payload::Capabilities::COMPRESSION
+    pub fn encode(
+        msg: &Message,
+        reserved: u64,
+        compression_threshold: Option<usize>,
+    ) -> io::Result<Vec<u8>> {
         let mut payload_buf = vec![];
         msg.write(&mut payload_buf)?;
 
+        let mut compression = Compression::None;
+        if compression_threshold.is_some_and(|t| payload_buf.len() >= t) {
+            let compressed = zstd::bulk::compress(&payload_buf, ZSTD_LEVEL)?;
+
+            // Incompressible payloads are sent as they are
+            if compressed.len() < payload_buf.len() {
+                payload_buf = compressed;
+                compression = Compression::Zstd;
+            }
+        }
+
         let mut header_buf = vec![];
         Header {
             checksum: calc_checksum(&payload_buf[..]),
             version: PROTOCOL_VERSION,
             reserved,
+            compression,
         }
         .write(&mut header_buf)?;
 
         Ok([header_buf, payload_buf].concat())
     }
 
-    pub fn decode<R: Read>(r: &mut R) -> io::Result<Self>
+    pub fn decode<R: Read>(r: &mut R) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
         let header = Header::read(r)?;
-        let payload = Message::read(r)?;
+        if !header.version.is_compatible(&PROTOCOL_VERSION) {
+            return Err(DecodeError::IncompatibleVersion(header.version));
+        }
+
+        let mut payload_buf = vec![];
+        r.read_to_end(&mut payload_buf)?;
+        if calc_checksum(&payload_buf[..]) != header.checksum {
+            return Err(DecodeError::InvalidChecksum);
+        }
+
+        if header.compression == Compression::Zstd {
+            payload_buf =
+                zstd::bulk::decompress(&payload_buf, MAX_PAYLOAD_SIZE)?;
+        }
+
+        let payload = Message::read(&mut &payload_buf[..])?;
 
         Ok(Pdu { header, payload })
     }
@@ -54,8 +150,14 @@ impl Pdu {
 
 impl Serializable for Header {
     fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
-        w.write_all(&self.version)?;
-        w.write_all(&self.reserved.to_le_bytes())?;
+        let mut reserved = self.reserved & !ZSTD_FLAG;
+        if self.compression == Compression::Zstd {
+            reserved |= ZSTD_FLAG;
+        }
+
+        w.write_all(&self.version.major.to_le_bytes())?;
+        w.write_all(&self.version.minor.to_le_bytes())?;
+        w.write_all(&reserved.to_le_bytes())?;
         w.write_all(&self.checksum)?;
         Ok(())
     }
@@ -65,13 +167,22 @@ impl Serializable for Header {
     where
         Self: Sized,
     {
-        let version = Self::read_bytes(r)?;
+        let version = Version {
+            major: Self::read_u32_le(r)?,
+            minor: Self::read_u32_le(r)?,
+        };
         let reserved = Self::read_u64_le(r)?;
         let checksum = Self::read_bytes(r)?;
 
+        let compression = match reserved & ZSTD_FLAG {
+            0 => Compression::None,
+            _ => Compression::Zstd,
+        };
+
         Ok(Header {
             version,
-            reserved,
+            reserved: reserved & !ZSTD_FLAG,
+            compression,
             checksum,
         })
     }
@@ -88,3 +199,97 @@ fn calc_checksum(buf: &[u8]) -> [u8; 4] {
     v.clone_from_slice(&res[0..4]);
     v
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+    use node_data::ledger::Block;
+    use node_data::message::payload::{Capabilities, Handshake};
+    use node_data::message::Payload;
+
+    fn encoded_handshake() -> Vec<u8> {
+        let msg = Message::new_handshake(Handshake {
+            capabilities: Capabilities::FAST_SYNC,
+            reply: true,
+        });
+        Pdu::encode(&msg, 1, None).expect("message to be encoded")
+    }
+
+    #[test]
+    fn test_decode() {
+        let pdu = Pdu::decode(&mut &encoded_handshake()[..])
+            .expect("frame to be decoded");
+        assert_eq!(pdu.header.version, PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_decode_invalid_checksum() {
+        let mut blob = encoded_handshake();
+        *blob.last_mut().unwrap() ^= 1;
+
+        let res = Pdu::decode(&mut &blob[..]);
+        assert!(matches!(res, Err(DecodeError::InvalidChecksum)));
+    }
+
+    #[test]
+    fn test_decode_incompatible_version() {
+        let mut blob = encoded_handshake();
+        blob[0] = 2;
+
+        let res = Pdu::decode(&mut &blob[..]);
+        assert!(matches!(
+            res,
+            Err(DecodeError::IncompatibleVersion(Version { major: 2, .. }))
+        ));
+
+        // A newer minor version is decoded
+        let mut blob = encoded_handshake();
+        blob[4] = 9;
+        assert!(Pdu::decode(&mut &blob[..]).is_ok());
+    }
+
+    #[test]
+    fn test_decode_previous_minor_version() {
+        let msg = Message::new_handshake(Handshake {
+            capabilities: Capabilities::FAST_SYNC,
+            reply: false,
+        });
+        let mut payload = vec![];
+        msg.write(&mut payload).expect("message to be written");
+
+        // Frame of a node on protocol version 0.1
+        let mut blob = vec![0, 0, 0, 0, 1, 0, 0, 0];
+        blob.extend_from_slice(&42u64.to_le_bytes());
+        blob.extend_from_slice(&calc_checksum(&payload));
+        blob.extend_from_slice(&payload);
+
+        let pdu = Pdu::decode(&mut &blob[..]).expect("frame to be decoded");
+        assert_eq!(pdu.header.version, Version { major: 0, minor: 1 });
+        assert_eq!(pdu.header.compression, Compression::None);
+        assert_eq!(pdu.header.reserved, 42);
+    }
+
+    #[test]
+    fn test_compression() {
+        let blk: Block = Faker.fake();
+        let msg = Message::new_block(blk.clone());
+
+        let raw = Pdu::encode(&msg, 1, None).expect("message to be encoded");
+        let compressed =
+            Pdu::encode(&msg, 1, Some(0)).expect("message to be encoded");
+        assert!(compressed.len() < raw.len());
+
+        // Messages below the threshold are not compressed
+        let uncompressed = Pdu::encode(&msg, 1, Some(raw.len()))
+            .expect("message to be encoded");
+        assert_eq!(uncompressed, raw);
+
+        let pdu = Pdu::decode(&mut &compressed[..]).expect("frame decoded");
+        assert_eq!(pdu.header.compression, Compression::Zstd);
+        match pdu.payload.payload {
+            Payload::Block(b) => assert_eq!(b.header().hash, blk.header().hash),
+            _ => panic!("block expected"),
+        }
+    }
+}
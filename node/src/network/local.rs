@@ -0,0 +1,556 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{BTreeMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use node_data::message::{AsyncQueue, Metadata, Topics};
+use tokio::sync::RwLock;
+use tokio::time::{self, Instant};
+use tracing::{error, info, trace};
+
+use super::{frame, FilterList, Misbehaviour, Reputation, RoutesList};
+use crate::{BoxedFilter, Message};
+
+/// What happens to a message routed through a [`LocalHub`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Deliver,
+    Drop,
+    /// Delivers the message once the given time has elapsed
+    Delay(Duration),
+}
+
+/// A hook inspecting, and possibly tampering with, every message routed
+/// from a node to another.
+pub type Interceptor =
+    Arc<dyn Fn(SocketAddr, SocketAddr, &mut Message) -> Verdict + Send + Sync>;
+
+/// The inbound side of a node registered to a [`LocalHub`].
+struct Endpoint<const N: usize> {
+    routes: Arc<RwLock<RoutesList<N>>>,
+    filters: Arc<RwLock<FilterList<N>>>,
+    reputation: Reputation,
+}
+
+impl<const N: usize> Clone for Endpoint<N> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+            filters: self.filters.clone(),
+            reputation: self.reputation.clone(),
+        }
+    }
+}
+
+struct HubState<const N: usize> {
+    endpoints: BTreeMap<SocketAddr, Endpoint<N>>,
+    isolated: HashSet<SocketAddr>,
+    interceptors: Vec<Interceptor>,
+}
+
+/// Routes messages over channels between the nodes of a single process.
+///
+/// Every node joining the hub gets a [`LocalNetwork`], and is directly
+/// reachable by all the others unless isolated. Messages are encoded and
+/// decoded as they would be on the wire, and pass through the interceptors
+/// of the hub before being delivered.
+pub struct LocalHub<const N: usize>(Arc<Mutex<HubState<N>>>);
+
+impl<const N: usize> Clone for LocalHub<N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<const N: usize> Default for LocalHub<N> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(HubState {
+            endpoints: BTreeMap::new(),
+            isolated: HashSet::new(),
+            interceptors: vec![],
+        })))
+    }
+}
+
+impl<const N: usize> LocalHub<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a node reachable at the given address.
+    pub fn join(&self, addr: SocketAddr) -> anyhow::Result<LocalNetwork<N>> {
+        const INIT: Option<AsyncQueue<Message>> = None;
+        const INIT_FN: Option<BoxedFilter> = None;
+
+        let endpoint = Endpoint {
+            routes: Arc::new(RwLock::new([INIT; N])),
+            filters: Arc::new(RwLock::new([INIT_FN; N])),
+            reputation: Reputation::default(),
+        };
+
+        let mut state = self.state()?;
+        if state.endpoints.contains_key(&addr) {
+            anyhow::bail!("address {addr} already joined");
+        }
+        state.endpoints.insert(addr, endpoint.clone());
+
+        Ok(LocalNetwork {
+            addr,
+            hub: self.clone(),
+            routes: endpoint.routes,
+            filters: endpoint.filters,
+            reputation: endpoint.reputation,
+        })
+    }
+
+    /// Adds a hook called on every message routed through the hub.
+    ///
+    /// A message is dropped if any interceptor drops it, and is delayed by
+    /// the sum of the delays requested.
+    pub fn intercept<F>(&self, interceptor: F)
+    where
+        F: Fn(SocketAddr, SocketAddr, &mut Message) -> Verdict
+            + Send
+            + Sync
+            + 'static,
+    {
+        if let Ok(mut state) = self.state() {
+            state.interceptors.push(Arc::new(interceptor));
+        }
+    }
+
+    /// Cuts a node off the hub, dropping all the messages it sends or is
+    /// sent.
+    pub fn isolate(&self, addr: SocketAddr) {
+        if let Ok(mut state) = self.state() {
+            state.isolated.insert(addr);
+        }
+    }
+
+    /// Reconnects an isolated node.
+    pub fn rejoin(&self, addr: SocketAddr) {
+        if let Ok(mut state) = self.state() {
+            state.isolated.remove(&addr);
+        }
+    }
+
+    /// Removes all the injected faults and interceptors.
+    pub fn heal(&self) {
+        if let Ok(mut state) = self.state() {
+            state.isolated.clear();
+            state.interceptors.clear();
+        }
+    }
+
+    /// Returns the addresses of the nodes joined, in order.
+    pub fn nodes(&self) -> Vec<SocketAddr> {
+        self.state()
+            .map(|state| state.endpoints.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn state(&self) -> anyhow::Result<std::sync::MutexGuard<HubState<N>>> {
+        self.0
+            .lock()
+            .map_err(|_| anyhow::anyhow!("local hub lock poisoned"))
+    }
+
+    fn leave(&self, addr: SocketAddr) {
+        if let Ok(mut state) = self.state() {
+            state.endpoints.remove(&addr);
+            state.isolated.remove(&addr);
+        }
+    }
+
+    /// Returns the nodes reachable from `from`, starting from the one
+    /// following it so that requests are spread across the hub.
+    fn reachable(&self, from: SocketAddr) -> Vec<SocketAddr> {
+        let Ok(state) = self.state() else {
+            return vec![];
+        };
+
+        if state.isolated.contains(&from) {
+            return vec![];
+        }
+
+        let reachable = |addr: &&SocketAddr| {
+            **addr != from && !state.isolated.contains(addr)
+        };
+        let after = state.endpoints.range(from..).map(|(a, _)| a);
+        let before = state.endpoints.range(..from).map(|(a, _)| a);
+
+        after.chain(before).filter(reachable).copied().collect()
+    }
+
+    /// Routes an encoded message to the node at `to`.
+    fn deliver(&self, from: SocketAddr, to: SocketAddr, blob: &[u8]) {
+        let (endpoint, interceptors) = {
+            let Ok(state) = self.state() else {
+                return;
+            };
+
+            if state.isolated.contains(&from) || state.isolated.contains(&to) {
+                trace!("drop message from {from} to isolated {to}");
+                return;
+            }
+
+            let Some(endpoint) = state.endpoints.get(&to) else {
+                trace!("drop message from {from} to unknown {to}");
+                return;
+            };
+
+            (endpoint.clone(), state.interceptors.clone())
+        };
+
+        if !endpoint.reputation.on_message(from) {
+            trace!("discard message from peer {from}");
+            return;
+        }
+
+        let mut msg = match frame::Pdu::decode(&mut &blob[..]) {
+            Ok(pdu) => pdu.payload,
+            Err(err) => {
+                error!("could not decode message from {from}: {err}");
+                return;
+            }
+        };
+
+        // Messages reach all the nodes directly, so that none of them has to
+        // propagate it further
+        msg.metadata = Some(Metadata {
+            height: 0,
+            src_addr: from,
+        });
+
+        let mut delay = Duration::ZERO;
+        for interceptor in interceptors {
+            match interceptor(from, to, &mut msg) {
+                Verdict::Deliver => {}
+                Verdict::Drop => {
+                    trace!("intercepted message from {from} to {to}");
+                    return;
+                }
+                Verdict::Delay(d) => delay += d,
+            }
+        }
+
+        let topic = msg.topic() as usize;
+        let filtered = match endpoint.filters.try_write() {
+            Ok(mut filters) => match filters.get_mut(topic) {
+                Some(Some(f)) => f.filter(&msg),
+                _ => Ok(()),
+            },
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = filtered {
+            info!("discard message due to {e}");
+            return;
+        }
+
+        tokio::spawn(async move {
+            if !delay.is_zero() {
+                time::sleep(delay).await;
+            }
+
+            if let Some(Some(queue)) = endpoint.routes.read().await.get(topic) {
+                if let Err(e) = queue.send(msg).await {
+                    error!("Unable to reroute message with topic {topic}: {e}");
+                };
+            };
+        });
+    }
+}
+
+/// A [`Network`] connected to the other nodes of a [`LocalHub`].
+///
+/// [`Network`]: crate::Network
+pub struct LocalNetwork<const N: usize> {
+    addr: SocketAddr,
+    hub: LocalHub<N>,
+    routes: Arc<RwLock<RoutesList<N>>>,
+    filters: Arc<RwLock<FilterList<N>>>,
+    reputation: Reputation,
+}
+
+impl<const N: usize> LocalNetwork<N> {
+    /// Returns the address the node is reachable at.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn hub(&self) -> &LocalHub<N> {
+        &self.hub
+    }
+
+    /// Removes a route, if exists, for a given topic.
+    async fn remove_route(&mut self, topic: u8) {
+        let mut guard = self.routes.write().await;
+
+        if let Some(Some(_)) = guard.get_mut(topic as usize) {
+            guard[topic as usize] = None;
+        }
+    }
+
+    fn send(&self, msg: &Message, peers: &[SocketAddr]) -> anyhow::Result<()> {
+        let encoded = frame::Pdu::encode(msg, 0, None)
+            .map_err(|err| anyhow::anyhow!("failed to encode: {err}"))?;
+
+        for recv_addr in peers {
+            trace!("sending msg ({:?}) to peer {recv_addr}", msg.topic());
+            self.hub.deliver(self.addr, *recv_addr, &encoded);
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Drop for LocalNetwork<N> {
+    fn drop(&mut self) {
+        self.hub.leave(self.addr);
+    }
+}
+
+#[async_trait]
+impl<const N: usize> crate::Network for LocalNetwork<N> {
+    async fn broadcast(&self, msg: &Message) -> anyhow::Result<()> {
+        if let Some(Metadata { height: 0, .. }) = msg.metadata {
+            return Ok(());
+        }
+
+        self.send(msg, &self.hub.reachable(self.addr))
+    }
+
+    async fn send_to_peer(
+        &self,
+        msg: &Message,
+        recv_addr: SocketAddr,
+    ) -> anyhow::Result<()> {
+        self.send(msg, &[recv_addr])
+    }
+
+    async fn send_to_alive_peers(
+        &self,
+        msg: &Message,
+        amount: usize,
+    ) -> anyhow::Result<()> {
+        self.send(msg, &self.alive_nodes(amount).await)
+    }
+
+    async fn alive_nodes(&self, amount: usize) -> Vec<SocketAddr> {
+        let mut peers = self.hub.reachable(self.addr);
+        peers.retain(|peer| !self.reputation.is_banned(peer));
+        peers.truncate(amount);
+        peers
+    }
+
+    fn report_peer(&self, peer_addr: SocketAddr, misbehaviour: Misbehaviour) {
+        self.reputation.report(peer_addr, misbehaviour);
+    }
+
+    async fn send_and_wait(
+        &mut self,
+        request_msg: &Message,
+        response_msg_topic: Topics,
+        timeout_millis: u64,
+        recv_peers_count: usize,
+    ) -> anyhow::Result<Message> {
+        self.remove_route(response_msg_topic.into()).await;
+
+        let res = {
+            let queue = AsyncQueue::unbounded();
+            // register a temporary route that will be unregister on drop
+            self.add_route(response_msg_topic.into(), queue.clone())
+                .await?;
+
+            self.send_to_alive_peers(request_msg, recv_peers_count)
+                .await?;
+
+            let deadline =
+                Instant::now() + Duration::from_millis(timeout_millis);
+
+            // Wait for a response message or a timeout
+            match time::timeout_at(deadline, queue.recv()).await {
+                // Got a response message
+                Ok(Ok(msg)) => Ok(msg),
+                // Failed to receive a response message
+                Ok(Err(_)) => anyhow::bail!("failed to receive"),
+                // Timeout expired
+                Err(_) => anyhow::bail!("timeout err"),
+            }
+        };
+
+        self.remove_route(response_msg_topic.into()).await;
+        res
+    }
+
+    async fn add_route(
+        &mut self,
+        topic: u8,
+        queue: AsyncQueue<Message>,
+    ) -> anyhow::Result<()> {
+        let mut guard = self.routes.write().await;
+
+        let route = guard
+            .get_mut(topic as usize)
+            .ok_or_else(|| anyhow::anyhow!("topic out of range: {topic}"))?;
+
+        debug_assert!(route.is_none(), "topic already registered");
+
+        *route = Some(queue);
+
+        Ok(())
+    }
+
+    async fn add_filter(
+        &mut self,
+        msg_type: u8,
+        filter_fn: BoxedFilter,
+    ) -> anyhow::Result<()> {
+        let mut guard = self.filters.write().await;
+
+        let filter = guard
+            .get_mut(msg_type as usize)
+            .ok_or_else(|| anyhow::anyhow!("topic out of range: {msg_type}"))?;
+
+        *filter = Some(filter_fn);
+
+        Ok(())
+    }
+
+    fn get_info(&self) -> anyhow::Result<String> {
+        Ok(self.addr.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Network;
+    use node_data::message::payload::{GetData, GetMempool, Inv};
+    use node_data::message::Payload;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    async fn listen(
+        net: &mut LocalNetwork<255>,
+        topic: Topics,
+    ) -> AsyncQueue<Message> {
+        let queue = AsyncQueue::unbounded();
+        net.add_route(topic.into(), queue.clone()).await.unwrap();
+        queue
+    }
+
+    async fn recv(queue: &AsyncQueue<Message>) -> Option<Message> {
+        time::timeout(Duration::from_millis(100), queue.recv())
+            .await
+            .ok()
+            .and_then(Result::ok)
+    }
+
+    #[tokio::test]
+    async fn test_broadcast() {
+        let hub = LocalHub::<255>::new();
+        let a = hub.join(addr(1)).unwrap();
+        let mut b = hub.join(addr(2)).unwrap();
+        let mut c = hub.join(addr(3)).unwrap();
+        assert!(hub.join(addr(3)).is_err());
+
+        let queue_b = listen(&mut b, Topics::GetMempool).await;
+        let queue_c = listen(&mut c, Topics::GetMempool).await;
+
+        let msg = Message::new_get_mempool(GetMempool::default());
+        a.broadcast(&msg).await.unwrap();
+
+        for queue in [&queue_b, &queue_c] {
+            let recv = recv(queue).await.expect("message to be delivered");
+            let metadata = recv.metadata.as_ref().expect("metadata to be set");
+            assert_eq!(metadata.src_addr, addr(1));
+
+            // Relaying a received message is a no-op
+            b.broadcast(&recv).await.unwrap();
+        }
+        assert!(recv(&queue_c).await.is_none());
+
+        // Peers leave the hub once dropped
+        drop(c);
+        assert_eq!(a.alive_nodes(10).await, vec![addr(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_send_and_wait() {
+        let hub = LocalHub::<255>::new();
+        let mut a = hub.join(addr(1)).unwrap();
+        let mut b = hub.join(addr(2)).unwrap();
+
+        let requests = listen(&mut b, Topics::GetData).await;
+        tokio::spawn(async move {
+            let request = requests.recv().await.unwrap();
+            let Payload::GetData(get_data) = request.payload else {
+                panic!("unexpected payload");
+            };
+            let src = request.metadata.unwrap().src_addr;
+            let resp = Message::new_inv(get_data.inner);
+            b.send_to_peer(&resp, src).await.unwrap();
+        });
+
+        let mut inv = Inv::default();
+        inv.add_block_from_height(7);
+        let request = Message::new_get_data(GetData { inner: inv });
+
+        let resp = a
+            .send_and_wait(&request, Topics::GetInv, 1000, 1)
+            .await
+            .expect("response to be received");
+        let Payload::GetInv(inv) = resp.payload else {
+            panic!("unexpected payload");
+        };
+        assert_eq!(inv.inv_list.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_faults() {
+        let hub = LocalHub::<255>::new();
+        let a = hub.join(addr(1)).unwrap();
+        let mut b = hub.join(addr(2)).unwrap();
+        let queue = listen(&mut b, Topics::GetMempool).await;
+        let msg = Message::new_get_mempool(GetMempool::default());
+
+        hub.isolate(addr(2));
+        assert!(a.alive_nodes(10).await.is_empty());
+        a.send_to_peer(&msg, addr(2)).await.unwrap();
+        assert!(recv(&queue).await.is_none());
+
+        hub.rejoin(addr(2));
+        hub.intercept(|_, to, _| {
+            if to == addr(2) {
+                Verdict::Drop
+            } else {
+                Verdict::Deliver
+            }
+        });
+        a.send_to_peer(&msg, addr(2)).await.unwrap();
+        assert!(recv(&queue).await.is_none());
+
+        hub.heal();
+        hub.intercept(|_, _, _| Verdict::Delay(Duration::from_millis(50)));
+        a.send_to_peer(&msg, addr(2)).await.unwrap();
+        assert!(recv(&queue).await.is_some());
+
+        // Banned peers are discarded
+        for _ in 0..5 {
+            b.report_peer(addr(1), Misbehaviour::InvalidBlock);
+        }
+        a.send_to_peer(&msg, addr(2)).await.unwrap();
+        assert!(recv(&queue).await.is_none());
+        assert!(b.alive_nodes(10).await.is_empty());
+    }
+}
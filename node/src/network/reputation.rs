@@ -0,0 +1,297 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use tracing::warn;
+
+/// Score at which a peer gets banned.
+const BAN_SCORE: i32 = -100;
+
+/// Time all messages of a banned peer are discarded for.
+const BAN_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Time after which a point of a negative score is recovered.
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Number of messages a peer may send within a [`RATE_WINDOW`].
+const MAX_MESSAGES_PER_WINDOW: u32 = 1000;
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Number of records above which the ones of well-behaved peers are pruned.
+const MAX_RECORDS: usize = 10_000;
+
+/// Behaviours a peer is penalized for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehaviour {
+    /// Sent a block failing verification
+    InvalidBlock,
+    /// Sent a consensus message failing verification
+    InvalidMessage,
+    /// Sent a frame that could not be decoded
+    InvalidFrame,
+    /// Sent more messages than allowed in a time window
+    Spam,
+}
+
+impl Misbehaviour {
+    fn penalty(self) -> i32 {
+        match self {
+            Misbehaviour::InvalidBlock => 20,
+            Misbehaviour::InvalidMessage => 10,
+            Misbehaviour::InvalidFrame => 10,
+            Misbehaviour::Spam => 25,
+        }
+    }
+}
+
+/// The reputation of a peer, as reported to operators.
+#[derive(Debug, Clone)]
+pub struct PeerScore {
+    pub address: SocketAddr,
+    pub score: i32,
+    /// Number of misbehaviours reported since the peer was first seen
+    pub offences: u32,
+    pub last_offence: Option<Misbehaviour>,
+    pub banned_until: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct Record {
+    score: i32,
+    recovered_at: Instant,
+    offences: u32,
+    last_offence: Option<Misbehaviour>,
+    banned_until: Option<Instant>,
+
+    window_start: Instant,
+    window_messages: u32,
+}
+
+impl Record {
+    fn new(now: Instant) -> Self {
+        Self {
+            score: 0,
+            recovered_at: now,
+            offences: 0,
+            last_offence: None,
+            banned_until: None,
+            window_start: now,
+            window_messages: 0,
+        }
+    }
+
+    /// Recovers the points earned since the last recovery.
+    fn recover(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.recovered_at);
+        let points = (elapsed.as_secs() / RECOVERY_INTERVAL.as_secs()) as u32;
+
+        self.recovered_at += RECOVERY_INTERVAL * points;
+        self.score = self.score.saturating_add_unsigned(points).min(0);
+
+        if self.banned_until.is_some_and(|until| until <= now) {
+            self.banned_until = None;
+        }
+    }
+
+    fn is_banned(&self, now: Instant) -> bool {
+        self.banned_until.is_some_and(|until| until > now)
+    }
+
+    fn is_neutral(&self, now: Instant) -> bool {
+        self.score == 0
+            && !self.is_banned(now)
+            && now.saturating_duration_since(self.window_start) >= RATE_WINDOW
+    }
+}
+
+/// Scores peers by their misbehaviours, temporarily banning the ones reaching
+/// [`BAN_SCORE`].
+///
+/// Scores recover over time, so that only peers misbehaving repeatedly are
+/// banned.
+#[derive(Debug, Clone, Default)]
+pub struct Reputation(Arc<Mutex<HashMap<SocketAddr, Record>>>);
+
+impl Reputation {
+    /// Penalizes a peer for a misbehaviour.
+    pub fn report(&self, peer: SocketAddr, misbehaviour: Misbehaviour) {
+        self.report_at(peer, misbehaviour, Instant::now())
+    }
+
+    /// Returns true if messages of the peer are to be discarded.
+    pub fn is_banned(&self, peer: &SocketAddr) -> bool {
+        let now = Instant::now();
+        self.0
+            .lock()
+            .map(|records| records.get(peer).is_some_and(|r| r.is_banned(now)))
+            .unwrap_or_default()
+    }
+
+    /// Accounts a message received from a peer, returning false if it has to
+    /// be discarded because the peer is banned or exceeds its rate limit.
+    pub(crate) fn on_message(&self, peer: SocketAddr) -> bool {
+        self.on_message_at(peer, Instant::now())
+    }
+
+    /// Lists the scores of the peers having misbehaved.
+    pub fn scores(&self) -> Vec<PeerScore> {
+        let now = Instant::now();
+        let wall_now = SystemTime::now();
+
+        let Ok(mut records) = self.0.lock() else {
+            return vec![];
+        };
+
+        records
+            .iter_mut()
+            .filter(|(_, r)| r.offences > 0)
+            .map(|(address, r)| {
+                r.recover(now);
+                PeerScore {
+                    address: *address,
+                    score: r.score,
+                    offences: r.offences,
+                    last_offence: r.last_offence,
+                    banned_until: r
+                        .banned_until
+                        .map(|until| wall_now + (until - now)),
+                }
+            })
+            .collect()
+    }
+
+    fn report_at(
+        &self,
+        peer: SocketAddr,
+        misbehaviour: Misbehaviour,
+        now: Instant,
+    ) {
+        let Ok(mut records) = self.0.lock() else {
+            return;
+        };
+
+        Self::prune(&mut records, now);
+
+        let record = records.entry(peer).or_insert_with(|| Record::new(now));
+        record.recover(now);
+        record.score -= misbehaviour.penalty();
+        record.offences += 1;
+        record.last_offence = Some(misbehaviour);
+
+        if record.score <= BAN_SCORE {
+            warn!(event = "peer banned", ?peer, ?misbehaviour);
+            record.banned_until = Some(now + BAN_DURATION);
+            record.score = 0;
+        }
+    }
+
+    fn on_message_at(&self, peer: SocketAddr, now: Instant) -> bool {
+        let spam = {
+            let Ok(mut records) = self.0.lock() else {
+                return true;
+            };
+
+            Self::prune(&mut records, now);
+
+            let record =
+                records.entry(peer).or_insert_with(|| Record::new(now));
+            if record.is_banned(now) {
+                return false;
+            }
+
+            if now.saturating_duration_since(record.window_start) >= RATE_WINDOW
+            {
+                record.window_start = now;
+                record.window_messages = 0;
+            }
+
+            record.window_messages += 1;
+            record.window_messages > MAX_MESSAGES_PER_WINDOW
+        };
+
+        if spam {
+            self.report_at(peer, Misbehaviour::Spam, now);
+        }
+
+        !spam
+    }
+
+    /// Drops the records of well-behaved peers once there are too many.
+    fn prune(records: &mut HashMap<SocketAddr, Record>, now: Instant) {
+        if records.len() >= MAX_RECORDS {
+            records.retain(|_, r| {
+                r.recover(now);
+                !r.is_neutral(now)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn test_ban_after_repeated_misbehaviour() {
+        let now = Instant::now();
+        let reputation = Reputation::default();
+
+        for _ in 0..4 {
+            reputation.report_at(peer(1), Misbehaviour::InvalidBlock, now);
+        }
+        assert!(reputation.on_message_at(peer(1), now));
+
+        reputation.report_at(peer(1), Misbehaviour::InvalidBlock, now);
+        assert!(!reputation.on_message_at(peer(1), now));
+        assert!(reputation.on_message_at(peer(2), now));
+
+        // The ban is lifted once expired
+        assert!(reputation.on_message_at(peer(1), now + BAN_DURATION));
+    }
+
+    #[test]
+    fn test_score_recovery() {
+        let now = Instant::now();
+        let reputation = Reputation::default();
+
+        for _ in 0..4 {
+            reputation.report_at(peer(1), Misbehaviour::InvalidBlock, now);
+        }
+
+        // Recovered points keep the peer from being banned
+        let later = now + RECOVERY_INTERVAL * 20;
+        reputation.report_at(peer(1), Misbehaviour::InvalidBlock, later);
+        assert!(reputation.on_message_at(peer(1), later));
+
+        let scores = reputation.scores();
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].offences, 5);
+        assert_eq!(scores[0].last_offence, Some(Misbehaviour::InvalidBlock));
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let now = Instant::now();
+        let reputation = Reputation::default();
+
+        for _ in 0..MAX_MESSAGES_PER_WINDOW {
+            assert!(reputation.on_message_at(peer(1), now));
+        }
+        assert!(!reputation.on_message_at(peer(1), now));
+        assert!(reputation.on_message_at(peer(1), now + RATE_WINDOW));
+
+        let scores = reputation.scores();
+        assert_eq!(scores[0].last_offence, Some(Misbehaviour::Spam));
+    }
+}
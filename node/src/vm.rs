@@ -9,7 +9,7 @@ use dusk_consensus::{
     operations::{CallParams, VerificationOutput},
     user::{provisioners::Provisioners, stake::Stake},
 };
-use node_data::ledger::{Block, SpentTransaction, Transaction};
+use node_data::ledger::{Block, SpentTransaction, StakeEvent, Transaction};
 
 #[derive(Default)]
 pub struct Config {}
@@ -33,12 +33,20 @@ pub trait VMExecution: Send + Sync + 'static {
     fn accept(
         &self,
         blk: &Block,
-    ) -> anyhow::Result<(Vec<SpentTransaction>, VerificationOutput)>;
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        VerificationOutput,
+        Vec<StakeEvent>,
+    )>;
 
     fn finalize(
         &self,
         blk: &Block,
-    ) -> anyhow::Result<(Vec<SpentTransaction>, VerificationOutput)>;
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        VerificationOutput,
+        Vec<StakeEvent>,
+    )>;
 
     fn preverify(&self, tx: &Transaction) -> anyhow::Result<()>;
 
@@ -56,4 +64,23 @@ pub trait VMExecution: Send + Sync + 'static {
 
     fn revert(&self, state_hash: [u8; 32]) -> anyhow::Result<[u8; 32]>;
     fn revert_to_finalized(&self) -> anyhow::Result<[u8; 32]>;
+
+    /// Keeps the given state roots from being deleted when finalizing,
+    /// replacing any previously retained ones
+    fn retain_commits(&self, state_roots: Vec<[u8; 32]>) -> anyhow::Result<()>;
+
+    /// Archives the commit with the given state root, to be served to peers
+    /// fast syncing
+    fn state_snapshot(&self, state_root: [u8; 32]) -> anyhow::Result<Vec<u8>>;
+
+    /// Makes the commit archived in a snapshot available, checking its state
+    /// root is the given one and its memory is part of it
+    fn import_snapshot(
+        &self,
+        state_root: [u8; 32],
+        snapshot: &[u8],
+    ) -> anyhow::Result<()>;
+
+    /// Sets an imported commit as the finalized state
+    fn install_snapshot(&self, state_root: [u8; 32]) -> anyhow::Result<()>;
 }
@@ -6,12 +6,25 @@
 
 pub use piecrust_uplink::*;
 
+/// Calls a host function, panicking if charging its cost exceeds the gas
+/// limit, so that the calling contract runs out of gas.
+#[cfg(feature = "abi")]
+fn charged_host_query<A, Ret>(name: &str, arg: A) -> Ret
+where
+    A: for<'a> rkyv::Serialize<StandardBufSerializer<'a>>,
+    Ret: rkyv::Archive,
+    Ret::Archived: rkyv::Deserialize<Ret, rkyv::Infallible>,
+{
+    let ret: Option<Ret> = host_query(name, arg);
+    ret.expect("The host function should be within the gas limit")
+}
+
 /// Compute the blake2b hash of the given bytes, returning the resulting scalar.
 /// The output of the hasher is truncated (last nibble) to fit onto a scalar.
 #[cfg(feature = "abi")]
 pub fn hash(bytes: alloc::vec::Vec<u8>) -> dusk_bls12_381::BlsScalar {
     use crate::Query;
-    host_query(Query::HASH, bytes)
+    charged_host_query(Query::HASH, bytes)
 }
 
 /// Compute the poseidon hash of the given scalars
@@ -20,7 +33,7 @@ pub fn poseidon_hash(
     scalars: alloc::vec::Vec<dusk_bls12_381::BlsScalar>,
 ) -> dusk_bls12_381::BlsScalar {
     use crate::Query;
-    host_query(Query::POSEIDON_HASH, scalars)
+    charged_host_query(Query::POSEIDON_HASH, scalars)
 }
 
 /// Verify a proof is valid for a given circuit type and public inputs
@@ -31,7 +44,7 @@ pub fn verify_proof(
     public_inputs: alloc::vec::Vec<crate::PublicInput>,
 ) -> bool {
     use crate::Query;
-    host_query(Query::VERIFY_PROOF, (verifier_data, proof, public_inputs))
+    charged_host_query(Query::VERIFY_PROOF, (verifier_data, proof, public_inputs))
 }
 
 /// Verify a schnorr signature is valid for the given public key and message
@@ -42,7 +55,7 @@ pub fn verify_schnorr(
     sig: dusk_schnorr::Signature,
 ) -> bool {
     use crate::Query;
-    host_query(Query::VERIFY_SCHNORR, (msg, pk, sig))
+    charged_host_query(Query::VERIFY_SCHNORR, (msg, pk, sig))
 }
 
 /// Verify a BLS signature is valid for the given public key and message
@@ -53,7 +66,97 @@ pub fn verify_bls(
     sig: dusk_bls12_381_sign::Signature,
 ) -> bool {
     use crate::Query;
-    host_query(Query::VERIFY_BLS, (msg, pk, sig))
+    charged_host_query(Query::VERIFY_BLS, (msg, pk, sig))
+}
+
+/// Verify an aggregated BLS signature is valid for the given public keys, all
+/// of them having signed the same message
+#[cfg(feature = "abi")]
+pub fn verify_bls_multisig(
+    msg: alloc::vec::Vec<u8>,
+    pks: alloc::vec::Vec<dusk_bls12_381_sign::PublicKey>,
+    sig: dusk_bls12_381_sign::Signature,
+) -> bool {
+    use crate::Query;
+    charged_host_query(Query::VERIFY_BLS_MULTISIG, (msg, pks, sig))
+}
+
+/// Verify an aggregated BLS signature is valid for the given aggregated public
+/// key and message
+#[cfg(feature = "abi")]
+pub fn verify_bls_apk(
+    msg: alloc::vec::Vec<u8>,
+    apk: dusk_bls12_381_sign::APK,
+    sig: dusk_bls12_381_sign::Signature,
+) -> bool {
+    use crate::Query;
+    charged_host_query(Query::VERIFY_BLS_APK, (msg, apk, sig))
+}
+
+/// Verify an aggregated BLS signature is valid for the given public keys, each
+/// of them having signed a distinct message
+#[cfg(feature = "abi")]
+pub fn verify_bls_aggregate(
+    msgs: alloc::vec::Vec<(
+        alloc::vec::Vec<u8>,
+        dusk_bls12_381_sign::PublicKey,
+    )>,
+    sig: dusk_bls12_381_sign::Signature,
+) -> bool {
+    use crate::Query;
+    charged_host_query(Query::VERIFY_BLS_AGGREGATE, (msgs, sig))
+}
+
+/// Compute the SHA-256 hash of the given bytes
+#[cfg(feature = "abi")]
+pub fn sha256(bytes: alloc::vec::Vec<u8>) -> [u8; 32] {
+    use crate::Query;
+    charged_host_query(Query::SHA256, bytes)
+}
+
+/// Compute the Keccak-256 hash of the given bytes, as used by Ethereum
+#[cfg(feature = "abi")]
+pub fn keccak256(bytes: alloc::vec::Vec<u8>) -> [u8; 32] {
+    use crate::Query;
+    charged_host_query(Query::KECCAK256, bytes)
+}
+
+/// Verify an ECDSA signature over secp256k1 is valid for the given public key
+/// and message hash. The public key is SEC1 encoded, either compressed or
+/// uncompressed, and the signature is the concatenation of `r` and `s`.
+#[cfg(feature = "abi")]
+pub fn verify_ecdsa_secp256k1(
+    msg_hash: [u8; 32],
+    pk: alloc::vec::Vec<u8>,
+    sig: [u8; 64],
+) -> bool {
+    use crate::Query;
+    charged_host_query(Query::VERIFY_ECDSA_SECP256K1, (msg_hash, pk, sig))
+}
+
+/// Recover the public key that produced an ECDSA signature over secp256k1 for
+/// the given message hash, returning it SEC1 encoded and uncompressed.
+///
+/// Returns `None` if the signature or the recovery id are invalid.
+#[cfg(feature = "abi")]
+pub fn recover_ecdsa_secp256k1(
+    msg_hash: [u8; 32],
+    sig: [u8; 64],
+    recovery_id: u8,
+) -> Option<[u8; 65]> {
+    use crate::Query;
+    charged_host_query(Query::RECOVER_ECDSA_SECP256K1, (msg_hash, sig, recovery_id))
+}
+
+/// Verify an ed25519 signature is valid for the given public key and message
+#[cfg(feature = "abi")]
+pub fn verify_ed25519(
+    msg: alloc::vec::Vec<u8>,
+    pk: [u8; 32],
+    sig: [u8; 64],
+) -> bool {
+    use crate::Query;
+    charged_host_query(Query::VERIFY_ED25519, (msg, pk, sig))
 }
 
 /// Get the current block height.
@@ -63,6 +166,14 @@ pub fn block_height() -> u64 {
     meta_data(Metadata::BLOCK_HEIGHT).unwrap()
 }
 
+/// Get the gas charged for the host functions called so far during the
+/// current transaction. The VM doesn't include this gas in [`spent`].
+#[cfg(feature = "abi")]
+pub fn host_gas_spent() -> u64 {
+    use crate::Query;
+    charged_host_query(Query::HOST_GAS_SPENT, ())
+}
+
 /// Query a contract for the types of payment it accepts.
 #[cfg(feature = "abi")]
 pub fn payment_info(
@@ -0,0 +1,209 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Gas charged for the execution of host functions.
+//!
+//! Host functions run natively, outside of the metered WASM runtime. To
+//! account for the work they perform, each of them is charged according to a
+//! [`GasSchedule`]: a base cost for each call, plus a cost for each unit of its
+//! input. The charges are accumulated by the meter of the current thread while
+//! running within [`metered`], and should be added by the caller to the gas
+//! spent by the call, the call running out of gas if the total exceeds its
+//! limit. If the charges alone exceed the gas limit given to [`metered`], the
+//! contract calling the host function panics, and the call fails with
+//! [`Error::OutOfGas`]. Contracts can query the charges so far with
+//! `host_gas_spent`, to account for them when limiting the gas of their own
+//! calls.
+//!
+//! The default schedule charges roughly one gas per nanosecond of execution,
+//! as measured by benchmarking the host functions in release mode. The
+//! `gas_schedule_calibration` test checks it against the timings of the
+//! machine it runs on:
+//!
+//! | Host function             | Unit         | Base       | Per unit  |
+//! |---------------------------|--------------|------------|-----------|
+//! | `hash`                    | byte         | 300        | 2         |
+//! | `poseidon_hash`           | scalar       | 45000      | 16000     |
+//! | `verify_proof`            | public input | 18000000   | 50000     |
+//! | `verify_schnorr`          | -            | 350000     | 0         |
+//! | `verify_bls`              | byte         | 4500000    | 2         |
+//! | `verify_bls_multisig`     | public key   | 3200000    | 1250000   |
+//! | `verify_bls_apk`          | byte         | 4500000    | 2         |
+//! | `verify_bls_aggregate`    | message      | 3500000    | 2400000   |
+//! | `sha256`                  | byte         | 100        | 1         |
+//! | `keccak256`               | byte         | 450        | 4         |
+//! | `verify_ecdsa_secp256k1`  | -            | 100000     | 0         |
+//! | `recover_ecdsa_secp256k1` | -            | 200000     | 0         |
+//! | `verify_ed25519`          | byte         | 50000      | 2         |
+//!
+//! The schedule is part of consensus: every node must execute contracts using
+//! the same one.
+
+use std::cell::Cell;
+
+use crate::{Error, Query};
+
+/// Gas charged for a call to a host function: a base cost, plus a cost for
+/// each unit of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostCost {
+    /// Gas charged for each call.
+    pub base: u64,
+    /// Gas charged for each unit of input.
+    pub per_unit: u64,
+}
+
+impl HostCost {
+    /// Create a new cost with the given `base` and `per_unit` gas.
+    pub const fn new(base: u64, per_unit: u64) -> Self {
+        Self { base, per_unit }
+    }
+
+    /// The gas charged for a call with the given number of input units.
+    pub const fn cost(&self, units: usize) -> u64 {
+        self.per_unit
+            .saturating_mul(units as u64)
+            .saturating_add(self.base)
+    }
+}
+
+/// The cost of each of the host functions. See the [module
+/// documentation](self) for the units of their inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct GasSchedule {
+    pub hash: HostCost,
+    pub poseidon_hash: HostCost,
+    pub verify_proof: HostCost,
+    pub verify_schnorr: HostCost,
+    pub verify_bls: HostCost,
+    pub verify_bls_multisig: HostCost,
+    pub verify_bls_apk: HostCost,
+    pub verify_bls_aggregate: HostCost,
+    pub sha256: HostCost,
+    pub keccak256: HostCost,
+    pub verify_ecdsa_secp256k1: HostCost,
+    pub recover_ecdsa_secp256k1: HostCost,
+    pub verify_ed25519: HostCost,
+}
+
+impl GasSchedule {
+    /// The schedule used by [`new_vm`] and [`new_ephemeral_vm`].
+    ///
+    /// [`new_vm`]: crate::new_vm
+    /// [`new_ephemeral_vm`]: crate::new_ephemeral_vm
+    pub const DEFAULT: Self = Self {
+        hash: HostCost::new(300, 2),
+        poseidon_hash: HostCost::new(45_000, 16_000),
+        verify_proof: HostCost::new(18_000_000, 50_000),
+        verify_schnorr: HostCost::new(350_000, 0),
+        verify_bls: HostCost::new(4_500_000, 2),
+        verify_bls_multisig: HostCost::new(3_200_000, 1_250_000),
+        verify_bls_apk: HostCost::new(4_500_000, 2),
+        verify_bls_aggregate: HostCost::new(3_500_000, 2_400_000),
+        sha256: HostCost::new(100, 1),
+        keccak256: HostCost::new(450, 4),
+        verify_ecdsa_secp256k1: HostCost::new(100_000, 0),
+        recover_ecdsa_secp256k1: HostCost::new(200_000, 0),
+        verify_ed25519: HostCost::new(50_000, 2),
+    };
+
+    /// The cost of the host function with the given name.
+    pub fn get(&self, query: &str) -> Option<HostCost> {
+        let cost = match query {
+            Query::HASH => self.hash,
+            Query::POSEIDON_HASH => self.poseidon_hash,
+            Query::VERIFY_PROOF => self.verify_proof,
+            Query::VERIFY_SCHNORR => self.verify_schnorr,
+            Query::VERIFY_BLS => self.verify_bls,
+            Query::VERIFY_BLS_MULTISIG => self.verify_bls_multisig,
+            Query::VERIFY_BLS_APK => self.verify_bls_apk,
+            Query::VERIFY_BLS_AGGREGATE => self.verify_bls_aggregate,
+            Query::SHA256 => self.sha256,
+            Query::KECCAK256 => self.keccak256,
+            Query::VERIFY_ECDSA_SECP256K1 => self.verify_ecdsa_secp256k1,
+            Query::RECOVER_ECDSA_SECP256K1 => self.recover_ecdsa_secp256k1,
+            Query::VERIFY_ED25519 => self.verify_ed25519,
+            _ => return None,
+        };
+        Some(cost)
+    }
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Meter {
+    spent: u64,
+    limit: u64,
+    exhausted: bool,
+}
+
+impl Meter {
+    const UNLIMITED: Self = Self::new(u64::MAX);
+
+    const fn new(limit: u64) -> Self {
+        Self {
+            spent: 0,
+            limit,
+            exhausted: false,
+        }
+    }
+}
+
+thread_local! {
+    static METER: Cell<Meter> = Cell::new(Meter::UNLIMITED);
+}
+
+/// Runs the given closure, returning its result together with the gas charged
+/// by the host functions called during its execution.
+///
+/// If a host function is called by a contract while the charges exceed
+/// `limit`, the contract traps and a failed result is turned into
+/// [`Error::OutOfGas`]. Calls to [`metered`] may be nested, with the inner
+/// charges not counting towards the outer ones.
+pub fn metered<T, F>(limit: u64, f: F) -> (Result<T, Error>, u64)
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    let outer = METER.with(|meter| meter.replace(Meter::new(limit)));
+    let result = f();
+    let meter = METER.with(|meter| meter.replace(outer));
+
+    let result = match result {
+        Err(_) if meter.exhausted => Err(Error::OutOfGas),
+        result => result,
+    };
+
+    (result, meter.spent)
+}
+
+/// The gas charged to the meter of the current thread so far.
+pub(crate) fn spent() -> u64 {
+    METER.with(|meter| meter.get().spent)
+}
+
+/// Charges the given `gas` to the meter of the current thread, returning
+/// `false` if the limit of the meter is exceeded.
+pub(crate) fn charge(gas: u64) -> bool {
+    METER.with(|meter| {
+        let mut m = meter.get();
+        m.spent = m.spent.saturating_add(gas);
+
+        let within_limit = m.spent <= m.limit;
+        if !within_limit {
+            m.spent = m.limit;
+            m.exhausted = true;
+        }
+
+        meter.set(m);
+        within_limit
+    })
+}
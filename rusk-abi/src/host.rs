@@ -7,19 +7,30 @@
 use alloc::vec::Vec;
 use std::path::{Path, PathBuf};
 
-use dusk_bls12_381::BlsScalar;
+use dusk_bls12_381::{BlsScalar, G1Affine, G2Affine, Gt};
 use dusk_bls12_381_sign::{
-    PublicKey as BlsPublicKey, Signature as BlsSignature, APK,
+    PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    Signature as BlsSignature, APK,
 };
-use dusk_bytes::DeserializableSlice;
+use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_pki::PublicKey;
 use dusk_plonk::prelude::{Proof, Verifier};
 use dusk_schnorr::Signature;
+use ed25519_dalek::{
+    Signature as Ed25519Signature, VerifyingKey as Ed25519PublicKey,
+};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{
+    RecoveryId, Signature as EcdsaSignature, VerifyingKey as EcdsaPublicKey,
+};
 use rkyv::ser::serializers::AllocSerializer;
 use rkyv::{Archive, Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
 
 pub use piecrust::*;
 
+use crate::gas::{self, GasSchedule};
 use crate::hash::Hasher;
 use crate::{Metadata, PublicInput, Query};
 
@@ -52,38 +63,105 @@ pub fn new_genesis_session(vm: &VM) -> Session {
 pub fn new_vm<P: AsRef<Path> + Into<PathBuf>>(
     root_dir: P,
 ) -> Result<VM, Error> {
-    let mut vm = VM::new(root_dir)?;
-    register_host_queries(&mut vm);
-    Ok(vm)
+    new_vm_with_gas_schedule(root_dir, GasSchedule::DEFAULT)
 }
 
 /// Creates a new [`VM`] with a temporary directory.
 pub fn new_ephemeral_vm() -> Result<VM, Error> {
+    new_ephemeral_vm_with_gas_schedule(GasSchedule::DEFAULT)
+}
+
+/// Create a new [`VM`] compliant with Dusk's specification, charging the host
+/// functions according to the given `schedule`.
+pub fn new_vm_with_gas_schedule<P: AsRef<Path> + Into<PathBuf>>(
+    root_dir: P,
+    schedule: GasSchedule,
+) -> Result<VM, Error> {
+    let mut vm = VM::new(root_dir)?;
+    register_host_queries(&mut vm, schedule);
+    Ok(vm)
+}
+
+/// Creates a new [`VM`] with a temporary directory, charging the host functions
+/// according to the given `schedule`.
+pub fn new_ephemeral_vm_with_gas_schedule(
+    schedule: GasSchedule,
+) -> Result<VM, Error> {
     let mut vm = VM::ephemeral()?;
-    register_host_queries(&mut vm);
+    register_host_queries(&mut vm, schedule);
     Ok(vm)
 }
 
-fn register_host_queries(vm: &mut VM) {
-    vm.register_host_query(Query::HASH, host_hash);
-    vm.register_host_query(Query::POSEIDON_HASH, host_poseidon_hash);
-    vm.register_host_query(Query::VERIFY_PROOF, host_verify_proof);
-    vm.register_host_query(Query::VERIFY_SCHNORR, host_verify_schnorr);
-    vm.register_host_query(Query::VERIFY_BLS, host_verify_bls);
+fn register_host_queries(vm: &mut VM, schedule: GasSchedule) {
+    register(vm, Query::HASH, schedule, host_hash);
+    register(vm, Query::POSEIDON_HASH, schedule, host_poseidon_hash);
+    register(vm, Query::VERIFY_PROOF, schedule, host_verify_proof);
+    register(vm, Query::VERIFY_SCHNORR, schedule, host_verify_schnorr);
+    register(vm, Query::VERIFY_BLS, schedule, host_verify_bls);
+    register(
+        vm,
+        Query::VERIFY_BLS_MULTISIG,
+        schedule,
+        host_verify_bls_multisig,
+    );
+    register(vm, Query::VERIFY_BLS_APK, schedule, host_verify_bls_apk);
+    register(
+        vm,
+        Query::VERIFY_BLS_AGGREGATE,
+        schedule,
+        host_verify_bls_aggregate,
+    );
+    register(vm, Query::SHA256, schedule, host_sha256);
+    register(vm, Query::KECCAK256, schedule, host_keccak256);
+    register(
+        vm,
+        Query::VERIFY_ECDSA_SECP256K1,
+        schedule,
+        host_verify_ecdsa_secp256k1,
+    );
+    register(
+        vm,
+        Query::RECOVER_ECDSA_SECP256K1,
+        schedule,
+        host_recover_ecdsa_secp256k1,
+    );
+    register(vm, Query::VERIFY_ED25519, schedule, host_verify_ed25519);
+    vm.register_host_query(Query::HOST_GAS_SPENT, host_gas_spent);
 }
 
-fn wrap_host_query<A, R, F>(arg_buf: &mut [u8], arg_len: u32, closure: F) -> u32
+fn register<Q>(vm: &mut VM, name: &'static str, schedule: GasSchedule, query: Q)
 where
+    Q: 'static + Send + Sync + Fn(&GasSchedule, &mut [u8], u32) -> u32,
+{
+    vm.register_host_query(name, move |arg_buf: &mut [u8], arg_len| {
+        query(&schedule, arg_buf, arg_len)
+    });
+}
+
+/// Runs a host function, returning its result to the contract.
+///
+/// Host functions can't trap the contract calling them, so their result is
+/// returned as an [`Option`], being `None` if charging their cost exceeds the
+/// gas limit. The contract then panics, and [`gas::metered`] turns the failed
+/// call into [`Error::OutOfGas`].
+fn wrap_host_query<A, R, C, F>(
+    arg_buf: &mut [u8],
+    arg_len: u32,
+    cost: C,
+    closure: F,
+) -> u32
+where
+    C: FnOnce(&A) -> u64,
     F: FnOnce(A) -> R,
     A: Archive,
     A::Archived: Deserialize<A, rkyv::Infallible>,
-    R: Serialize<AllocSerializer<1024>>,
+    Option<R>: Serialize<AllocSerializer<1024>>,
 {
     let root =
         unsafe { rkyv::archived_root::<A>(&arg_buf[..arg_len as usize]) };
     let arg: A = root.deserialize(&mut rkyv::Infallible).unwrap();
 
-    let result = closure(arg);
+    let result = gas::charge(cost(&arg)).then(|| closure(arg));
 
     let bytes = rkyv::to_bytes::<_, 1024>(&result).unwrap();
 
@@ -91,28 +169,189 @@ where
     bytes.len() as u32
 }
 
-fn host_hash(arg_buf: &mut [u8], arg_len: u32) -> u32 {
-    wrap_host_query(arg_buf, arg_len, hash)
+fn host_hash(schedule: &GasSchedule, arg_buf: &mut [u8], arg_len: u32) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |bytes: &Vec<u8>| schedule.hash.cost(bytes.len()),
+        hash,
+    )
 }
 
-fn host_poseidon_hash(arg_buf: &mut [u8], arg_len: u32) -> u32 {
-    wrap_host_query(arg_buf, arg_len, poseidon_hash)
+fn host_poseidon_hash(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |scalars: &Vec<BlsScalar>| schedule.poseidon_hash.cost(scalars.len()),
+        poseidon_hash,
+    )
 }
 
-fn host_verify_proof(arg_buf: &mut [u8], arg_len: u32) -> u32 {
-    wrap_host_query(arg_buf, arg_len, |(vd, proof, pis)| {
-        verify_proof(vd, proof, pis)
-    })
+fn host_verify_proof(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(_, _, pis): &(Vec<u8>, Vec<u8>, Vec<PublicInput>)| {
+            schedule.verify_proof.cost(pis.len())
+        },
+        |(vd, proof, pis)| verify_proof(vd, proof, pis),
+    )
 }
 
-fn host_verify_schnorr(arg_buf: &mut [u8], arg_len: u32) -> u32 {
-    wrap_host_query(arg_buf, arg_len, |(msg, pk, sig)| {
-        verify_schnorr(msg, pk, sig)
-    })
+fn host_verify_schnorr(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |_: &(BlsScalar, PublicKey, Signature)| schedule.verify_schnorr.cost(0),
+        |(msg, pk, sig)| verify_schnorr(msg, pk, sig),
+    )
+}
+
+fn host_verify_bls(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(msg, _, _): &(Vec<u8>, BlsPublicKey, BlsSignature)| {
+            schedule.verify_bls.cost(msg.len())
+        },
+        |(msg, pk, sig)| verify_bls(msg, pk, sig),
+    )
 }
 
-fn host_verify_bls(arg_buf: &mut [u8], arg_len: u32) -> u32 {
-    wrap_host_query(arg_buf, arg_len, |(msg, pk, sig)| verify_bls(msg, pk, sig))
+fn host_verify_bls_multisig(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(_, pks, _): &(Vec<u8>, Vec<BlsPublicKey>, BlsSignature)| {
+            schedule.verify_bls_multisig.cost(pks.len())
+        },
+        |(msg, pks, sig)| verify_bls_multisig(msg, pks, sig),
+    )
+}
+
+fn host_verify_bls_apk(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(msg, _, _): &(Vec<u8>, APK, BlsSignature)| {
+            schedule.verify_bls_apk.cost(msg.len())
+        },
+        |(msg, apk, sig)| verify_bls_apk(msg, apk, sig),
+    )
+}
+
+fn host_verify_bls_aggregate(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(msgs, _): &(Vec<(Vec<u8>, BlsPublicKey)>, BlsSignature)| {
+            schedule.verify_bls_aggregate.cost(msgs.len())
+        },
+        |(msgs, sig)| verify_bls_aggregate(msgs, sig),
+    )
+}
+
+fn host_sha256(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |bytes: &Vec<u8>| schedule.sha256.cost(bytes.len()),
+        sha256,
+    )
+}
+
+fn host_keccak256(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |bytes: &Vec<u8>| schedule.keccak256.cost(bytes.len()),
+        keccak256,
+    )
+}
+
+fn host_verify_ecdsa_secp256k1(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |_: &([u8; 32], Vec<u8>, [u8; 64])| {
+            schedule.verify_ecdsa_secp256k1.cost(0)
+        },
+        |(msg_hash, pk, sig)| verify_ecdsa_secp256k1(msg_hash, pk, sig),
+    )
+}
+
+fn host_recover_ecdsa_secp256k1(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |_: &([u8; 32], [u8; 64], u8)| schedule.recover_ecdsa_secp256k1.cost(0),
+        |(msg_hash, sig, recovery_id)| {
+            recover_ecdsa_secp256k1(msg_hash, sig, recovery_id)
+        },
+    )
+}
+
+fn host_verify_ed25519(
+    schedule: &GasSchedule,
+    arg_buf: &mut [u8],
+    arg_len: u32,
+) -> u32 {
+    wrap_host_query(
+        arg_buf,
+        arg_len,
+        |(msg, _, _): &(Vec<u8>, [u8; 32], [u8; 64])| {
+            schedule.verify_ed25519.cost(msg.len())
+        },
+        |(msg, pk, sig)| verify_ed25519(msg, pk, sig),
+    )
+}
+
+fn host_gas_spent(arg_buf: &mut [u8], arg_len: u32) -> u32 {
+    wrap_host_query(arg_buf, arg_len, |_: &()| 0, |_| gas::spent())
 }
 
 /// Compute the blake2b hash of the given scalars, returning the resulting
@@ -172,3 +411,121 @@ pub fn verify_bls(msg: Vec<u8>, pk: BlsPublicKey, sig: BlsSignature) -> bool {
     let apk = APK::from(&pk);
     apk.verify(&sig, &msg).is_ok()
 }
+
+/// Verify an aggregated BLS signature is valid for the given public keys, all
+/// of them having signed the same message
+///
+/// Returns `false` if no public key is given.
+pub fn verify_bls_multisig(
+    msg: Vec<u8>,
+    pks: Vec<BlsPublicKey>,
+    sig: BlsSignature,
+) -> bool {
+    let Some((first, rest)) = pks.split_first() else {
+        return false;
+    };
+
+    let mut apk = APK::from(first);
+    apk.aggregate(rest);
+
+    verify_bls_apk(msg, apk, sig)
+}
+
+/// Verify an aggregated BLS signature is valid for the given aggregated public
+/// key and message
+pub fn verify_bls_apk(msg: Vec<u8>, apk: APK, sig: BlsSignature) -> bool {
+    apk.verify(&sig, &msg).is_ok()
+}
+
+/// Verify an aggregated BLS signature is valid for the given public keys, each
+/// of them having signed a distinct message
+///
+/// Returns `false` if no message is given.
+pub fn verify_bls_aggregate(
+    msgs: Vec<(Vec<u8>, BlsPublicKey)>,
+    sig: BlsSignature,
+) -> bool {
+    if msgs.is_empty() {
+        return false;
+    }
+
+    let Ok(sig) = G1Affine::from_bytes(&sig.to_bytes()) else {
+        return false;
+    };
+
+    let mut pairings = Vec::with_capacity(msgs.len());
+    for (msg, pk) in &msgs {
+        let Some(h0) = bls_hash_to_point(msg) else {
+            return false;
+        };
+        pairings.push(dusk_bls12_381::pairing(&h0, &pk.pk_t()));
+    }
+
+    let p1 = dusk_bls12_381::pairing(&sig, &G2Affine::generator());
+    let p2: Gt = pairings.into_iter().sum();
+
+    p1 == p2
+}
+
+/// Hashes a message onto G1 the same way `dusk_bls12_381_sign` does when
+/// signing, by signing it with the unit secret key.
+fn bls_hash_to_point(msg: &[u8]) -> Option<G1Affine> {
+    let sig = BlsSecretKey::from(BlsScalar::one()).sign_vulnerable(msg);
+    G1Affine::from_bytes(&sig.to_bytes()).ok()
+}
+
+/// Compute the SHA-256 hash of the given bytes
+pub fn sha256(bytes: Vec<u8>) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+/// Compute the Keccak-256 hash of the given bytes, as used by Ethereum
+pub fn keccak256(bytes: Vec<u8>) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+/// Verify an ECDSA signature over secp256k1 is valid for the given public key
+/// and message hash. The public key is SEC1 encoded, either compressed or
+/// uncompressed, and the signature is the concatenation of `r` and `s`.
+pub fn verify_ecdsa_secp256k1(
+    msg_hash: [u8; 32],
+    pk: Vec<u8>,
+    sig: [u8; 64],
+) -> bool {
+    let (Ok(pk), Ok(sig)) = (
+        EcdsaPublicKey::from_sec1_bytes(&pk),
+        EcdsaSignature::from_slice(&sig),
+    ) else {
+        return false;
+    };
+
+    pk.verify_prehash(&msg_hash, &sig).is_ok()
+}
+
+/// Recover the public key that produced an ECDSA signature over secp256k1 for
+/// the given message hash, returning it SEC1 encoded and uncompressed.
+///
+/// Returns `None` if the signature or the recovery id are invalid.
+pub fn recover_ecdsa_secp256k1(
+    msg_hash: [u8; 32],
+    sig: [u8; 64],
+    recovery_id: u8,
+) -> Option<[u8; 65]> {
+    let sig = EcdsaSignature::from_slice(&sig).ok()?;
+    let recovery_id = RecoveryId::from_byte(recovery_id)?;
+
+    let pk = EcdsaPublicKey::recover_from_prehash(&msg_hash, &sig, recovery_id)
+        .ok()?;
+
+    pk.to_encoded_point(false).as_bytes().try_into().ok()
+}
+
+/// Verify an ed25519 signature is valid for the given public key and message
+pub fn verify_ed25519(msg: Vec<u8>, pk: [u8; 32], sig: [u8; 64]) -> bool {
+    let Ok(pk) = Ed25519PublicKey::from_bytes(&pk) else {
+        return false;
+    };
+    let sig = Ed25519Signature::from_bytes(&sig);
+
+    pk.verify_strict(&msg, &sig).is_ok()
+}
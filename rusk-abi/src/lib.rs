@@ -33,6 +33,9 @@ mod host;
 #[cfg(feature = "host")]
 pub use host::*;
 
+#[cfg(feature = "host")]
+pub mod gas;
+
 pub mod dusk;
 #[doc(hidden)]
 pub mod hash;
@@ -21,6 +21,15 @@ impl Query {
     pub const VERIFY_PROOF: &'static str = "verify_proof";
     pub const VERIFY_SCHNORR: &'static str = "verify_schnorr";
     pub const VERIFY_BLS: &'static str = "verify_bls";
+    pub const VERIFY_BLS_MULTISIG: &'static str = "verify_bls_multisig";
+    pub const VERIFY_BLS_APK: &'static str = "verify_bls_apk";
+    pub const VERIFY_BLS_AGGREGATE: &'static str = "verify_bls_aggregate";
+    pub const SHA256: &'static str = "sha256";
+    pub const KECCAK256: &'static str = "keccak256";
+    pub const VERIFY_ECDSA_SECP256K1: &'static str = "verify_ecdsa_secp256k1";
+    pub const RECOVER_ECDSA_SECP256K1: &'static str = "recover_ecdsa_secp256k1";
+    pub const VERIFY_ED25519: &'static str = "verify_ed25519";
+    pub const HOST_GAS_SPENT: &'static str = "host_gas_spent";
 }
 
 pub(crate) enum Metadata {}
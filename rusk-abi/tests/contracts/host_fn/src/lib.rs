@@ -13,7 +13,7 @@ use alloc::vec::Vec;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bls12_381_sign::{
-    PublicKey as BlsPublicKey, Signature as BlsSignature,
+    PublicKey as BlsPublicKey, Signature as BlsSignature, APK,
 };
 use dusk_bytes::Serializable;
 use dusk_pki::{PublicKey, PublicSpendKey};
@@ -64,6 +64,72 @@ impl HostFnTest {
         rusk_abi::verify_bls(msg, pk, sig)
     }
 
+    pub fn verify_bls_multisig(
+        &self,
+        msg: Vec<u8>,
+        pks: Vec<BlsPublicKey>,
+        sig: BlsSignature,
+    ) -> bool {
+        rusk_abi::verify_bls_multisig(msg, pks, sig)
+    }
+
+    pub fn verify_bls_apk(
+        &self,
+        msg: Vec<u8>,
+        apk: APK,
+        sig: BlsSignature,
+    ) -> bool {
+        rusk_abi::verify_bls_apk(msg, apk, sig)
+    }
+
+    pub fn verify_bls_aggregate(
+        &self,
+        msgs: Vec<(Vec<u8>, BlsPublicKey)>,
+        sig: BlsSignature,
+    ) -> bool {
+        rusk_abi::verify_bls_aggregate(msgs, sig)
+    }
+
+    pub fn sha256(&self, bytes: Vec<u8>) -> [u8; 32] {
+        rusk_abi::sha256(bytes)
+    }
+
+    pub fn sha256_gas_spent(&self, bytes: Vec<u8>) -> u64 {
+        rusk_abi::sha256(bytes);
+        rusk_abi::host_gas_spent()
+    }
+
+    pub fn keccak256(&self, bytes: Vec<u8>) -> [u8; 32] {
+        rusk_abi::keccak256(bytes)
+    }
+
+    pub fn verify_ecdsa_secp256k1(
+        &self,
+        msg_hash: [u8; 32],
+        pk: Vec<u8>,
+        sig: [u8; 64],
+    ) -> bool {
+        rusk_abi::verify_ecdsa_secp256k1(msg_hash, pk, sig)
+    }
+
+    pub fn recover_ecdsa_secp256k1(
+        &self,
+        msg_hash: [u8; 32],
+        sig: [u8; 64],
+        recovery_id: u8,
+    ) -> Option<[u8; 65]> {
+        rusk_abi::recover_ecdsa_secp256k1(msg_hash, sig, recovery_id)
+    }
+
+    pub fn verify_ed25519(
+        &self,
+        msg: Vec<u8>,
+        pk: [u8; 32],
+        sig: [u8; 64],
+    ) -> bool {
+        rusk_abi::verify_ed25519(msg, pk, sig)
+    }
+
     pub fn block_height(&self) -> u64 {
         rusk_abi::block_height()
     }
@@ -104,6 +170,63 @@ unsafe fn verify_bls(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn verify_bls_multisig(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msg, pks, sig)| {
+        STATE.verify_bls_multisig(msg, pks, sig)
+    })
+}
+
+#[no_mangle]
+unsafe fn verify_bls_apk(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msg, apk, sig)| {
+        STATE.verify_bls_apk(msg, apk, sig)
+    })
+}
+
+#[no_mangle]
+unsafe fn verify_bls_aggregate(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msgs, sig)| {
+        STATE.verify_bls_aggregate(msgs, sig)
+    })
+}
+
+#[no_mangle]
+unsafe fn sha256(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |bytes| STATE.sha256(bytes))
+}
+
+#[no_mangle]
+unsafe fn sha256_gas_spent(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |bytes| STATE.sha256_gas_spent(bytes))
+}
+
+#[no_mangle]
+unsafe fn keccak256(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |bytes| STATE.keccak256(bytes))
+}
+
+#[no_mangle]
+unsafe fn verify_ecdsa_secp256k1(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msg_hash, pk, sig)| {
+        STATE.verify_ecdsa_secp256k1(msg_hash, pk, sig)
+    })
+}
+
+#[no_mangle]
+unsafe fn recover_ecdsa_secp256k1(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msg_hash, sig, recovery_id)| {
+        STATE.recover_ecdsa_secp256k1(msg_hash, sig, recovery_id)
+    })
+}
+
+#[no_mangle]
+unsafe fn verify_ed25519(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |(msg, pk, sig)| {
+        STATE.verify_ed25519(msg, pk, sig)
+    })
+}
+
 #[no_mangle]
 unsafe fn block_height(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |_: ()| STATE.block_height())
@@ -8,21 +8,28 @@
 #![cfg(feature = "host")]
 
 use std::sync::OnceLock;
+use std::time::Instant;
 
-use rand_core::OsRng;
+use rand_core::{OsRng, RngCore};
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bls12_381_sign::{
-    PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    PublicKey as BlsPublicKey, SecretKey as BlsSecretKey, APK,
 };
 use dusk_bytes::{ParseHexStr, Serializable};
 use dusk_pki::{PublicKey, PublicSpendKey, SecretKey, SecretSpendKey};
 use dusk_plonk::prelude::*;
 use dusk_schnorr::Signature;
+use ed25519_dalek::{Signer, SigningKey as Ed25519SecretKey};
 use ff::Field;
+use k256::ecdsa::SigningKey as EcdsaSecretKey;
+use rusk_abi::gas::{self, GasSchedule, HostCost};
 use rusk_abi::hash::Hasher;
 use rusk_abi::PublicInput;
 use rusk_abi::{ContractData, ContractId, Session, VM};
+use sha3::{Digest, Keccak256};
 
 const POINT_LIMIT: u64 = 0x700000;
 
@@ -209,6 +216,220 @@ fn bls_signature() {
     assert!(!valid, "BLS Signature verification expected to fail");
 }
 
+#[test]
+fn bls_multisig() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let message = b"some-message".to_vec();
+
+    let sks: Vec<_> =
+        (0..4).map(|_| BlsSecretKey::random(&mut OsRng)).collect();
+    let pks: Vec<_> = sks.iter().map(BlsPublicKey::from).collect();
+
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&pks)
+        .map(|(sk, pk)| sk.sign(pk, &message))
+        .collect();
+    let sig = sigs[0].aggregate(&sigs[1..]);
+
+    let arg = (message.clone(), pks.clone(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_multisig", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(valid, "BLS multisig verification expected to succeed");
+
+    let mut apk = APK::from(&pks[0]);
+    apk.aggregate(&pks[1..]);
+
+    let arg = (message.clone(), apk, sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_apk", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(valid, "BLS APK verification expected to succeed");
+
+    let arg = (message.clone(), pks[1..].to_vec(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_multisig", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(!valid, "BLS multisig verification expected to fail");
+
+    let arg = (message, Vec::<BlsPublicKey>::new(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_multisig", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(
+        !valid,
+        "BLS multisig verification without keys expected to fail"
+    );
+}
+
+#[test]
+fn bls_aggregate() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let mut msgs = Vec::new();
+    let mut sigs = Vec::new();
+
+    for i in 0..4 {
+        let message = format!("some-message-{i}").into_bytes();
+
+        let sk = BlsSecretKey::random(&mut OsRng);
+        let pk = BlsPublicKey::from(&sk);
+
+        sigs.push(sk.sign(&pk, &message));
+        msgs.push((message, pk));
+    }
+    let sig = sigs[0].aggregate(&sigs[1..]);
+
+    let arg = (msgs.clone(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_aggregate", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(valid, "BLS aggregate verification expected to succeed");
+
+    let mut wrong_msgs = msgs;
+    wrong_msgs[0].0 = b"wrong-message".to_vec();
+
+    let arg = (wrong_msgs, sig);
+    let valid: bool = session
+        .call(contract_id, "verify_bls_aggregate", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(!valid, "BLS aggregate verification expected to fail");
+}
+
+#[test]
+fn sha256() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let hash: [u8; 32] = session
+        .call(contract_id, "sha256", &b"abc".to_vec(), POINT_LIMIT)
+        .expect("Querying should succeed")
+        .data;
+
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        hex::encode(hash)
+    );
+}
+
+#[test]
+fn keccak256() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let hash: [u8; 32] = session
+        .call(contract_id, "keccak256", &b"abc".to_vec(), POINT_LIMIT)
+        .expect("Querying should succeed")
+        .data;
+
+    assert_eq!(
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        hex::encode(hash)
+    );
+}
+
+#[test]
+fn ecdsa_secp256k1_signature() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let sk = EcdsaSecretKey::random(&mut OsRng);
+    let pk = sk.verifying_key().to_encoded_point(false);
+    let pk: [u8; 65] = pk.as_bytes().try_into().unwrap();
+
+    let msg_hash: [u8; 32] = Keccak256::digest(b"some-message").into();
+    let (sig, recovery_id) = sk
+        .sign_prehash_recoverable(&msg_hash)
+        .expect("Signing should succeed");
+    let sig: [u8; 64] = sig.to_bytes().into();
+
+    let arg = (msg_hash, pk.to_vec(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_ecdsa_secp256k1", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(valid, "ECDSA signature verification expected to succeed");
+
+    let arg = (msg_hash, sig, recovery_id.to_byte());
+    let recovered: Option<[u8; 65]> = session
+        .call(contract_id, "recover_ecdsa_secp256k1", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert_eq!(recovered, Some(pk), "The public key should be recovered");
+
+    let wrong_sk = EcdsaSecretKey::random(&mut OsRng);
+    let wrong_pk = wrong_sk.verifying_key().to_encoded_point(true);
+
+    let arg = (msg_hash, wrong_pk.as_bytes().to_vec(), sig);
+    let valid: bool = session
+        .call(contract_id, "verify_ecdsa_secp256k1", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(!valid, "ECDSA signature verification expected to fail");
+}
+
+#[test]
+fn ed25519_signature() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let message = b"some-message".to_vec();
+
+    let sk = Ed25519SecretKey::from_bytes(&rand_bytes());
+    let pk = sk.verifying_key().to_bytes();
+    let sig = sk.sign(&message).to_bytes();
+
+    let arg = (message, pk, sig);
+    let valid: bool = session
+        .call(contract_id, "verify_ed25519", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(valid, "Ed25519 signature verification expected to succeed");
+
+    let wrong_sk = Ed25519SecretKey::from_bytes(&rand_bytes());
+    let wrong_pk = wrong_sk.verifying_key().to_bytes();
+
+    let arg = (arg.0, wrong_pk, arg.2);
+    let valid: bool = session
+        .call(contract_id, "verify_ed25519", &arg, POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+
+    assert!(!valid, "Ed25519 signature verification expected to fail");
+}
+
+fn rand_bytes() -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
 #[derive(Debug, Default)]
 pub struct TestCircuit {
     pub a: BlsScalar,
@@ -343,3 +564,412 @@ fn owner() {
 
     assert_eq!(owner, get_owner().to_bytes());
 }
+
+#[test]
+fn host_gas() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let bytes = vec![0u8; 1024];
+
+    let (receipt, host_gas) = gas::metered(POINT_LIMIT, || {
+        session.call::<_, [u8; 32]>(contract_id, "sha256", &bytes, POINT_LIMIT)
+    });
+    receipt.expect("Querying should succeed");
+
+    assert_eq!(host_gas, GasSchedule::DEFAULT.sha256.cost(bytes.len()));
+
+    let (receipt, host_gas) = gas::metered(POINT_LIMIT, || {
+        session.call::<_, u64>(
+            contract_id,
+            "sha256_gas_spent",
+            &bytes,
+            POINT_LIMIT,
+        )
+    });
+    let queried = receipt.expect("Querying should succeed").data;
+
+    assert_eq!(queried, host_gas, "Contracts should see the gas charged");
+
+    let schedule = GasSchedule {
+        sha256: HostCost::new(1, 1),
+        ..GasSchedule::DEFAULT
+    };
+    let vm = rusk_abi::new_ephemeral_vm_with_gas_schedule(schedule)
+        .expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let (receipt, host_gas) = gas::metered(POINT_LIMIT, || {
+        session.call::<_, [u8; 32]>(contract_id, "sha256", &bytes, POINT_LIMIT)
+    });
+    receipt.expect("Querying should succeed");
+
+    assert_eq!(host_gas, 1025, "The given schedule should be used");
+}
+
+#[test]
+fn host_out_of_gas() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let message = b"some-message".to_vec();
+
+    let sk = BlsSecretKey::random(&mut OsRng);
+    let pk = BlsPublicKey::from(&sk);
+    let sign = sk.sign(&pk, &message);
+
+    let limit = GasSchedule::DEFAULT.verify_bls.cost(message.len()) - 1;
+    let arg = (message, pk, sign);
+
+    let (receipt, host_gas) = gas::metered(limit, || {
+        session.call::<_, bool>(contract_id, "verify_bls", &arg, POINT_LIMIT)
+    });
+
+    let err = receipt.expect_err("Exceeding the limit should fail the call");
+    assert!(
+        matches!(err, rusk_abi::Error::OutOfGas),
+        "The call should run out of gas"
+    );
+    assert_eq!(host_gas, limit, "All the gas should be spent");
+
+    let (receipt, _) = gas::metered(limit + 1, || {
+        session.call::<_, bool>(contract_id, "verify_bls", &arg, POINT_LIMIT)
+    });
+
+    assert!(receipt.expect("Querying should succeed").data);
+}
+
+#[test]
+fn gas_schedule_table() {
+    let docs = include_str!("../src/gas.rs");
+
+    let rows: Vec<Vec<&str>> = docs
+        .lines()
+        .filter_map(|line| line.strip_prefix("//! | `"))
+        .map(|row| row.split('|').map(|cell| cell.trim()).collect())
+        .collect();
+
+    assert_eq!(rows.len(), 13, "All host functions should be documented");
+
+    for row in rows {
+        let name = row[0].trim_end_matches('`');
+        let cost = GasSchedule::DEFAULT
+            .get(name)
+            .unwrap_or_else(|| panic!("{name} should be in the schedule"));
+
+        let documented = HostCost::new(
+            row[2].parse().expect("Base cost should be a number"),
+            row[3].parse().expect("Unit cost should be a number"),
+        );
+        assert_eq!(cost, documented, "{name} should be documented correctly");
+    }
+}
+
+/// Asserts that calling `fn_name` on the contract charges exactly `cost` gas
+/// for host functions.
+fn assert_charged<A>(
+    session: &mut Session,
+    contract_id: ContractId,
+    fn_name: &str,
+    arg: &A,
+    cost: u64,
+) where
+    A: Serialize<AllocSerializer<1024>>,
+{
+    let (receipt, host_gas) = gas::metered(u64::MAX, || {
+        session.call_raw(
+            contract_id,
+            fn_name,
+            rkyv::to_bytes::<_, 1024>(arg)
+                .expect("Serializing should succeed")
+                .to_vec(),
+            POINT_LIMIT,
+        )
+    });
+    receipt.unwrap_or_else(|err| panic!("Calling {fn_name} failed: {err}"));
+
+    assert_eq!(
+        host_gas, cost,
+        "{fn_name} should be charged by the schedule"
+    );
+}
+
+#[test]
+fn gas_schedule_charges() {
+    let vm =
+        rusk_abi::new_ephemeral_vm().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+    let session = &mut session;
+
+    let schedule = GasSchedule::DEFAULT;
+
+    let bytes = vec![0u8; 1024];
+    let message = b"some-message".to_vec();
+
+    let cost = schedule.hash.cost(bytes.len());
+    assert_charged(session, contract_id, "hash", &bytes, cost);
+    let cost = schedule.sha256.cost(bytes.len());
+    assert_charged(session, contract_id, "sha256", &bytes, cost);
+    let cost = schedule.keccak256.cost(bytes.len());
+    assert_charged(session, contract_id, "keccak256", &bytes, cost);
+
+    let scalars = vec![BlsScalar::one(); 16];
+    let cost = schedule.poseidon_hash.cost(scalars.len());
+    assert_charged(session, contract_id, "poseidon_hash", &scalars, cost);
+
+    let sk = SecretKey::random(&mut OsRng);
+    let pk = PublicKey::from(&sk);
+    let msg = BlsScalar::random(&mut OsRng);
+    let sig = Signature::new(&sk, &mut OsRng, msg);
+    let cost = schedule.verify_schnorr.cost(0);
+    let arg = (msg, pk, sig);
+    assert_charged(session, contract_id, "verify_schnorr", &arg, cost);
+
+    let sks: Vec<_> =
+        (0..8).map(|_| BlsSecretKey::random(&mut OsRng)).collect();
+    let pks: Vec<_> = sks.iter().map(BlsPublicKey::from).collect();
+
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&pks)
+        .map(|(sk, pk)| sk.sign(pk, &message))
+        .collect();
+    let sig = sigs[0].aggregate(&sigs[1..]);
+
+    let cost = schedule.verify_bls.cost(message.len());
+    let arg = (message.clone(), pks[0], sigs[0]);
+    assert_charged(session, contract_id, "verify_bls", &arg, cost);
+
+    let cost = schedule.verify_bls_multisig.cost(pks.len());
+    let arg = (message.clone(), pks.clone(), sig);
+    assert_charged(session, contract_id, "verify_bls_multisig", &arg, cost);
+
+    let mut apk = APK::from(&pks[0]);
+    apk.aggregate(&pks[1..]);
+    let cost = schedule.verify_bls_apk.cost(message.len());
+    let arg = (message.clone(), apk, sig);
+    assert_charged(session, contract_id, "verify_bls_apk", &arg, cost);
+
+    let msgs: Vec<_> = pks
+        .iter()
+        .enumerate()
+        .map(|(i, pk)| (format!("some-message-{i}").into_bytes(), *pk))
+        .collect();
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&msgs)
+        .map(|(sk, (msg, pk))| sk.sign(pk, msg))
+        .collect();
+    let sig = sigs[0].aggregate(&sigs[1..]);
+    let cost = schedule.verify_bls_aggregate.cost(msgs.len());
+    let arg = (msgs, sig);
+    assert_charged(session, contract_id, "verify_bls_aggregate", &arg, cost);
+
+    let sk = EcdsaSecretKey::random(&mut OsRng);
+    let pk = sk
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let msg_hash: [u8; 32] = Keccak256::digest(&message).into();
+    let (sig, recovery_id) = sk
+        .sign_prehash_recoverable(&msg_hash)
+        .expect("Signing should succeed");
+    let sig: [u8; 64] = sig.to_bytes().into();
+
+    let cost = schedule.verify_ecdsa_secp256k1.cost(0);
+    let arg = (msg_hash, pk, sig);
+    assert_charged(session, contract_id, "verify_ecdsa_secp256k1", &arg, cost);
+    let cost = schedule.recover_ecdsa_secp256k1.cost(0);
+    let arg = (msg_hash, sig, recovery_id.to_byte());
+    assert_charged(session, contract_id, "recover_ecdsa_secp256k1", &arg, cost);
+
+    let sk = Ed25519SecretKey::from_bytes(&rand_bytes());
+    let pk = sk.verifying_key().to_bytes();
+    let sig = sk.sign(&bytes).to_bytes();
+    let cost = schedule.verify_ed25519.cost(bytes.len());
+    let arg = (bytes, pk, sig);
+    assert_charged(session, contract_id, "verify_ed25519", &arg, cost);
+
+    let pp = include_bytes!("./pp_test.bin");
+    let pp = unsafe { PublicParameters::from_slice_unchecked(&pp[..]) };
+
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"dusk-network")
+            .expect("Circuit should compile successfully");
+    let (proof, pis) = prover
+        .prove(&mut OsRng, &TestCircuit::new(1, 2))
+        .expect("Proving circuit should succeed");
+
+    let verifier = verifier.to_bytes();
+    let proof = proof.to_bytes().to_vec();
+    let pis: Vec<PublicInput> = pis.into_iter().map(Into::into).collect();
+
+    let cost = schedule.verify_proof.cost(pis.len());
+    let arg = (verifier, proof, pis);
+    assert_charged(session, contract_id, "verify_proof", &arg, cost);
+}
+
+/// Maximum ratio between the time a host function takes, in nanoseconds, and
+/// the gas it is charged, or the other way around.
+const MAX_CALIBRATION_RATIO: u64 = 4;
+
+/// Asserts that calling `f` takes roughly as many nanoseconds as the gas
+/// charged by `cost`, within a factor of [`MAX_CALIBRATION_RATIO`].
+fn assert_calibrated<F: FnMut()>(name: &str, cost: u64, mut f: F) {
+    const RUNS: usize = 16;
+
+    f();
+    let ns = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_nanos() as u64
+        })
+        .min()
+        .expect("There should be at least a run");
+
+    println!("{name}: {ns}ns for {cost} gas");
+    assert!(
+        ns <= MAX_CALIBRATION_RATIO * cost,
+        "{name} takes {ns}ns, but is only charged {cost} gas"
+    );
+    assert!(
+        cost <= MAX_CALIBRATION_RATIO * ns,
+        "{name} takes {ns}ns, but is charged {cost} gas"
+    );
+}
+
+/// Checks the default schedule against the time the host functions take.
+///
+/// Timings depend on the machine, so the test is only run on demand, in
+/// release mode, when changing the schedule or the host functions:
+/// `cargo test --release --no-default-features --features=host --
+/// --ignored gas_schedule_calibration`.
+#[test]
+#[ignore = "calibration, to be run on demand in release mode"]
+fn gas_schedule_calibration() {
+    let schedule = GasSchedule::DEFAULT;
+
+    let bytes = vec![0u8; 64 * 1024];
+    let message = b"some-message".to_vec();
+
+    assert_calibrated("hash", schedule.hash.cost(bytes.len()), || {
+        rusk_abi::hash(bytes.clone());
+    });
+    assert_calibrated("sha256", schedule.sha256.cost(bytes.len()), || {
+        rusk_abi::sha256(bytes.clone());
+    });
+    assert_calibrated("keccak256", schedule.keccak256.cost(bytes.len()), || {
+        rusk_abi::keccak256(bytes.clone());
+    });
+
+    let scalars = vec![BlsScalar::one(); 16];
+    let cost = schedule.poseidon_hash.cost(scalars.len());
+    assert_calibrated("poseidon_hash", cost, || {
+        rusk_abi::poseidon_hash(scalars.clone());
+    });
+
+    let sk = SecretKey::random(&mut OsRng);
+    let pk = PublicKey::from(&sk);
+    let msg = BlsScalar::random(&mut OsRng);
+    let sig = Signature::new(&sk, &mut OsRng, msg);
+    let cost = schedule.verify_schnorr.cost(0);
+    assert_calibrated("verify_schnorr", cost, || {
+        rusk_abi::verify_schnorr(msg, pk, sig);
+    });
+
+    let sks: Vec<_> =
+        (0..8).map(|_| BlsSecretKey::random(&mut OsRng)).collect();
+    let pks: Vec<_> = sks.iter().map(BlsPublicKey::from).collect();
+
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&pks)
+        .map(|(sk, pk)| sk.sign(pk, &message))
+        .collect();
+    let sig = sigs[0].aggregate(&sigs[1..]);
+
+    let cost = schedule.verify_bls.cost(message.len());
+    assert_calibrated("verify_bls", cost, || {
+        rusk_abi::verify_bls(message.clone(), pks[0], sigs[0]);
+    });
+
+    let cost = schedule.verify_bls_multisig.cost(pks.len());
+    assert_calibrated("verify_bls_multisig", cost, || {
+        rusk_abi::verify_bls_multisig(message.clone(), pks.clone(), sig);
+    });
+
+    let mut apk = APK::from(&pks[0]);
+    apk.aggregate(&pks[1..]);
+    let cost = schedule.verify_bls_apk.cost(message.len());
+    assert_calibrated("verify_bls_apk", cost, || {
+        rusk_abi::verify_bls_apk(message.clone(), apk, sig);
+    });
+
+    let msgs: Vec<_> = pks
+        .iter()
+        .enumerate()
+        .map(|(i, pk)| (format!("some-message-{i}").into_bytes(), *pk))
+        .collect();
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&msgs)
+        .map(|(sk, (msg, pk))| sk.sign(pk, msg))
+        .collect();
+    let sig = sigs[0].aggregate(&sigs[1..]);
+    let cost = schedule.verify_bls_aggregate.cost(msgs.len());
+    assert_calibrated("verify_bls_aggregate", cost, || {
+        rusk_abi::verify_bls_aggregate(msgs.clone(), sig);
+    });
+
+    let sk = EcdsaSecretKey::random(&mut OsRng);
+    let pk = sk
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let msg_hash: [u8; 32] = Keccak256::digest(&message).into();
+    let (sig, recovery_id) = sk
+        .sign_prehash_recoverable(&msg_hash)
+        .expect("Signing should succeed");
+    let sig: [u8; 64] = sig.to_bytes().into();
+
+    let cost = schedule.verify_ecdsa_secp256k1.cost(0);
+    assert_calibrated("verify_ecdsa_secp256k1", cost, || {
+        rusk_abi::verify_ecdsa_secp256k1(msg_hash, pk.clone(), sig);
+    });
+    let cost = schedule.recover_ecdsa_secp256k1.cost(0);
+    assert_calibrated("recover_ecdsa_secp256k1", cost, || {
+        rusk_abi::recover_ecdsa_secp256k1(msg_hash, sig, recovery_id.to_byte());
+    });
+
+    let sk = Ed25519SecretKey::from_bytes(&rand_bytes());
+    let pk = sk.verifying_key().to_bytes();
+    let sig = sk.sign(&bytes).to_bytes();
+    let cost = schedule.verify_ed25519.cost(bytes.len());
+    assert_calibrated("verify_ed25519", cost, || {
+        rusk_abi::verify_ed25519(bytes.clone(), pk, sig);
+    });
+
+    let pp = include_bytes!("./pp_test.bin");
+    let pp = unsafe { PublicParameters::from_slice_unchecked(&pp[..]) };
+
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"dusk-network")
+            .expect("Circuit should compile successfully");
+    let (proof, pis) = prover
+        .prove(&mut OsRng, &TestCircuit::new(1, 2))
+        .expect("Proving circuit should succeed");
+
+    let verifier = verifier.to_bytes();
+    let proof = proof.to_bytes().to_vec();
+    let pis: Vec<PublicInput> = pis.into_iter().map(Into::into).collect();
+
+    let cost = schedule.verify_proof.cost(pis.len());
+    assert_calibrated("verify_proof", cost, || {
+        rusk_abi::verify_proof(verifier.clone(), proof.clone(), pis.clone());
+    });
+}
@@ -80,7 +80,7 @@ fn deploy_governance_contract(
     session.call::<_, ()>(
         contract_id,
         "set_authority",
-        governance.authority(),
+        &governance.authority(),
         u64::MAX,
     )?;
 
@@ -70,6 +70,12 @@ impl Snapshot {
         self.stake.iter()
     }
 
+    /// Replaces the stakes included in this snapshot.
+    pub fn with_stakes(mut self, stakes: Vec<GenesisStake>) -> Self {
+        self.stake = stakes;
+        self
+    }
+
     /// Return the owner of the smart contract.
     pub fn owner(&self) -> [u8; PublicSpendKey::SIZE] {
         let dusk = Wrapper::from(*state::DUSK_KEY);
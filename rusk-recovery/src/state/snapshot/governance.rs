@@ -19,7 +19,8 @@ pub struct Governance {
         Option<Wrapper<PublicSpendKey, { PublicSpendKey::SIZE }>>,
     pub contract_address: u64,
     pub name: String,
-    pub(crate) authority: Wrapper<BlsPublicKey, { BlsPublicKey::SIZE }>,
+    pub(crate) authority: Vec<Wrapper<BlsPublicKey, { BlsPublicKey::SIZE }>>,
+    pub threshold: Option<u32>,
     pub(crate) broker: Wrapper<PublicKey, { PublicKey::SIZE }>,
 }
 
@@ -29,9 +30,14 @@ impl Governance {
         self.contract_owner.as_ref().unwrap_or(&dusk).to_bytes()
     }
 
-    pub fn authority(&self) -> &BlsPublicKey {
-        &self.authority
+    /// Returns the authority keys together with the number of approvals
+    /// required, defaulting to all of the keys.
+    pub fn authority(&self) -> (Vec<BlsPublicKey>, u32) {
+        let keys: Vec<_> = self.authority.iter().map(|k| **k).collect();
+        let threshold = self.threshold.unwrap_or(keys.len() as u32);
+        (keys, threshold)
     }
+
     pub fn broker(&self) -> &PublicKey {
         &self.broker
     }
@@ -20,6 +20,16 @@ pub struct GenesisStake {
 }
 
 impl GenesisStake {
+    /// Creates a stake of the given amount, eligible and without rewards.
+    pub fn new(address: BlsPublicKey, amount: Dusk) -> Self {
+        Self {
+            address: address.into(),
+            amount,
+            eligibility: None,
+            reward: None,
+        }
+    }
+
     pub fn address(&self) -> &BlsPublicKey {
         &self.address
     }
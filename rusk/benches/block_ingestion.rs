@@ -97,6 +97,7 @@ pub fn accept_benchmark(c: &mut Criterion) {
                             txs,
                             None,
                             &[],
+                            &[],
                         )
                         .expect("Accepting transactions should succeed");
 
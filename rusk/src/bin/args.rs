@@ -6,6 +6,13 @@
 
 #[cfg(any(feature = "recovery-state", feature = "recovery-keys"))]
 mod command;
+#[cfg(all(feature = "recovery-state", feature = "node"))]
+mod devnet;
+#[cfg(all(
+    any(feature = "recovery-state", feature = "recovery-keys"),
+    feature = "node"
+))]
+mod migrate;
 #[cfg(feature = "recovery-state")]
 mod state;
 
@@ -10,6 +10,8 @@ use rusk_recovery_tools::Theme;
 use std::io;
 use tracing::info;
 
+use crate::config::Config;
+
 #[allow(clippy::large_enum_variant)]
 #[derive(PartialEq, Eq, Hash, Clone, Subcommand, Debug)]
 pub enum Command {
@@ -35,6 +37,52 @@ pub enum Command {
         #[clap(short, long, value_parser, num_args(1))]
         output: Option<super::PathBuf>,
     },
+
+    /// Generates and launches a local network of provisioners
+    #[cfg(all(feature = "recovery-state", feature = "node"))]
+    Devnet {
+        /// Number of nodes, each staking as a provisioner
+        #[clap(short, long, default_value_t = 4)]
+        nodes: usize,
+
+        /// Directory the keys, state, configurations and logs of the nodes
+        /// are written to
+        #[clap(short, long, value_parser, default_value = "devnet")]
+        dir: super::PathBuf,
+
+        /// Snapshot the genesis state is built from, with its stakes
+        /// replaced by the ones of the nodes.
+        #[clap(short, long, value_parser)]
+        init: Option<super::PathBuf>,
+
+        /// Kadcast port of the first node, incremented for the others
+        #[clap(long, default_value_t = 9000)]
+        kadcast_port: u16,
+
+        /// HTTP port of the first node, incremented for the others
+        #[clap(long, default_value_t = 8080)]
+        http_port: u16,
+
+        /// Regenerates the devnet even if it exists in the directory
+        #[clap(short = 'f', value_parser = BoolishValueParser::new(), long)]
+        force: bool,
+
+        /// Only generates the devnet, without launching the nodes
+        #[clap(long)]
+        no_launch: bool,
+    },
+
+    /// Migrates the chain database to the schema of this node
+    #[cfg(feature = "node")]
+    MigrateDb {
+        /// Only reports the migrations, without applying them
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Migrates without backing up the database first
+        #[clap(long)]
+        no_backup: bool,
+    },
 }
 
 impl Command {
@@ -51,7 +99,11 @@ impl Command {
         Ok(())
     }
 
-    pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg_attr(not(feature = "node"), allow(unused_variables))]
+    pub fn run(
+        self,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let theme = Theme::default();
 
         Self::display_env(&theme)?;
@@ -67,6 +119,33 @@ impl Command {
             Self::RecoveryKeys { keep } => {
                 rusk_recovery_tools::keys::exec(keep)
             }
+            #[cfg(all(feature = "recovery-state", feature = "node"))]
+            Self::Devnet {
+                nodes,
+                dir,
+                init,
+                kadcast_port,
+                http_port,
+                force,
+                no_launch,
+            } => super::devnet::Devnet {
+                nodes,
+                dir,
+                init,
+                kadcast_port,
+                http_port,
+                force,
+                launch: !no_launch,
+            }
+            .run(),
+            #[cfg(feature = "node")]
+            Self::MigrateDb { dry_run, no_backup } => {
+                super::migrate::migrate_db(
+                    config.chain.db_path(),
+                    dry_run,
+                    no_backup,
+                )
+            }
         };
 
         if let Err(e) = &result {
@@ -0,0 +1,353 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+
+use std::fs::{self, File};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process::{Child, Stdio};
+use std::{env, io};
+
+use dusk_bls12_381_sign::{PublicKey as BlsPublicKey, SecretKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rusk::chain::MINIMUM_STAKE;
+use rusk_recovery_tools::state::{deploy, GenesisStake, Snapshot};
+use rusk_recovery_tools::Theme;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Password the consensus keys of the devnet are encrypted with.
+const KEYS_PASSWORD: &str = "password";
+
+/// Number of nodes every node bootstraps from.
+const BOOTSTRAPPING_NODES: usize = 2;
+
+/// The options of a devnet.
+pub struct Devnet {
+    pub nodes: usize,
+    pub dir: PathBuf,
+    pub init: Option<PathBuf>,
+    pub kadcast_port: u16,
+    pub http_port: u16,
+    pub force: bool,
+    pub launch: bool,
+}
+
+/// The parameters a devnet was generated with, checked against the options
+/// when reusing it.
+#[derive(Serialize, Deserialize)]
+struct DevnetInfo {
+    nodes: usize,
+}
+
+/// The sections of the node configuration differing between devnet nodes.
+#[derive(Serialize)]
+struct NodeConfig {
+    log_type: &'static str,
+    chain: ChainSection,
+    http: HttpSection,
+    kadcast: kadcast::config::Config,
+}
+
+#[derive(Serialize)]
+struct ChainSection {
+    db_path: PathBuf,
+    consensus_keys_path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct HttpSection {
+    listen_address: String,
+}
+
+impl Devnet {
+    pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
+        let theme = Theme::default();
+
+        if self.nodes == 0 {
+            Err("A devnet needs at least one node")?
+        }
+
+        if self.force {
+            self.remove()?;
+        }
+
+        // Fail early on ports overflowing, rather than after generating
+        let last = self.nodes - 1;
+        self.kadcast_address(last)?;
+        self.http_address(last)?;
+
+        if self.genesis_path().exists() {
+            self.check_info()?;
+            info!("{} devnet at {}", theme.info("Found"), self.dir.display());
+        } else {
+            self.generate(&theme)?;
+        }
+
+        if self.launch {
+            self.launch(&theme)?;
+        }
+
+        Ok(())
+    }
+
+    fn node_dir(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("node-{index}"))
+    }
+
+    fn genesis_path(&self) -> PathBuf {
+        self.dir.join("genesis.toml")
+    }
+
+    fn info_path(&self) -> PathBuf {
+        self.dir.join("devnet.toml")
+    }
+
+    fn kadcast_address(&self, index: usize) -> Result<SocketAddr, String> {
+        local_address(self.kadcast_port, index)
+    }
+
+    fn http_address(&self, index: usize) -> Result<SocketAddr, String> {
+        local_address(self.http_port, index)
+    }
+
+    /// Removes the files of the devnet generated in the directory, if any,
+    /// leaving anything else in it untouched.
+    fn remove(&self) -> io::Result<()> {
+        if !self.genesis_path().exists() && !self.info_path().exists() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let is_node_dir = name
+                .to_str()
+                .and_then(|name| name.strip_prefix("node-"))
+                .is_some_and(|index| index.parse::<usize>().is_ok());
+
+            if is_node_dir && entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+
+        remove_path(&self.dir.join("state"))?;
+        remove_path(&self.dir.join("logs"))?;
+        remove_path(&self.info_path())?;
+        remove_path(&self.genesis_path())
+    }
+
+    /// Checks the existing devnet was generated with the same number of
+    /// nodes.
+    fn check_info(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.info_path();
+        let info = fs::read_to_string(&path).map_err(|_| {
+            format!("file {path:?} not found, use --force to regenerate it")
+        })?;
+        let info: DevnetInfo = toml::from_str(&info)?;
+
+        if info.nodes != self.nodes {
+            Err(format!(
+                "The devnet at {} has {} nodes, use --force to regenerate it \
+                 with {}",
+                self.dir.display(),
+                info.nodes,
+                self.nodes
+            ))?
+        }
+
+        Ok(())
+    }
+
+    /// Generates the provisioner keys, the genesis state and the
+    /// configuration of every node.
+    fn generate(
+        &self,
+        theme: &Theme,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        info!(
+            "{} devnet of {} nodes at {}",
+            theme.action("Generating"),
+            self.nodes,
+            self.dir.display()
+        );
+
+        let snapshot: Snapshot = match &self.init {
+            Some(path) => toml::from_str(
+                &fs::read_to_string(path)
+                    .map_err(|_| format!("file {path:?} not found"))?,
+            )?,
+            None => Snapshot::default(),
+        };
+
+        let mut rng = StdRng::from_entropy();
+        let mut stakes = vec![];
+        for index in 0..self.nodes {
+            let node_dir = self.node_dir(index);
+            fs::create_dir_all(&node_dir)?;
+
+            let sk = SecretKey::random(&mut rng);
+            let pk = BlsPublicKey::from(&sk);
+            node_data::bls::save_keys(
+                &node_dir.join("consensus.keys"),
+                KEYS_PASSWORD,
+                &sk,
+                &pk,
+            )?;
+            stakes.push(GenesisStake::new(pk, MINIMUM_STAKE));
+
+            self.write_config(index)?;
+        }
+
+        // The snapshot is written along the state, so that the genesis of
+        // the devnet can be inspected and rebuilt
+        let snapshot = snapshot.with_stakes(stakes);
+        let info = DevnetInfo { nodes: self.nodes };
+        fs::write(self.info_path(), toml::to_string(&info)?)?;
+        fs::write(self.genesis_path(), toml::to_string(&snapshot)?)?;
+        info!("{} {} provisioners", theme.success("Staked"), self.nodes);
+
+        let state_dir = self.dir.join("state");
+        fs::create_dir_all(&state_dir)?;
+        let (_, commit_id) = deploy(&state_dir, &snapshot)?;
+        info!(
+            "{} {}",
+            theme.action("Genesis Root"),
+            hex::encode(commit_id)
+        );
+
+        // Every node runs its own VM, thus needs its own copy of the state
+        for index in 0..self.nodes {
+            copy_dir(&state_dir, &self.node_dir(index).join("state"))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_config(
+        &self,
+        index: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let node_dir = self.node_dir(index);
+        let bootstrapping_nodes = (0..self.nodes.min(BOOTSTRAPPING_NODES))
+            .filter(|i| *i != index)
+            .map(|i| self.kadcast_address(i).map(|a| a.to_string()))
+            .collect::<Result<_, _>>()?;
+
+        let config = NodeConfig {
+            log_type: "plain",
+            chain: ChainSection {
+                db_path: node_dir.join("chain.db"),
+                consensus_keys_path: node_dir.join("consensus.keys"),
+            },
+            http: HttpSection {
+                listen_address: self.http_address(index)?.to_string(),
+            },
+            kadcast: kadcast::config::Config {
+                public_address: self.kadcast_address(index)?.to_string(),
+                bootstrapping_nodes,
+                ..Default::default()
+            },
+        };
+
+        fs::write(node_dir.join("rusk.toml"), toml::to_string(&config)?)?;
+        Ok(())
+    }
+
+    /// Launches every node as a child process, logging to a shared
+    /// directory, and waits for all of them to exit.
+    fn launch(&self, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+        let log_dir = self.dir.join("logs");
+        fs::create_dir_all(&log_dir)?;
+
+        let exe = env::current_exe()?;
+        let mut children: Vec<Child> = vec![];
+        for index in 0..self.nodes {
+            let node_dir = self.node_dir(index);
+            let log_path = log_dir.join(format!("node-{index}.log"));
+            let log = File::create(&log_path)?;
+
+            let child = std::process::Command::new(&exe)
+                .arg("--config")
+                .arg(node_dir.join("rusk.toml"))
+                .env("RUSK_STATE_PATH", node_dir.join("state"))
+                .env("DUSK_CONSENSUS_KEYS_PASS", KEYS_PASSWORD)
+                .stdout(Stdio::from(log.try_clone()?))
+                .stderr(Stdio::from(log))
+                .spawn();
+
+            let child = match child {
+                Ok(child) => child,
+                Err(e) => {
+                    children.iter_mut().for_each(|c| {
+                        let _ = c.kill();
+                    });
+                    return Err(e.into());
+                }
+            };
+
+            info!(
+                "{} node {index} at {} (http {}), logging to {}",
+                theme.success("Launched"),
+                self.kadcast_address(index)?,
+                self.http_address(index)?,
+                log_path.display()
+            );
+            children.push(child);
+        }
+
+        for (index, child) in children.iter_mut().enumerate() {
+            let status = child.wait()?;
+            info!("{} node {index} with {status}", theme.info("Exited"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the local address of the node with the given index, listening on
+/// the given base port offset by the index.
+fn local_address(base_port: u16, index: usize) -> Result<SocketAddr, String> {
+    u16::try_from(index)
+        .ok()
+        .and_then(|index| base_port.checked_add(index))
+        .map(|port| SocketAddr::from(([127, 0, 0, 1], port)))
+        .ok_or_else(|| format!("Port {base_port} + {index} is out of range"))
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dst = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dst)?;
+        } else {
+            fs::copy(entry.path(), dst)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    let removed = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+
+    removed.or_else(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            Ok(())
+        } else {
+            Err(e)
+        }
+    })
+}
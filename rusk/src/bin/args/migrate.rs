@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::path::PathBuf;
+
+use node::database::rocksdb::migration::{Migrator, SCHEMA_VERSION};
+use rusk_recovery_tools::Theme;
+use tracing::info;
+
+pub fn migrate_db(
+    db_path: PathBuf,
+    dry_run: bool,
+    no_backup: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = Theme::default();
+
+    let migrator = Migrator::open(db_path)?;
+    let version = migrator.version()?;
+    info!(
+        "{} schema version {version}, supported {SCHEMA_VERSION}",
+        theme.info("Found"),
+    );
+
+    let pending = migrator.pending()?;
+    if pending.is_empty() {
+        info!("{} database is up to date", theme.success("Chain"));
+        return Ok(());
+    }
+
+    if !dry_run && !no_backup {
+        let backup = migrator.backup()?;
+        info!(
+            "{} database to {}",
+            theme.action("Backed up"),
+            backup.display()
+        );
+    }
+
+    for report in migrator.migrate(dry_run)? {
+        let action = match dry_run {
+            true => theme.info("Would migrate"),
+            false => theme.success("Migrated"),
+        };
+        info!(
+            "{action} v{} to v{}: {} ({} records)",
+            report.from, report.to, report.description, report.records
+        );
+    }
+
+    Ok(())
+}
@@ -14,6 +14,10 @@ use crate::args::Args;
 pub(crate) struct ChainConfig {
     db_path: Option<PathBuf>,
     consensus_keys_path: Option<PathBuf>,
+    /// Whether to sync the state from a snapshot served by peers when
+    /// starting from genesis
+    #[serde(default)]
+    fast_sync: bool,
 }
 
 impl ChainConfig {
@@ -38,6 +42,10 @@ impl ChainConfig {
         })
     }
 
+    pub(crate) fn fast_sync(&self) -> bool {
+        self.fast_sync
+    }
+
     pub(crate) fn consensus_keys_path(&self) -> String {
         self.consensus_keys_path
             .clone()
@@ -4,19 +4,46 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use rusk::http::{
+    CompressionConfig, CorsConfig, HttpLimits, HttpOptions, KeepAliveConfig,
+    TlsConfig,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::args::Args;
 
+/// Default gas limit of contract queries, equal to the default block gas
+/// limit.
+const DEFAULT_QUERY_GAS_LIMIT: u64 = 5_000_000_000;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct HttpConfig {
     pub cert: Option<PathBuf>,
     pub key: Option<PathBuf>,
+    pub client_ca: Option<PathBuf>,
+    #[serde(default)]
+    pub client_cert_targets: HashSet<String>,
+    #[serde(default, with = "humantime_serde")]
+    pub tls_reload_interval: Option<Duration>,
     #[serde(default = "default_listen")]
     pub listen: bool,
     listen_address: Option<String>,
+    #[serde(default)]
+    pub limits: HttpLimits,
+    #[serde(default)]
+    pub cors: CorsConfig,
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    #[serde(default)]
+    pub keepalive: KeepAliveConfig,
+    #[serde(default = "default_query_gas_limit")]
+    pub query_gas_limit: u64,
+    #[serde(default = "default_metrics")]
+    pub metrics: bool,
 }
 
 impl Default for HttpConfig {
@@ -26,6 +53,15 @@ impl Default for HttpConfig {
             listen_address: None,
             cert: None,
             key: None,
+            client_ca: None,
+            client_cert_targets: HashSet::new(),
+            tls_reload_interval: None,
+            limits: HttpLimits::default(),
+            cors: CorsConfig::default(),
+            compression: CompressionConfig::default(),
+            keepalive: KeepAliveConfig::default(),
+            query_gas_limit: default_query_gas_limit(),
+            metrics: default_metrics(),
         }
     }
 }
@@ -34,6 +70,14 @@ const fn default_listen() -> bool {
     true
 }
 
+const fn default_query_gas_limit() -> u64 {
+    DEFAULT_QUERY_GAS_LIMIT
+}
+
+const fn default_metrics() -> bool {
+    false
+}
+
 impl HttpConfig {
     pub fn listen_addr(&self) -> String {
         self.listen_address
@@ -41,6 +85,27 @@ impl HttpConfig {
             .unwrap_or("127.0.0.1:8080".into())
     }
 
+    pub fn tls(&self) -> Option<TlsConfig> {
+        let (cert, key) = self.cert.as_ref().zip(self.key.as_ref())?;
+
+        Some(TlsConfig {
+            client_ca: self.client_ca.clone(),
+            client_cert_targets: self.client_cert_targets.clone(),
+            reload_interval: self.tls_reload_interval,
+            ..TlsConfig::new(cert, key)
+        })
+    }
+
+    pub fn options(&self) -> HttpOptions {
+        HttpOptions {
+            limits: self.limits.clone(),
+            cors: self.cors.clone(),
+            compression: self.compression.clone(),
+            keepalive: self.keepalive.clone(),
+            metrics: self.metrics,
+        }
+    }
+
     pub(crate) fn merge(&mut self, args: &Args) {
         // Overwrite config ws-listen-addr
         if let Some(http_listen_addr) = &args.http_listen_addr {
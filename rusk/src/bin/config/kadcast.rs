@@ -10,27 +10,38 @@ use serde::{Deserialize, Serialize};
 use crate::args::Args;
 
 #[derive(Serialize, Deserialize, Clone, Default)]
-pub(crate) struct KadcastConfig(Config);
+pub(crate) struct KadcastConfig {
+    #[serde(flatten)]
+    config: Config,
+
+    /// Size in bytes above which outgoing messages are compressed
+    #[serde(default)]
+    compression_threshold: Option<usize>,
+}
 
 impl From<KadcastConfig> for Config {
     fn from(conf: KadcastConfig) -> Self {
-        conf.0
+        conf.config
     }
 }
 
 impl KadcastConfig {
+    pub(crate) fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
     pub(crate) fn merge(&mut self, arg: &Args) {
         if let Some(public_address) = &arg.kadcast_public_address {
-            self.0.public_address = public_address.into();
+            self.config.public_address = public_address.into();
         };
         if let Some(listen_address) = &arg.kadcast_listen_address {
-            self.0.listen_address = Some(listen_address.into());
+            self.config.listen_address = Some(listen_address.into());
         };
         if let Some(bootstrapping_nodes) = arg.kadcast_bootstrap.clone() {
-            self.0.bootstrapping_nodes = bootstrapping_nodes
+            self.config.bootstrapping_nodes = bootstrapping_nodes
         };
         if let Some(network_id) = arg.kadcast_network_id {
-            self.0.kadcast_id = Some(network_id)
+            self.config.kadcast_id = Some(network_id)
         };
     }
 }
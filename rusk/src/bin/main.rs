@@ -23,11 +23,15 @@ use node::{
     LongLivedService, Node,
 };
 #[cfg(feature = "node")]
+use node_data::message::payload::Capabilities;
+#[cfg(feature = "node")]
 use rusk::chain::Rusk;
 use rusk::http::DataSources;
 use rusk::Result;
 
 use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload};
 
 use rusk::http::HttpServer;
 use tracing::info;
@@ -45,44 +49,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let log = config.log_level();
     let log_filter = config.log_filter();
 
-    // Generate a subscriber with the desired default log level and optional log
+    // Generate a filter with the desired default log level and optional log
     // filter.
-    let subscriber = tracing_subscriber::fmt::Subscriber::builder()
-        .with_env_filter(EnvFilter::new(log_filter).add_directive(log.into()));
+    let filter = EnvFilter::new(log_filter).add_directive(log.into());
 
     #[cfg(any(feature = "recovery-state", feature = "recovery-keys"))]
     // Set custom tracing format if subcommand is specified
     if let Some(command) = args.command {
-        let subscriber = subscriber
+        let subscriber = tracing_subscriber::fmt::Subscriber::builder()
+            .with_env_filter(filter)
             .with_level(false)
             .without_time()
             .with_target(false)
             .finish();
         tracing::subscriber::set_global_default(subscriber)?;
-        command.run()?;
+        command.run(&config)?;
         return Ok(());
     }
 
+    // The filter may be changed at runtime through the admin target
+    let (filter, log_filter) = reload::Layer::new(filter);
+    let subscriber = tracing_subscriber::registry().with(filter);
+
     // Set the subscriber as global.
     // so this subscriber will be used as the default in all threads for the
     // remainder of the duration of the program, similar to how `loggers`
     // work in the `log` crate.
     match config.log_type().as_str() {
         "json" => {
-            let subscriber = subscriber
-                .json()
-                .with_current_span(false)
-                .flatten_event(true)
-                .finish();
+            let subscriber = subscriber.with(
+                fmt::layer()
+                    .json()
+                    .with_current_span(false)
+                    .flatten_event(true),
+            );
 
             tracing::subscriber::set_global_default(subscriber)?;
         }
         "plain" => {
-            let subscriber = subscriber.with_ansi(false).finish();
+            let subscriber = subscriber.with(fmt::layer().with_ansi(false));
             tracing::subscriber::set_global_default(subscriber)?;
         }
         "coloured" => {
-            let subscriber = subscriber.finish();
+            let subscriber = subscriber.with(fmt::layer());
             tracing::subscriber::set_global_default(subscriber)?;
         }
         _ => unreachable!(),
@@ -98,7 +107,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (rusk, node, mut service_list) = {
         let state_dir = rusk_profile::get_rusk_state_dir()?;
         info!("Using state from {state_dir:?}");
-        let rusk = Rusk::new(state_dir)?;
+        let rusk = Rusk::new(state_dir)?
+            .with_query_gas_limit(config.http.query_gas_limit);
 
         info!("Rusk VM loaded");
 
@@ -108,10 +118,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         type Services =
             dyn LongLivedService<Kadcast<255>, rocksdb::Backend, Rusk>;
 
+        let chain = ChainSrv::new(config.chain.consensus_keys_path())
+            .with_fast_sync(config.chain.fast_sync());
+        let admin = chain.admin();
+
         // Select list of services to enable
         let service_list: Vec<Box<Services>> = vec![
             Box::<MempoolSrv>::default(),
-            Box::new(ChainSrv::new(config.chain.consensus_keys_path())),
+            Box::new(chain),
             Box::new(DataBrokerSrv::new(config.clone().databroker.into())),
         ];
 
@@ -125,9 +139,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let db_path = config.chain.db_path();
 
         let db = rocksdb::Backend::create_or_open(db_path);
-        let net = Kadcast::new(config.clone().kadcast.into())?;
+        // The data broker serves state snapshots to fast syncing peers
+        let net = Kadcast::new(config.clone().kadcast.into())?
+            .with_capabilities(Capabilities::FAST_SYNC)
+            .with_compression(config.kadcast.compression_threshold());
 
-        let node = rusk::chain::RuskNode(Node::new(net, db, rusk.clone()));
+        let node =
+            rusk::chain::RuskNode(Node::new(net, db, rusk.clone()), admin);
         (rusk, node, service_list)
     };
     let mut _ws_server = None;
@@ -141,17 +159,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             rusk,
             #[cfg(feature = "prover")]
             prover: rusk_prover::LocalProver,
+            log_filter: Some(log_filter),
         };
 
         let listen_addr = config.http.listen_addr();
+        let options = config.http.options();
 
-        let cert_and_key = match (config.http.cert, config.http.key) {
-            (Some(cert), Some(key)) => Some((cert, key)),
-            _ => None,
-        };
+        let tls = config.http.tls();
 
         _ws_server =
-            Some(HttpServer::bind(handler, listen_addr, cert_and_key).await?);
+            Some(HttpServer::bind(handler, listen_addr, tls, options).await?);
     }
 
     #[cfg(feature = "node")]
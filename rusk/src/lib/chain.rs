@@ -12,16 +12,24 @@ use std::sync::Arc;
 
 use parking_lot::Mutex;
 
+use node::chain::ChainAdmin;
 use node::database::rocksdb::Backend;
 use node::network::Kadcast;
+use node::Network;
 use rusk_abi::dusk::{dusk, Dusk};
 use rusk_abi::VM;
 
 pub const MINIMUM_STAKE: Dusk = dusk(1000.0);
 
+/// Amount hard slashed from the stake of a provisioner equivocating in
+/// consensus, taken from its unbonding funds if the stake is not enough.
+pub const EQUIVOCATION_SLASH: Dusk = MINIMUM_STAKE;
+
 pub struct RuskInner {
     pub current_commit: [u8; 32],
     pub base_commit: [u8; 32],
+    /// Commits kept when finalizing, such as the ones of epoch checkpoints
+    pub retained_commits: Vec<[u8; 32]>,
     pub vm: VM,
 }
 
@@ -29,18 +37,35 @@ pub struct RuskInner {
 pub struct Rusk {
     inner: Arc<Mutex<RuskInner>>,
     dir: PathBuf,
+    query_gas_limit: u64,
 }
 
-#[derive(Clone)]
-pub struct RuskNode(pub node::Node<Kadcast<255>, Backend, Rusk>);
+/// A node running on the Rusk VM.
+///
+/// The network defaults to Kadcast, while tests and single-process devnets
+/// can connect several nodes through a [`node::network::LocalHub`].
+pub struct RuskNode<N: Network = Kadcast<255>>(
+    pub node::Node<N, Backend, Rusk>,
+    pub ChainAdmin,
+);
+
+impl<N: Network> Clone for RuskNode<N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone())
+    }
+}
 
-impl RuskNode {
+impl<N: Network> RuskNode<N> {
     pub fn db(&self) -> Arc<tokio::sync::RwLock<Backend>> {
         self.0.database() as Arc<tokio::sync::RwLock<Backend>>
     }
 
-    pub fn network(&self) -> Arc<tokio::sync::RwLock<Kadcast<255>>> {
-        self.0.network() as Arc<tokio::sync::RwLock<Kadcast<255>>>
+    pub fn network(&self) -> Arc<tokio::sync::RwLock<N>> {
+        self.0.network()
+    }
+
+    pub fn admin(&self) -> &ChainAdmin {
+        &self.1
     }
 }
 
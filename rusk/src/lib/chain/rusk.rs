@@ -10,22 +10,37 @@ use std::{fs, io};
 
 use parking_lot::{Mutex, MutexGuard};
 use sha3::{Digest, Sha3_256};
+use tracing::warn;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bls12_381_sign::PublicKey as BlsPublicKey;
-use dusk_bytes::DeserializableSlice;
+use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_consensus::operations::VerificationOutput;
-use node_data::ledger::{SpentTransaction, Transaction};
+use dusk_pki::{PublicKey, PublicSpendKey};
+use node::metrics::metrics;
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::{SpentTransaction, StakeEvent, Transaction};
 use phoenix_core::transaction::StakeData;
 use phoenix_core::Transaction as PhoenixTransaction;
+use rkyv::ser::Serializer;
+use rkyv::{Archive, Fallible, Serialize};
 use rusk_abi::dusk::Dusk;
+use rusk_abi::gas;
 use rusk_abi::{
-    CallReceipt, ContractError, Error as PiecrustError, Event, Session,
-    STAKE_CONTRACT, TRANSFER_CONTRACT,
+    CallReceipt, ContractData, ContractError, ContractId,
+    Error as PiecrustError, Event, Session, STAKE_CONTRACT, TRANSFER_CONTRACT,
+    VM,
 };
 use rusk_profile::to_rusk_state_id_path;
+use stake_contract_types::StakingEvent;
+use transfer_contract_types::{
+    Deploy, ModuleId, PendingUpgrade, DEPLOY_FN_NAME, DEPLOY_OWNER_SIZE,
+    GAS_PER_DEPLOY_BYTE, MIGRATION_GAS_LIMIT, UPGRADE_FN_NAME,
+};
 
-use super::{coinbase_value, emission_amount, Rusk, RuskInner};
+use super::{
+    coinbase_value, emission_amount, Rusk, RuskInner, EQUIVOCATION_SLASH,
+};
 use crate::{Error, Result};
 
 pub static DUSK_KEY: LazyLock<BlsPublicKey> = LazyLock::new(|| {
@@ -58,15 +73,28 @@ impl Rusk {
         let inner = Arc::new(Mutex::new(RuskInner {
             current_commit: base_commit,
             base_commit,
+            retained_commits: vec![],
             vm,
         }));
 
         Ok(Self {
             inner,
             dir: dir.into(),
+            query_gas_limit: u64::MAX,
         })
     }
 
+    /// Sets the gas limit of the queries made with [`query_raw`], and of the
+    /// host calls of those made with [`feeder_query_raw`], which are
+    /// otherwise unlimited.
+    ///
+    /// [`query_raw`]: Rusk::query_raw
+    /// [`feeder_query_raw`]: Rusk::feeder_query_raw
+    pub fn with_query_gas_limit(mut self, limit: u64) -> Self {
+        self.query_gas_limit = limit;
+        self
+    }
+
     pub fn execute_transactions<I: Iterator<Item = Transaction>>(
         &self,
         block_height: u64,
@@ -74,13 +102,14 @@ impl Rusk {
         generator: &BlsPublicKey,
         txs: I,
         missed_generators: &[BlsPublicKey],
+        equivocators: &[BlsPublicKey],
     ) -> Result<(Vec<SpentTransaction>, Vec<Transaction>, VerificationOutput)>
     {
         let inner = self.inner.lock();
 
         let current_commit = inner.current_commit;
         let mut session =
-            rusk_abi::new_session(&inner.vm, current_commit, block_height)?;
+            block_session(&inner.vm, current_commit, block_height)?;
 
         let mut block_gas_left = block_gas_limit;
 
@@ -101,7 +130,7 @@ impl Rusk {
                     // re-execute all spent transactions. We don't discard the
                     // transaction, since it is technically valid.
                     if gas_spent > block_gas_left {
-                        session = rusk_abi::new_session(
+                        session = block_session(
                             &inner.vm,
                             current_commit,
                             block_height,
@@ -147,6 +176,7 @@ impl Rusk {
             dusk_spent,
             generator,
             missed_generators,
+            equivocators,
         )?;
 
         let state_root = session.root();
@@ -170,21 +200,25 @@ impl Rusk {
         generator: &BlsPublicKey,
         txs: &[Transaction],
         missed_generators: &[BlsPublicKey],
+        equivocators: &[BlsPublicKey],
     ) -> Result<(Vec<SpentTransaction>, VerificationOutput)> {
         let inner = self.inner.lock();
 
         let current_commit = inner.current_commit;
         let mut session =
-            rusk_abi::new_session(&inner.vm, current_commit, block_height)?;
+            block_session(&inner.vm, current_commit, block_height)?;
 
-        accept(
+        let (spent_txs, verification_output, _) = accept(
             &mut session,
             block_height,
             block_gas_limit,
             generator,
             txs,
             missed_generators,
-        )
+            equivocators,
+        )?;
+
+        Ok((spent_txs, verification_output))
     }
 
     /// Accept the given transactions.
@@ -192,6 +226,7 @@ impl Rusk {
     ///   * `consistency_check` - represents a state_root, the caller expects to
     ///   be returned on successful transactions execution. Passing a None
     ///   value disables the check.
+    #[allow(clippy::too_many_arguments)]
     pub fn accept_transactions(
         &self,
         block_height: u64,
@@ -200,20 +235,23 @@ impl Rusk {
         txs: Vec<Transaction>,
         consistency_check: Option<VerificationOutput>,
         missed_generators: &[BlsPublicKey],
-    ) -> Result<(Vec<SpentTransaction>, VerificationOutput)> {
+        equivocators: &[BlsPublicKey],
+    ) -> Result<(Vec<SpentTransaction>, VerificationOutput, Vec<StakeEvent>)>
+    {
         let mut inner = self.inner.lock();
 
         let current_commit = inner.current_commit;
         let mut session =
-            rusk_abi::new_session(&inner.vm, current_commit, block_height)?;
+            block_session(&inner.vm, current_commit, block_height)?;
 
-        let (spent_txs, verification_output) = accept(
+        let (spent_txs, verification_output, stake_events) = accept(
             &mut session,
             block_height,
             block_gas_limit,
             &generator,
             &txs[..],
             missed_generators,
+            equivocators,
         )?;
 
         if let Some(expected_verification) = consistency_check {
@@ -226,8 +264,9 @@ impl Rusk {
 
         let commit_id = session.commit()?;
         inner.current_commit = commit_id;
+        metrics().state_commits.inc();
 
-        Ok((spent_txs, verification_output))
+        Ok((spent_txs, verification_output, stake_events))
     }
 
     /// Finalize the given transactions.
@@ -235,6 +274,7 @@ impl Rusk {
     /// * `consistency_check` - represents a state_root, the caller expects to
     ///   be returned on successful transactions execution. Passing None value
     ///   disables the check.
+    #[allow(clippy::too_many_arguments)]
     pub fn finalize_transactions(
         &self,
         block_height: u64,
@@ -243,20 +283,23 @@ impl Rusk {
         txs: Vec<Transaction>,
         consistency_check: Option<VerificationOutput>,
         missed_generators: &[BlsPublicKey],
-    ) -> Result<(Vec<SpentTransaction>, VerificationOutput)> {
+        equivocators: &[BlsPublicKey],
+    ) -> Result<(Vec<SpentTransaction>, VerificationOutput, Vec<StakeEvent>)>
+    {
         let mut inner = self.inner.lock();
 
         let current_commit = inner.current_commit;
         let mut session =
-            rusk_abi::new_session(&inner.vm, current_commit, block_height)?;
+            block_session(&inner.vm, current_commit, block_height)?;
 
-        let (spent_txs, verification_output) = accept(
+        let (spent_txs, verification_output, stake_events) = accept(
             &mut session,
             block_height,
             block_gas_limit,
             &generator,
             &txs[..],
             missed_generators,
+            equivocators,
         )?;
 
         if let Some(expected_verification) = consistency_check {
@@ -269,14 +312,16 @@ impl Rusk {
 
         let commit_id = session.commit()?;
         inner.current_commit = commit_id;
+        metrics().state_commits.inc();
 
-        // Delete all commits except the previous base commit, and the current
-        // commit
+        // Delete all commits except the previous base commit, the current
+        // commit and the retained ones
         let mut delete_commits = inner.vm.commits();
         delete_commits.retain(|c| {
             c != &inner.current_commit
                 && c != &inner.base_commit
                 && c != &current_commit
+                && !inner.retained_commits.contains(c)
         });
         for commit in delete_commits {
             inner.vm.delete_commit(commit)?;
@@ -287,7 +332,7 @@ impl Rusk {
 
         inner.base_commit = commit_id;
 
-        Ok((spent_txs, verification_output))
+        Ok((spent_txs, verification_output, stake_events))
     }
 
     pub fn revert(&self, state_hash: [u8; 32]) -> Result<[u8; 32]> {
@@ -306,6 +351,98 @@ impl Rusk {
         self.revert(self.base_root())
     }
 
+    /// Keeps the given commits from being deleted on finalization.
+    pub fn retain_commits(&self, commits: Vec<[u8; 32]>) {
+        self.inner.lock().retained_commits = commits;
+    }
+
+    /// Archives the commit with the given state root.
+    ///
+    /// The compiled contract modules are left out of the archive, since they
+    /// are native code: the importing node compiles them from the bytecode.
+    pub fn state_snapshot(&self, state_root: [u8; 32]) -> Result<Vec<u8>> {
+        let commit_dir = {
+            let inner = self.inner.lock();
+            if !inner.vm.commits().contains(&state_root) {
+                return Err(Error::CommitNotFound(state_root));
+            }
+            inner.vm.root_dir().join(hex::encode(state_root))
+        };
+
+        let mut archive = tar::Builder::new(Vec::new());
+        append_snapshot_files(&mut archive, &commit_dir, Path::new(""))?;
+        Ok(archive.into_inner()?)
+    }
+
+    /// Imports a commit archived with [`state_snapshot`], reloading the VM
+    /// for the commit to be available.
+    ///
+    /// The archive is first unpacked in a staging directory, discarding any
+    /// compiled module it carries. Every memory page of its contracts must be
+    /// proven to be part of the given state root, and the code and owner of
+    /// every contract must match a trusted source: the ones recorded by the
+    /// transfer contract for contracts deployed or upgraded by transactions,
+    /// or the ones in the base commit for the others. Only then is the commit
+    /// moved into the state directory.
+    ///
+    /// [`state_snapshot`]: Rusk::state_snapshot
+    pub fn import_snapshot(
+        &self,
+        state_root: [u8; 32],
+        snapshot: &[u8],
+    ) -> Result<()> {
+        let mut inner = self.inner.lock();
+        if inner.vm.commits().contains(&state_root) {
+            return Ok(());
+        }
+
+        let root_dir = inner.vm.root_dir().to_path_buf();
+        let staging_dir = root_dir.with_extension("import");
+        let commit_name = hex::encode(state_root);
+        let commit_dir = root_dir.join(&commit_name);
+        let base_dir = root_dir
+            .join(hex::encode(inner.base_commit))
+            .join(BYTECODE_DIR);
+
+        let _ = fs::remove_dir_all(&staging_dir);
+
+        // The VM only loads the commits at creation
+        let staged_dir = staging_dir.join(&commit_name);
+        let vm = unpack_snapshot(snapshot, &staged_dir)
+            .and_then(|_| verify_snapshot(&staging_dir, state_root, &base_dir))
+            .and_then(|_| Ok(fs::rename(&staged_dir, &commit_dir)?))
+            .and_then(|_| Ok(rusk_abi::new_vm(&root_dir)?));
+
+        let _ = fs::remove_dir_all(&staging_dir);
+
+        match vm {
+            Ok(vm) => {
+                inner.vm = vm;
+                Ok(())
+            }
+            Err(err) => {
+                let _ = fs::remove_dir_all(commit_dir);
+                Err(err)
+            }
+        }
+    }
+
+    /// Sets an imported commit as the base commit.
+    pub fn install_snapshot(&self, state_root: [u8; 32]) -> Result<()> {
+        let mut inner = self.inner.lock();
+        if !inner.vm.commits().contains(&state_root) {
+            return Err(Error::CommitNotFound(state_root));
+        }
+
+        let commit_id_path = to_rusk_state_id_path(&self.dir);
+        fs::write(commit_id_path, state_root)?;
+
+        inner.current_commit = state_root;
+        inner.base_commit = state_root;
+
+        Ok(())
+    }
+
     /// Perform an action with the underlying data structure.
     pub fn with_inner<'a, F, T>(&'a self, closure: F) -> T
     where
@@ -361,19 +498,22 @@ fn accept(
     generator: &BlsPublicKey,
     txs: &[Transaction],
     missed_generators: &[BlsPublicKey],
-) -> Result<(Vec<SpentTransaction>, VerificationOutput)> {
+    equivocators: &[BlsPublicKey],
+) -> Result<(Vec<SpentTransaction>, VerificationOutput, Vec<StakeEvent>)> {
     let mut block_gas_left = block_gas_limit;
 
     let mut spent_txs = Vec::with_capacity(txs.len());
     let mut dusk_spent = 0;
 
     let mut event_hasher = Sha3_256::new();
+    let mut stake_events = vec![];
 
     for unspent_tx in txs {
         let tx = &unspent_tx.inner;
         let receipt = execute(session, tx)?;
 
         for event in receipt.events {
+            stake_events.extend(to_stake_event(&event));
             update_hasher(&mut event_hasher, event);
         }
         let gas_spent = receipt.gas_spent;
@@ -392,13 +532,15 @@ fn accept(
         });
     }
 
-    reward_slash_and_update_root(
+    let events = reward_slash_and_update_root(
         session,
         block_height,
         dusk_spent,
         generator,
         missed_generators,
+        equivocators,
     )?;
+    stake_events.extend(events.iter().filter_map(to_stake_event));
 
     let state_root = session.root();
     let event_hash = event_hasher.finalize().into();
@@ -409,6 +551,7 @@ fn accept(
             state_root,
             event_hash,
         },
+        stake_events,
     ))
 }
 
@@ -418,9 +561,19 @@ fn accept(
 /// 1. Call the "spend_and_execute" function on the transfer contract with
 ///    unlimited gas. If this fails, an error is returned. If an error is
 ///    returned the transaction should be considered unspendable/invalid, but no
-///    re-execution of previous transactions is required.
+///    re-execution of previous transactions is required. The gas charged for
+///    the host functions called during it is added to the gas spent.
+///
+/// 2. If the transaction is a deployment, deploy the contract it carries,
+///    charging gas for each byte of bytecode. The id of the new contract is
+///    returned as the data of the receipt.
 ///
-/// 2. Call the "refund" function on the transfer contract with unlimited gas.
+/// 3. If the transaction schedules an upgrade, charge gas for each byte of it
+///    the same way as deployments. The transfer contract refuses to schedule
+///    upgrades whose charge exceeds the gas left, so that they run out of gas
+///    before being applied.
+///
+/// 4. Call the "refund" function on the transfer contract with unlimited gas.
 ///    The amount charged depends on the gas spent by the transaction, and the
 ///    optional contract call in step 1.
 fn execute(
@@ -429,12 +582,36 @@ fn execute(
 ) -> Result<CallReceipt<Result<Vec<u8>, ContractError>>, PiecrustError> {
     // Spend the inputs and execute the call. If this errors the transaction is
     // unspendable.
-    let mut receipt = session.call::<_, Result<Vec<u8>, ContractError>>(
-        TRANSFER_CONTRACT,
-        "spend_and_execute",
-        tx,
-        tx.fee.gas_limit,
-    )?;
+    let (receipt, host_gas) = gas::metered(tx.fee.gas_limit, || {
+        session.call::<_, Result<Vec<u8>, ContractError>>(
+            TRANSFER_CONTRACT,
+            "spend_and_execute",
+            tx,
+            tx.fee.gas_limit,
+        )
+    });
+    let mut receipt = receipt?;
+    charge_host_gas(&mut receipt, host_gas);
+
+    if receipt.data.is_ok() {
+        if let Some(deploy) = deploy_data(tx) {
+            deploy_contract(session, deploy, &mut receipt);
+        }
+    }
+
+    // Upgrades carry bytecode as well, and are charged for it the same way as
+    // deployments.
+    if receipt.data.is_ok() {
+        if let Some(bytes) = upgrade_data_len(tx) {
+            let gas_left = receipt.gas_limit.saturating_sub(receipt.gas_spent);
+            let bytecode_gas = GAS_PER_DEPLOY_BYTE.saturating_mul(bytes as u64);
+
+            match bytecode_gas > gas_left {
+                true => receipt.data = Err(ContractError::OutOfGas),
+                false => receipt.gas_spent += bytecode_gas,
+            }
+        }
+    }
 
     // Ensure all gas is consumed if there's an error in the contract call
     if receipt.data.is_err() {
@@ -458,46 +635,787 @@ fn execute(
     Ok(receipt)
 }
 
+/// Returns the deployment carried by the transaction, if its call targets the
+/// deploy function of the transfer contract. Malformed deployments are
+/// returned as errors so they can be reported in the receipt.
+fn deploy_data(
+    tx: &PhoenixTransaction,
+) -> Option<Result<Deploy, ContractError>> {
+    let (contract, fn_name, fn_args) = tx.call.as_ref()?;
+
+    if *contract != TRANSFER_CONTRACT.to_bytes() || fn_name != DEPLOY_FN_NAME {
+        return None;
+    }
+
+    Some(
+        rkyv::from_bytes::<Deploy>(fn_args).map_err(|_| {
+            ContractError::Panic(String::from("Invalid deploy data"))
+        }),
+    )
+}
+
+/// Deploys the contract carried by a transaction, recording the outcome in the
+/// given receipt.
+///
+/// The deployment must be signed by its owner, and the id of the contract is
+/// derived from the same bytes they sign: the bytecode, the owner, the
+/// constructor arguments, and the nonce. The gas charged for the bytecode is
+/// deducted from the gas left to the transaction before the contract is
+/// initialized.
+///
+/// The constructor runs with the rest of the gas left. The VM runs it as part
+/// of the deployment and only reports whether it succeeded, not the gas it
+/// spent, so all the gas it is given is charged when it runs. This is
+/// intended: charging less would let constructors do unpaid work, so the
+/// sender is expected to set the gas limit of a deployment with constructor
+/// arguments close to what it needs.
+fn deploy_contract(
+    session: &mut Session,
+    deploy: Result<Deploy, ContractError>,
+    receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
+) {
+    let deploy = match deploy {
+        Ok(deploy) => deploy,
+        Err(err) => {
+            receipt.data = Err(err);
+            return;
+        }
+    };
+
+    if let Err(err) = verify_deploy_signature(&deploy) {
+        receipt.data = Err(err);
+        return;
+    }
+
+    let gas_left = receipt.gas_limit.saturating_sub(receipt.gas_spent);
+    let bytecode_gas =
+        GAS_PER_DEPLOY_BYTE.saturating_mul(deploy.bytecode.len() as u64);
+
+    if bytecode_gas > gas_left {
+        receipt.data = Err(ContractError::OutOfGas);
+        return;
+    }
+    receipt.gas_spent += bytecode_gas;
+
+    let gas_limit = gas_left - bytecode_gas;
+    let contract_id = rusk_abi::gen_contract_id(&deploy.id_bytes());
+    let data = ContractData::builder(deploy.owner).contract_id(contract_id);
+
+    let (result, host_gas) =
+        gas::metered(gas_limit, || match &deploy.init_args {
+            Some(args) => session.deploy(
+                &deploy.bytecode,
+                data.constructor_arg(&RawArg(args)),
+                gas_limit,
+            ),
+            None => session.deploy(&deploy.bytecode, data, gas_limit),
+        });
+
+    if deploy.init_args.is_some() {
+        receipt.gas_spent += gas_limit;
+    }
+    charge_host_gas(receipt, host_gas);
+    if receipt.data.is_err() {
+        return;
+    }
+
+    receipt.data = match result {
+        Ok(id) => {
+            let code_hash = rusk_abi::hash(deploy.bytecode);
+            session
+                .call::<_, ()>(
+                    TRANSFER_CONTRACT,
+                    "set_deployed",
+                    &(id.to_bytes(), code_hash, deploy.owner),
+                    u64::MAX,
+                )
+                .map(|_| id.to_bytes().to_vec())
+                .map_err(|err| {
+                    ContractError::Panic(format!(
+                        "Recording the deployment failed: {err}"
+                    ))
+                })
+        }
+        Err(PiecrustError::OutOfGas) => Err(ContractError::OutOfGas),
+        Err(err) => Err(ContractError::Panic(format!("Deploy failed: {err}"))),
+    };
+}
+
+/// Checks that a deployment is signed by the owner it names.
+fn verify_deploy_signature(deploy: &Deploy) -> Result<(), ContractError> {
+    let owner = PublicSpendKey::from_bytes(&deploy.owner).map_err(|_| {
+        ContractError::Panic(String::from("Invalid deploy owner"))
+    })?;
+
+    let msg = Deploy::signature_message(
+        &deploy.bytecode,
+        &deploy.owner,
+        deploy.init_args.as_deref(),
+        deploy.nonce,
+    );
+    let msg = rusk_abi::hash(msg);
+
+    if !rusk_abi::verify_schnorr(
+        msg,
+        PublicKey::from(owner.A()),
+        deploy.signature,
+    ) {
+        return Err(ContractError::Panic(String::from(
+            "Invalid deploy signature",
+        )));
+    }
+
+    Ok(())
+}
+
+/// Directory of the contract bytecode in a commit directory.
+const BYTECODE_DIR: &str = "bytecode";
+
+/// Extension of the compiled contract modules in a commit directory.
+const OBJECTCODE_EXTENSION: &str = "a";
+
+/// Extension of the contract metadata in a commit directory.
+const METADATA_EXTENSION: &str = "m";
+
+/// Appends the files of a commit directory to a snapshot archive, leaving out
+/// the compiled contract modules.
+fn append_snapshot_files(
+    archive: &mut tar::Builder<Vec<u8>>,
+    dir: &Path,
+    prefix: &Path,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = prefix.join(path.file_name().unwrap_or_default());
+
+        if path.is_dir() {
+            archive.append_dir(&name, &path)?;
+            append_snapshot_files(archive, &path, &name)?;
+        } else if path.extension() != Some(OBJECTCODE_EXTENSION.as_ref()) {
+            archive.append_path_with_name(&path, &name)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Unpacks a snapshot archive in the given directory, only accepting regular
+/// files and directories, and discarding compiled contract modules.
+fn unpack_snapshot(snapshot: &[u8], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for entry in tar::Archive::new(snapshot).entries()? {
+        let mut entry = entry?;
+
+        match entry.header().entry_type() {
+            tar::EntryType::Regular | tar::EntryType::Directory => {}
+            _ => return Err(invalid_snapshot("unexpected entry type")),
+        }
+
+        let path = entry.path()?;
+        if path.extension() == Some(OBJECTCODE_EXTENSION.as_ref()) {
+            continue;
+        }
+
+        if !entry.unpack_in(dir)? {
+            return Err(invalid_snapshot("entry outside of the commit"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the commit unpacked in the given directory has the given state
+/// root, with every memory page of its contracts being part of it, and that
+/// their code and owners are trusted.
+///
+/// The memory of a commit is covered by its root, but its bytecode and
+/// contract metadata are not. The transfer contract is checked first against
+/// the base commit in `base_dir`, since it is then trusted to report the code
+/// hash and owner of the contracts deployed or upgraded by transactions. The
+/// other contracts must be the same as in the base commit.
+///
+/// The contract modules are compiled from the bytecode when loading the
+/// commit.
+fn verify_snapshot(
+    dir: &Path,
+    state_root: [u8; 32],
+    base_dir: &Path,
+) -> Result<()> {
+    let vm = rusk_abi::new_vm(dir)?;
+    if !vm.commits().contains(&state_root) {
+        return Err(Error::CommitNotFound(state_root));
+    }
+
+    let bytecode_dir = dir.join(hex::encode(state_root)).join(BYTECODE_DIR);
+    let mut session = rusk_abi::new_session(&vm, state_root, 0)?;
+    let mut contracts = Vec::new();
+
+    for entry in fs::read_dir(bytecode_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some() {
+            continue;
+        }
+
+        let contract = path
+            .file_name()
+            .and_then(|name| hex::decode(name.as_encoded_bytes()).ok())
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(ContractId::from_bytes)
+            .ok_or_else(|| invalid_snapshot("invalid contract bytecode"))?;
+
+        if session.memory_len(contract)?.is_none() {
+            return Err(invalid_snapshot("contract missing from the index"));
+        }
+
+        let pages = session
+            .memory_pages(contract)
+            .ok_or_else(|| invalid_snapshot("contract memory not found"))?;
+
+        for (index, page, opening) in pages {
+            if opening.root().as_ref() != state_root || !opening.verify(page) {
+                return Err(invalid_snapshot(format!(
+                    "memory page {index} of contract {} is not in the state",
+                    hex::encode(contract.to_bytes())
+                )));
+            }
+        }
+
+        contracts.push((contract, path));
+    }
+
+    contracts.sort_by_key(|(contract, _)| *contract != TRANSFER_CONTRACT);
+    let has_transfer = contracts
+        .first()
+        .is_some_and(|(contract, _)| *contract == TRANSFER_CONTRACT);
+
+    for (contract, path) in contracts {
+        let recorded = match has_transfer && contract != TRANSFER_CONTRACT {
+            true => recorded_code(&mut session, contract)?,
+            false => None,
+        };
+
+        let metadata_path = path.with_extension(METADATA_EXTENSION);
+
+        let trusted_code = match recorded {
+            Some((code_hash, _)) => {
+                rusk_abi::hash(fs::read(&path)?) == code_hash
+            }
+            None => same_file(&path, base_dir),
+        };
+        let trusted_owner = match recorded.and_then(|(_, owner)| owner) {
+            Some(owner) => session
+                .contract_metadata(&contract)
+                .is_some_and(|metadata| metadata.owner == owner),
+            None => same_file(&metadata_path, base_dir),
+        };
+
+        if !trusted_code || !trusted_owner {
+            return Err(invalid_snapshot(format!(
+                "code of contract {} is not trusted",
+                hex::encode(contract.to_bytes())
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// The code hash and owner the transfer contract recorded for a contract, if
+/// it was deployed or upgraded by a transaction. Contracts that were upgraded
+/// without being deployed by a transaction have no recorded owner.
+fn recorded_code(
+    session: &mut Session,
+    contract: ContractId,
+) -> Result<Option<(BlsScalar, Option<[u8; DEPLOY_OWNER_SIZE]>)>> {
+    let contract = contract.to_bytes();
+
+    let code_hash = session
+        .call::<_, Option<BlsScalar>>(
+            TRANSFER_CONTRACT,
+            "code_hash",
+            &contract,
+            u64::MAX,
+        )?
+        .data;
+    let Some(code_hash) = code_hash else {
+        return Ok(None);
+    };
+
+    let owner = session
+        .call::<_, Option<[u8; DEPLOY_OWNER_SIZE]>>(
+            TRANSFER_CONTRACT,
+            "deployed_owner",
+            &contract,
+            u64::MAX,
+        )?
+        .data;
+
+    Ok(Some((code_hash, owner)))
+}
+
+/// Whether the file has the same contents as the file with the same name in
+/// the given directory.
+fn same_file(path: &Path, dir: &Path) -> bool {
+    let Some(name) = path.file_name() else {
+        return false;
+    };
+
+    match (fs::read(path), fs::read(dir.join(name))) {
+        (Ok(data), Ok(base_data)) => data == base_data,
+        _ => false,
+    }
+}
+
+fn invalid_snapshot(reason: impl Into<String>) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.into()).into()
+}
+
+/// Adds the gas charged for host functions to the gas spent by a call.
+///
+/// The VM meters host functions apart from the contract execution, so each of
+/// them is bounded by the limit of the call. If their total exceeds the gas
+/// left after the execution, the call runs out of gas, spending all of it.
+fn charge_host_gas(
+    receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
+    host_gas: u64,
+) {
+    let gas_left = receipt.gas_limit.saturating_sub(receipt.gas_spent);
+
+    match host_gas > gas_left {
+        true => {
+            receipt.data = Err(ContractError::OutOfGas);
+            receipt.gas_spent = receipt.gas_limit;
+        }
+        false => receipt.gas_spent += host_gas,
+    }
+}
+
+/// Returns the size of the upgrade carried by the transaction, if its call
+/// targets the upgrade function of the transfer contract.
+fn upgrade_data_len(tx: &PhoenixTransaction) -> Option<usize> {
+    let (contract, fn_name, fn_args) = tx.call.as_ref()?;
+
+    (*contract == TRANSFER_CONTRACT.to_bytes() && fn_name == UPGRADE_FN_NAME)
+        .then_some(fn_args.len())
+}
+
+/// Creates a session to execute a block at the given height on top of `base`.
+///
+/// The contract upgrades whose timelock expires at the given height are
+/// applied before anything else in the block. An upgrade failing leaves the
+/// contract untouched, and is recorded as failed in the transfer contract.
+fn block_session(
+    vm: &VM,
+    base: [u8; 32],
+    block_height: u64,
+) -> Result<Session> {
+    let mut session = rusk_abi::new_session(vm, base, block_height)?;
+
+    let upgrades = session
+        .call::<_, Vec<(ModuleId, PendingUpgrade)>>(
+            TRANSFER_CONTRACT,
+            "due_upgrades",
+            &block_height,
+            u64::MAX,
+        )?
+        .data;
+
+    if upgrades.is_empty() {
+        return Ok(session);
+    }
+
+    let mut applied = Vec::with_capacity(upgrades.len());
+    let mut failed = Vec::new();
+
+    for entry @ (contract, upgrade) in &upgrades {
+        match upgrade_contract(session, *contract, upgrade, block_height) {
+            Ok(s) => {
+                session = s;
+                applied.push(entry);
+            }
+            Err(err) => {
+                warn!("Upgrade of {} failed: {err}", hex::encode(contract));
+                failed.push(*contract);
+
+                // A failed migration leaves the session in an inconsistent
+                // state, so we start over and re-apply the successful ones.
+                session = reapply_upgrades(
+                    vm,
+                    base,
+                    block_height,
+                    &mut applied,
+                    &mut failed,
+                )?;
+            }
+        }
+    }
+
+    let results: Vec<_> = upgrades
+        .iter()
+        .map(|(contract, _)| (*contract, !failed.contains(contract)))
+        .collect();
+
+    session.call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "finish_upgrades",
+        &(block_height, results),
+        u64::MAX,
+    )?;
+
+    Ok(session)
+}
+
+/// Creates a new session with the `applied` upgrades. An upgrade failing again
+/// is moved to the `failed` ones, and the session started over without it.
+fn reapply_upgrades(
+    vm: &VM,
+    base: [u8; 32],
+    block_height: u64,
+    applied: &mut Vec<&(ModuleId, PendingUpgrade)>,
+    failed: &mut Vec<ModuleId>,
+) -> Result<Session> {
+    'start: loop {
+        let mut session = rusk_abi::new_session(vm, base, block_height)?;
+
+        for i in 0..applied.len() {
+            let (contract, upgrade) = applied[i];
+            match upgrade_contract(session, *contract, upgrade, block_height) {
+                Ok(s) => session = s,
+                Err(err) => {
+                    warn!(
+                        "Upgrade of {} failed again: {err}",
+                        hex::encode(contract)
+                    );
+                    failed.push(*contract);
+                    applied.remove(i);
+                    continue 'start;
+                }
+            }
+        }
+
+        return Ok(session);
+    }
+}
+
+/// Swaps the bytecode of a contract, keeping its id and owner, and performs
+/// the migration call on the new bytecode if present.
+fn upgrade_contract(
+    session: Session,
+    contract: ModuleId,
+    upgrade: &PendingUpgrade,
+    block_height: u64,
+) -> Result<Session> {
+    let contract = ContractId::from_bytes(contract);
+
+    let owner: [u8; DEPLOY_OWNER_SIZE] = session
+        .contract_metadata(&contract)
+        .and_then(|metadata| metadata.owner.clone().try_into().ok())
+        .ok_or(PiecrustError::ContractDoesNotExist(contract))?;
+
+    // The new bytecode lives under a temporary id during the migration
+    let migration_id = rusk_abi::gen_contract_id(
+        &[&contract.to_bytes()[..], &block_height.to_le_bytes()].concat(),
+    );
+
+    // Host functions called by the migration are bounded by the same limit
+    let (session, _) = gas::metered(MIGRATION_GAS_LIMIT, || {
+        session.migrate(
+            contract,
+            &upgrade.bytecode,
+            ContractData::builder(owner).contract_id(migration_id),
+            MIGRATION_GAS_LIMIT,
+            |new_contract, session| {
+                if let Some(migration) = &upgrade.migration {
+                    session.call_raw(
+                        new_contract,
+                        &migration.fn_name,
+                        migration.fn_args.clone(),
+                        MIGRATION_GAS_LIMIT,
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    });
+
+    Ok(session?)
+}
+
+/// An argument that is already serialized, and is passed as is to the `init`
+/// function of a contract.
+struct RawArg<'a>(&'a [u8]);
+
+impl Archive for RawArg<'_> {
+    type Archived = ();
+    type Resolver = ();
+
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, _: *mut ()) {}
+}
+
+impl<S: Serializer + Fallible + ?Sized> Serialize<S> for RawArg<'_> {
+    fn serialize(&self, serializer: &mut S) -> Result<(), S::Error> {
+        serializer.write(self.0)
+    }
+}
+
 fn update_hasher(hasher: &mut Sha3_256, event: Event) {
     hasher.update(event.source.as_bytes());
     hasher.update(event.topic.as_bytes());
     hasher.update(event.data);
 }
 
+/// Converts an event emitted by the stake contract into a [`StakeEvent`],
+/// returning `None` for events emitted by any other contract.
+fn to_stake_event(event: &Event) -> Option<StakeEvent> {
+    if event.source != STAKE_CONTRACT {
+        return None;
+    }
+
+    let staking = rkyv::from_bytes::<StakingEvent>(&event.data).ok()?;
+
+    Some(StakeEvent {
+        topic: event.topic.clone(),
+        provisioner: PublicKeyBytes(staking.public_key.to_bytes()),
+        value: staking.value,
+        height: staking.height,
+    })
+}
+
 fn reward_slash_and_update_root(
     session: &mut Session,
     block_height: u64,
     dusk_spent: Dusk,
     generator: &BlsPublicKey,
     slashing: &[BlsPublicKey],
-) -> Result<()> {
+    hard_slashing: &[BlsPublicKey],
+) -> Result<Vec<Event>> {
     let (dusk_value, generator_value) =
         coinbase_value(block_height, dusk_spent);
 
-    session.call::<_, ()>(
+    let mut events = vec![];
+
+    let r = session.call::<_, ()>(
         STAKE_CONTRACT,
         "reward",
         &(*DUSK_KEY, dusk_value),
         u64::MAX,
     )?;
-    session.call::<_, ()>(
+    events.extend(r.events);
+
+    let r = session.call::<_, ()>(
         STAKE_CONTRACT,
         "reward",
         &(*generator, generator_value),
         u64::MAX,
     )?;
+    events.extend(r.events);
+
     let slash_amount = emission_amount(block_height);
 
     for to_slash in slashing {
-        session.call::<_, ()>(
+        let r = session.call::<_, ()>(
             STAKE_CONTRACT,
             "slash",
             &(*to_slash, slash_amount),
             u64::MAX,
         )?;
+        events.extend(r.events);
+    }
+
+    for to_slash in hard_slashing {
+        let r = session.call::<_, ()>(
+            STAKE_CONTRACT,
+            "hard_slash",
+            &(*to_slash, EQUIVOCATION_SLASH),
+            u64::MAX,
+        )?;
+        events.extend(r.events);
     }
 
     session.call::<_, ()>(TRANSFER_CONTRACT, "update_root", &(), u64::MAX)?;
 
-    Ok(())
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    const ALICE_BYTECODE: &[u8] = include_bytes!(
+        "../../../../target/wasm32-unknown-unknown/release/alice.wasm"
+    );
+
+    /// Creates a genesis state with a single contract in the given
+    /// directory, returning a [`Rusk`] instance on top of it and the id of
+    /// the contract.
+    fn genesis_rusk(dir: &Path) -> (ContractId, Rusk) {
+        let vm = rusk_abi::new_vm(dir).expect("Creating the VM should succeed");
+        let mut session = rusk_abi::new_genesis_session(&vm);
+
+        let contract = session
+            .deploy(
+                ALICE_BYTECODE,
+                ContractData::builder([0u8; DEPLOY_OWNER_SIZE]),
+                u64::MAX,
+            )
+            .expect("Deploying the contract should succeed");
+
+        let state_root = session.commit().expect("Committing should succeed");
+        fs::write(to_rusk_state_id_path(dir), state_root)
+            .expect("Writing the state id should succeed");
+
+        let rusk = Rusk::new(dir).expect("Creating Rusk should succeed");
+        (contract, rusk)
+    }
+
+    /// Creates a state changing the contract of the genesis state in the
+    /// given directory, returning a [`Rusk`] instance on top of it and its
+    /// state root.
+    fn rusk_with_contract(dir: &Path) -> ([u8; 32], Rusk) {
+        let (contract, genesis) = genesis_rusk(dir);
+        let genesis_root = genesis.base_root();
+
+        let vm = rusk_abi::new_vm(dir).expect("Creating the VM should succeed");
+        let mut session = rusk_abi::new_session(&vm, genesis_root, 0)
+            .expect("Opening the genesis state should succeed");
+
+        session
+            .call::<_, ()>(contract, "ping", &(), u64::MAX)
+            .expect("Calling the contract should succeed");
+
+        let state_root = session.commit().expect("Committing should succeed");
+        fs::write(to_rusk_state_id_path(dir), state_root)
+            .expect("Writing the state id should succeed");
+
+        let rusk = Rusk::new(dir).expect("Creating Rusk should succeed");
+        (state_root, rusk)
+    }
+
+    /// Rebuilds a snapshot, passing the contents of its files through the
+    /// given closure.
+    fn rebuild_snapshot<F>(snapshot: &[u8], mut f: F) -> Vec<u8>
+    where
+        F: FnMut(&Path, &mut Vec<u8>),
+    {
+        let mut rebuilt = tar::Builder::new(Vec::new());
+
+        for entry in tar::Archive::new(snapshot).entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().into_owned();
+            let mut header = entry.header().clone();
+
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            f(&path, &mut data);
+
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            rebuilt.append_data(&mut header, path, &data[..]).unwrap();
+        }
+
+        rebuilt.into_inner().unwrap()
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let (state_root, source) = rusk_with_contract(source_dir.path());
+
+        let snapshot = source.state_snapshot(state_root).unwrap();
+        rebuild_snapshot(&snapshot, |path, _| {
+            assert_ne!(
+                path.extension(),
+                Some(OBJECTCODE_EXTENSION.as_ref()),
+                "Compiled modules should not be archived"
+            );
+        });
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let (_, target) = genesis_rusk(target_dir.path());
+        assert_ne!(target.base_root(), state_root);
+
+        target.import_snapshot(state_root, &snapshot).unwrap();
+        target.install_snapshot(state_root).unwrap();
+
+        assert_eq!(target.base_root(), state_root);
+        target.with_inner(|inner| {
+            rusk_abi::new_session(&inner.vm, state_root, 0)
+                .expect("The imported commit should be loadable");
+        });
+    }
+
+    #[test]
+    fn snapshot_forged_root_rejected() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let (state_root, source) = rusk_with_contract(source_dir.path());
+        let snapshot = source.state_snapshot(state_root).unwrap();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let (_, target) = genesis_rusk(target_dir.path());
+
+        let forged_root = [0xff; 32];
+        let forged = rebuild_snapshot(&snapshot, |_, _| {});
+        assert!(target.import_snapshot(forged_root, &forged).is_err());
+        target.with_inner(|inner| {
+            assert!(!inner.vm.commits().contains(&forged_root));
+        });
+        assert!(!target_dir.path().join(hex::encode(forged_root)).exists());
+    }
+
+    #[test]
+    fn snapshot_tampered_memory_rejected() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let (state_root, source) = rusk_with_contract(source_dir.path());
+        let snapshot = source.state_snapshot(state_root).unwrap();
+
+        let mut tampered_pages = 0;
+        let tampered = rebuild_snapshot(&snapshot, |path, data| {
+            if path.starts_with("memory") && !data.is_empty() {
+                data[0] ^= 0xff;
+                tampered_pages += 1;
+            }
+        });
+        assert!(tampered_pages > 0, "The snapshot should carry memory");
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let (_, target) = genesis_rusk(target_dir.path());
+
+        let err = target.import_snapshot(state_root, &tampered).unwrap_err();
+        assert!(err.to_string().contains("is not in the state"), "{err}");
+        target.with_inner(|inner| {
+            assert!(!inner.vm.commits().contains(&state_root));
+        });
+        assert!(!target_dir.path().join(hex::encode(state_root)).exists());
+    }
+
+    #[test]
+    fn snapshot_tampered_bytecode_rejected() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let (state_root, source) = rusk_with_contract(source_dir.path());
+        let snapshot = source.state_snapshot(state_root).unwrap();
+
+        // Appending a custom section keeps the bytecode valid, and leaves the
+        // memory of the contract untouched
+        let mut tampered_bytecodes = 0;
+        let tampered = rebuild_snapshot(&snapshot, |path, data| {
+            if path.starts_with(BYTECODE_DIR) && path.extension().is_none() {
+                data.extend([0, 2, 1, b'x']);
+                tampered_bytecodes += 1;
+            }
+        });
+        assert!(tampered_bytecodes > 0, "The snapshot should carry bytecode");
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let (_, target) = genesis_rusk(target_dir.path());
+
+        let err = target.import_snapshot(state_root, &tampered).unwrap_err();
+        assert!(err.to_string().contains("is not trusted"), "{err}");
+        target.with_inner(|inner| {
+            assert!(!inner.vm.commits().contains(&state_root));
+        });
+        assert!(!target_dir.path().join(hex::encode(state_root)).exists());
+    }
 }
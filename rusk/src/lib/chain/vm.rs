@@ -13,7 +13,9 @@ use dusk_consensus::operations::{CallParams, VerificationOutput};
 use dusk_consensus::user::provisioners::Provisioners;
 use dusk_consensus::user::stake::Stake;
 use node::vm::VMExecution;
-use node_data::ledger::{Block, SpentTransaction, Transaction};
+use node_data::ledger::{
+    to_equivocators, Block, SpentTransaction, StakeEvent, Transaction,
+};
 
 use super::{Rusk, MINIMUM_STAKE};
 
@@ -36,6 +38,7 @@ impl VMExecution for Rusk {
                 params.generator_pubkey.inner(),
                 txs,
                 &params.missed_generators[..],
+                &params.equivocators[..],
             )
             .map_err(|inner| {
                 anyhow::anyhow!("Cannot execute txs: {inner}!!")
@@ -61,6 +64,7 @@ impl VMExecution for Rusk {
                 &generator,
                 blk.txs(),
                 &blk.header().failed_iterations.to_missed_generators()?,
+                &to_equivocators(&blk.header().evidence)?,
             )
             .map_err(|inner| anyhow::anyhow!("Cannot verify txs: {inner}!!"))?;
 
@@ -70,14 +74,18 @@ impl VMExecution for Rusk {
     fn accept(
         &self,
         blk: &Block,
-    ) -> anyhow::Result<(Vec<SpentTransaction>, VerificationOutput)> {
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        VerificationOutput,
+        Vec<StakeEvent>,
+    )> {
         info!("Received accept request");
         let generator = blk.header().generator_bls_pubkey;
         let generator =
             dusk_bls12_381_sign::PublicKey::from_slice(&generator.0)
                 .map_err(|e| anyhow::anyhow!("Error in from_slice {e:?}"))?;
 
-        let (txs, verification_output) = self
+        let (txs, verification_output, stake_events) = self
             .accept_transactions(
                 blk.header().height,
                 blk.header().gas_limit,
@@ -88,23 +96,28 @@ impl VMExecution for Rusk {
                     event_hash: blk.header().event_hash,
                 }),
                 &blk.header().failed_iterations.to_missed_generators()?,
+                &to_equivocators(&blk.header().evidence)?,
             )
             .map_err(|inner| anyhow::anyhow!("Cannot accept txs: {inner}!!"))?;
 
-        Ok((txs, verification_output))
+        Ok((txs, verification_output, stake_events))
     }
 
     fn finalize(
         &self,
         blk: &Block,
-    ) -> anyhow::Result<(Vec<SpentTransaction>, VerificationOutput)> {
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        VerificationOutput,
+        Vec<StakeEvent>,
+    )> {
         info!("Received finalize request");
         let generator = blk.header().generator_bls_pubkey;
         let generator =
             dusk_bls12_381_sign::PublicKey::from_slice(&generator.0)
                 .map_err(|e| anyhow::anyhow!("Error in from_slice {e:?}"))?;
 
-        let (txs, state_root) = self
+        let (txs, state_root, stake_events) = self
             .finalize_transactions(
                 blk.header().height,
                 blk.header().gas_limit,
@@ -115,12 +128,13 @@ impl VMExecution for Rusk {
                     event_hash: blk.header().event_hash,
                 }),
                 &blk.header().failed_iterations.to_missed_generators()?,
+                &to_equivocators(&blk.header().evidence)?,
             )
             .map_err(|inner| {
                 anyhow::anyhow!("Cannot finalize txs: {inner}!!")
             })?;
 
-        Ok((txs, state_root))
+        Ok((txs, state_root, stake_events))
     }
 
     fn preverify(&self, tx: &Transaction) -> anyhow::Result<()> {
@@ -186,6 +200,30 @@ impl VMExecution for Rusk {
 
         Ok(state_hash)
     }
+
+    fn retain_commits(&self, state_roots: Vec<[u8; 32]>) -> anyhow::Result<()> {
+        self.retain_commits(state_roots);
+        Ok(())
+    }
+
+    fn state_snapshot(&self, state_root: [u8; 32]) -> anyhow::Result<Vec<u8>> {
+        self.state_snapshot(state_root)
+            .map_err(|inner| anyhow::anyhow!("Cannot archive state: {inner}"))
+    }
+
+    fn import_snapshot(
+        &self,
+        state_root: [u8; 32],
+        snapshot: &[u8],
+    ) -> anyhow::Result<()> {
+        self.import_snapshot(state_root, snapshot)
+            .map_err(|inner| anyhow::anyhow!("Cannot import state: {inner}"))
+    }
+
+    fn install_snapshot(&self, state_root: [u8; 32]) -> anyhow::Result<()> {
+        self.install_snapshot(state_root)
+            .map_err(|inner| anyhow::anyhow!("Cannot install state: {inner}"))
+    }
 }
 
 impl Rusk {
@@ -5,14 +5,22 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::chain::Rusk;
-use crate::Result;
+use crate::{Error, Result};
 
 use std::sync::mpsc;
 
 use bytecheck::CheckBytes;
 use rkyv::validation::validators::DefaultValidator;
 use rkyv::{Archive, Deserialize, Infallible, Serialize};
-use rusk_abi::{ContractId, StandardBufSerializer};
+use rusk_abi::{
+    gas, ContractId, StandardBufSerializer, LICENSE_CONTRACT, STAKE_CONTRACT,
+    TRANSFER_CONTRACT,
+};
+
+/// Contracts whose feeder functions are known to terminate, the only ones
+/// raw feeder queries can be made to.
+const FEEDER_CONTRACTS: [ContractId; 3] =
+    [TRANSFER_CONTRACT, STAKE_CONTRACT, LICENSE_CONTRACT];
 
 impl Rusk {
     pub fn query_raw<S, V>(
@@ -27,15 +35,18 @@ impl Rusk {
     {
         let inner = self.inner.lock();
 
-        // For queries we set a point limit of effectively infinite and a block
-        // height of zero since this doesn't affect the result.
+        // For queries we set the configured gas limit, bounding both the
+        // contract execution and its host calls, and a block height of zero
+        // since this doesn't affect the result.
         let current_commit = inner.current_commit;
         let mut session = rusk_abi::new_session(&inner.vm, current_commit, 0)?;
 
-        session
-            .call_raw(contract_id, fn_name.as_ref(), fn_arg, u64::MAX)
-            .map(|receipt| receipt.data)
-            .map_err(Into::into)
+        let limit = self.query_gas_limit;
+        let (receipt, _) = gas::metered(limit, || {
+            session.call_raw(contract_id, fn_name.as_ref(), fn_arg, limit)
+        });
+
+        receipt.map(|receipt| receipt.data).map_err(Into::into)
     }
 
     pub(crate) fn query<A, R>(
@@ -125,6 +136,18 @@ impl Rusk {
         Ok(())
     }
 
+    /// Checks raw feeder queries can be made to the contract.
+    ///
+    /// Feeder calls are made with a point limit which can't be lowered, so a
+    /// contract could loop forever without feeding. Only the contracts whose
+    /// feeder functions are known to terminate are therefore queried.
+    pub(crate) fn check_feeder_query(contract_id: ContractId) -> Result<()> {
+        if !FEEDER_CONTRACTS.contains(&contract_id) {
+            return Err(Error::UnboundedFeederQuery(contract_id.to_bytes()));
+        }
+        Ok(())
+    }
+
     pub fn feeder_query_raw<S, V>(
         &self,
         contract_id: ContractId,
@@ -136,19 +159,25 @@ impl Rusk {
         S: AsRef<str>,
         V: Into<Vec<u8>>,
     {
+        // Feeder calls are always made with a point limit of effectively
+        // infinite, thus only their host calls are metered against the query
+        // gas limit.
+        Self::check_feeder_query(contract_id)?;
+
         let inner = self.inner.lock();
 
-        // For queries we set a point limit of effectively infinite and a block
-        // height of zero since this doesn't affect the result.
         let current_commit = inner.current_commit;
         let mut session = rusk_abi::new_session(&inner.vm, current_commit, 0)?;
 
-        session.feeder_call_raw(
-            contract_id,
-            call_name.as_ref(),
-            call_arg,
-            feeder,
-        )?;
+        let (result, _) = gas::metered(self.query_gas_limit, || {
+            session.feeder_call_raw(
+                contract_id,
+                call_name.as_ref(),
+                call_arg,
+                feeder,
+            )
+        });
+        result?;
 
         Ok(())
     }
@@ -51,6 +51,8 @@ pub enum Error {
     Other(Box<dyn std::error::Error>),
     /// Commit not found amongst existing commits
     CommitNotFound([u8; 32]),
+    /// Feeder query of a contract whose gas can't be bounded
+    UnboundedFeederQuery([u8; 32]),
 }
 
 impl std::error::Error for Error {}
@@ -146,6 +148,13 @@ impl fmt::Display for Error {
             Error::CommitNotFound(commit_id) => {
                 write!(f, "Commit not found, id = {}", hex::encode(commit_id),)
             }
+            Error::UnboundedFeederQuery(contract_id) => {
+                write!(
+                    f,
+                    "Feeder queries are not supported by contract {}",
+                    hex::encode(contract_id)
+                )
+            }
         }
     }
 }
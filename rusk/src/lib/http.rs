@@ -6,26 +6,40 @@
 
 #![allow(unused)]
 
+mod admin;
 #[cfg(feature = "node")]
 mod chain;
+mod compression;
+mod cors;
 mod event;
+mod keepalive;
+mod limits;
+mod metrics;
 #[cfg(feature = "prover")]
 mod prover;
 #[cfg(feature = "node")]
 mod rusk;
 mod stream;
+mod tls;
 
+pub use admin::LogFilterHandle;
+pub use compression::{CompressionConfig, Encoding};
+pub use cors::CorsConfig;
 pub(crate) use event::{
     BinaryWrapper, DataType, ExecutionError, MessageResponse as EventResponse,
     RequestData, Target,
 };
 use hyper::http::{HeaderName, HeaderValue};
+pub use keepalive::KeepAliveConfig;
+pub use limits::{HttpLimits, RateLimit, RUSK_API_KEY_HEADER};
+pub use tls::TlsConfig;
 use tracing::info;
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::Infallible;
 use std::future::Future;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::pin::Pin;
 use std::str::FromStr;
@@ -36,16 +50,17 @@ use async_trait::async_trait;
 
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::ToSocketAddrs;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, OwnedSemaphorePermit};
 use tokio::{io, task};
 
+use hyper::header::ORIGIN;
 use hyper::server::conn::Http;
 use hyper::service::Service;
 use hyper::{body, Body, Request, Response, StatusCode};
 use hyper_tungstenite::{tungstenite, HyperWebsocket};
 
 use tungstenite::protocol::frame::coding::CloseCode;
-use tungstenite::protocol::{CloseFrame, Message};
+use tungstenite::protocol::{CloseFrame, Message, WebSocketConfig};
 
 use futures_util::stream::iter as stream_iter;
 use futures_util::{SinkExt, StreamExt};
@@ -54,11 +69,25 @@ use futures_util::{SinkExt, StreamExt};
 use crate::chain::{Rusk, RuskNode};
 use crate::VERSION;
 
-use self::event::{MessageRequest, ResponseData};
+use self::event::{Event, MessageRequest, ResponseData};
+use self::keepalive::Activity;
+use self::limits::{LimitBodyError, Limiter, Rejection};
 use self::stream::{Listener, Stream};
+use self::tls::Tls;
 
 const RUSK_VERSION_HEADER: &str = "Rusk-Version";
 
+/// Options of the HTTP server.
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    pub limits: HttpLimits,
+    pub cors: CorsConfig,
+    pub compression: CompressionConfig,
+    pub keepalive: KeepAliveConfig,
+    /// Whether to serve the Prometheus metrics at `/metrics`.
+    pub metrics: bool,
+}
+
 pub struct HttpServer {
     pub handle: task::JoinHandle<()>,
     local_addr: SocketAddr,
@@ -66,30 +95,37 @@ pub struct HttpServer {
 }
 
 impl HttpServer {
-    pub async fn bind<A, H, P1, P2>(
+    pub async fn bind<A, H>(
         handler: H,
         addr: A,
-        cert_and_key: Option<(P1, P2)>,
+        tls: Option<TlsConfig>,
+        options: HttpOptions,
     ) -> io::Result<Self>
     where
         A: ToSocketAddrs,
         H: HandleRequest,
-        P1: AsRef<Path>,
-        P2: AsRef<Path>,
     {
-        let listener = match cert_and_key {
-            Some(cert_and_key) => Listener::bind_tls(addr, cert_and_key).await,
+        let (shutdown_sender, shutdown_receiver) = broadcast::channel(1);
+
+        let listener = match tls {
+            Some(tls) => {
+                let tls = Arc::new(Tls::new(tls)?);
+                task::spawn(tls.clone().watch(shutdown_receiver.resubscribe()));
+                Listener::bind_tls(addr, tls).await
+            }
             None => Listener::bind(addr).await,
         }?;
 
-        let (shutdown_sender, shutdown_receiver) = broadcast::channel(1);
-
         let local_addr = listener.local_addr()?;
 
         info!("Starting HTTP Listener to {local_addr}");
 
-        let handle =
-            task::spawn(listening_loop(handler, listener, shutdown_receiver));
+        let handle = task::spawn(listening_loop(
+            handler,
+            listener,
+            options,
+            shutdown_receiver,
+        ));
 
         Ok(Self {
             handle,
@@ -106,6 +142,8 @@ pub struct DataSources {
     pub node: RuskNode,
     #[cfg(feature = "prover")]
     pub prover: rusk_prover::LocalProver,
+    /// Handle changing the log filter through the admin target
+    pub log_filter: Option<LogFilterHandle>,
 }
 
 #[async_trait]
@@ -133,6 +171,9 @@ impl HandleRequest for DataSources {
             }
             #[cfg(feature = "node")]
             (_, "Chain", _) => self.node.handle(request).await,
+            (Target::Host(_), admin::ADMIN_TARGET, _) => {
+                self.handle_admin(request).await
+            }
             _ => Err(anyhow::anyhow!("unsupported target type")),
         }
     }
@@ -141,12 +182,20 @@ impl HandleRequest for DataSources {
 async fn listening_loop<H>(
     handler: H,
     listener: Listener,
+    options: HttpOptions,
     mut shutdown: broadcast::Receiver<Infallible>,
 ) where
     H: HandleRequest,
 {
     let handler = Arc::new(handler);
-    let http = Http::new();
+    let limiter = Arc::new(Limiter::new(options.limits.clone()));
+    let options = Arc::new(options);
+    let cert_targets = Arc::new(listener.client_cert_targets());
+
+    let keepalive = &options.keepalive;
+    let mut http = Http::new();
+    http.http2_keep_alive_interval(keepalive.http2_interval)
+        .http2_keep_alive_timeout(keepalive.http2_timeout);
 
     loop {
         tokio::select! {
@@ -154,18 +203,49 @@ async fn listening_loop<H>(
                 break;
             }
             r = listener.accept() => {
-                let stream = match r {
-                    Ok(stream) => stream,
+                let (handshake, peer) = match r {
+                    Ok(accepted) => accepted,
                     Err(_) => break,
                 };
 
-                let service = ExecutionService {
+                let activity = Activity::new();
+                let mut service = ExecutionService {
                     sources: handler.clone(),
+                    limiter: limiter.clone(),
+                    options: options.clone(),
+                    activity: activity.clone(),
+                    peer: peer.ip(),
+                    client_cert: false,
+                    cert_targets: cert_targets.clone(),
                     shutdown: shutdown.resubscribe()
                 };
-                let conn = http.serve_connection(stream, service).with_upgrades();
-
-                task::spawn(conn);
+                let http = http.clone();
+                let idle_timeout = options.keepalive.idle_timeout;
+
+                task::spawn(async move {
+                    // Failed handshakes, such as the ones of clients without
+                    // a required certificate, only drop their connection.
+                    let stream = match handshake.finish().await {
+                        Ok(stream) => stream,
+                        Err(_) => return,
+                    };
+                    service.client_cert = stream.has_client_cert();
+
+                    let conn = http.serve_connection(stream, service).with_upgrades();
+                    let mut conn = std::pin::pin!(conn);
+
+                    // Idle connections are gracefully shut down, letting the
+                    // responses being sent complete.
+                    if let Some(idle_timeout) = idle_timeout {
+                        tokio::select! {
+                            _ = conn.as_mut() => return,
+                            _ = activity.idle(idle_timeout) => {
+                                conn.as_mut().graceful_shutdown();
+                            }
+                        }
+                    }
+                    let _ = conn.await;
+                });
             }
         }
     }
@@ -175,6 +255,8 @@ async fn handle_stream<H: HandleRequest>(
     sources: Arc<H>,
     websocket: HyperWebsocket,
     target: Target,
+    client: Client,
+    keepalive: KeepAliveConfig,
     mut shutdown: broadcast::Receiver<Infallible>,
 ) {
     let mut stream = match websocket.await {
@@ -196,6 +278,14 @@ async fn handle_stream<H: HandleRequest>(
 
     let (responder, mut responses) = mpsc::unbounded_channel::<EventResponse>();
 
+    let mut ping = keepalive.ws_ping();
+    let idle_deadline = || {
+        keepalive
+            .idle_timeout
+            .map(|t| tokio::time::Instant::now() + t)
+    };
+    let mut idle_at = idle_deadline();
+
     'outer: loop {
         tokio::select! {
             // If the server shuts down we send a close frame to the client
@@ -208,6 +298,22 @@ async fn handle_stream<H: HandleRequest>(
                 break;
             }
 
+            // If the client has not sent anything for too long we close the
+            // stream.
+            _ = keepalive::deadline(idle_at) => {
+                let _ = stream.close(Some(CloseFrame {
+                    code: CloseCode::Away,
+                    reason: Cow::from("Idle timeout"),
+                })).await;
+                break;
+            }
+
+            _ = keepalive::tick(&mut ping) => {
+                if stream.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+
             rsp = responses.recv() => {
                 // `responder` is never dropped so this can never be `None`
                 let rsp = rsp.unwrap();
@@ -263,6 +369,7 @@ async fn handle_stream<H: HandleRequest>(
             }
 
             msg = stream.next() => {
+                idle_at = idle_deadline();
 
                 let mut req = match msg {
                     Some(Ok(msg)) => match msg {
@@ -276,6 +383,9 @@ async fn handle_stream<H: HandleRequest>(
                             MessageRequest::parse(&msg)
                                 .map_err(|err| anyhow::anyhow!("Failed deserializing request: {err}"))
                         }
+                        // Pings are answered by the stream itself, and pongs
+                        // only keep the stream alive.
+                        Message::Ping(_) | Message::Pong(_) => continue 'outer,
                         // Any other type of message is unsupported.
                         _ => Err(anyhow::anyhow!("Only text and binary messages are supported"))
                     }
@@ -300,11 +410,22 @@ async fn handle_stream<H: HandleRequest>(
                     // We received a valid request and should spawn a new task to handle it
                     Ok(mut req) => {
                         req.event.target=target.clone();
-                        task::spawn(handle_execution(
-                            sources.clone(),
-                            req,
-                            responder.clone(),
-                        ));
+                        let admitted = client
+                            .admit()
+                            .and_then(|_| client.acquire(&req.event));
+                        match admitted {
+                            Ok(permit) => {
+                                let sources = sources.clone();
+                                let responder = responder.clone();
+                                task::spawn(async move {
+                                    handle_execution(sources, req, responder).await;
+                                    drop(permit);
+                                });
+                            }
+                            Err(rejection) => {
+                                let _ = responder.send(req.to_error(rejection.to_string()));
+                            }
+                        }
                     },
                     Err(e) => {
                         let _ = stream.close(Some(CloseFrame {
@@ -322,9 +443,80 @@ async fn handle_stream<H: HandleRequest>(
 
 struct ExecutionService<H> {
     sources: Arc<H>,
+    limiter: Arc<Limiter>,
+    options: Arc<HttpOptions>,
+    activity: Arc<Activity>,
+    peer: IpAddr,
+    client_cert: bool,
+    cert_targets: Arc<HashSet<String>>,
     shutdown: broadcast::Receiver<Infallible>,
 }
 
+/// A client making requests to the server, identified by its address and API
+/// key.
+struct Client {
+    limiter: Arc<Limiter>,
+    peer: IpAddr,
+    api_key: Option<String>,
+    authorized: bool,
+    client_cert: bool,
+    cert_targets: Arc<HashSet<String>>,
+}
+
+impl Client {
+    fn new(
+        limiter: Arc<Limiter>,
+        peer: IpAddr,
+        client_cert: bool,
+        cert_targets: Arc<HashSet<String>>,
+        req: &Request<Body>,
+    ) -> Result<Self, Rejection> {
+        let api_key = req
+            .headers()
+            .get(RUSK_API_KEY_HEADER)
+            .map(|key| key.to_str().unwrap_or_default().to_string());
+        let authorized = limiter.authorize(api_key.as_deref())?;
+
+        Ok(Self {
+            limiter,
+            peer,
+            api_key,
+            authorized,
+            client_cert,
+            cert_targets,
+        })
+    }
+
+    /// Takes a request from the rate limit of the client.
+    fn admit(&self) -> Result<(), Rejection> {
+        self.limiter
+            .admit(self.peer, self.api_key.as_deref(), self.authorized)
+    }
+
+    /// Acquires the permit to handle the given request, which must be held
+    /// while handling it.
+    fn acquire(
+        &self,
+        event: &Event,
+    ) -> Result<Option<OwnedSemaphorePermit>, Rejection> {
+        let (_, target, topic) = event.to_route();
+
+        let cert_required = self.cert_targets.contains(target)
+            || self.cert_targets.contains(topic);
+        if cert_required && !self.client_cert {
+            return Err(Rejection::ClientCertRequired);
+        }
+
+        // Controlling the node requires either an API key or a certificate
+        let admin = target == admin::ADMIN_TARGET;
+        if admin && !self.authorized && !self.client_cert {
+            return Err(Rejection::Unauthorized);
+        }
+
+        self.limiter.acquire(topic, self.authorized)
+    }
+}
+
 impl<H> Service<Request<Body>> for ExecutionService<H>
 where
     H: HandleRequest,
@@ -353,38 +545,102 @@ where
     /// latter task running the stream handler loop is spawned.
     fn call(&mut self, mut req: Request<Body>) -> Self::Future {
         let sources = self.sources.clone();
+        let limiter = self.limiter.clone();
+        let options = self.options.clone();
+        let busy = self.activity.busy();
+        let peer = self.peer;
+        let client_cert = self.client_cert;
+        let cert_targets = self.cert_targets.clone();
         let shutdown = self.shutdown.resubscribe();
 
         Box::pin(async move {
-            let response = handle_request(req, shutdown, sources).await;
-            response.or_else(|error| {
-                Ok(Response::builder()
-                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(Body::from(error.to_string()))
-                    .expect("Failed to build response"))
-            })
+            let _busy = busy;
+
+            // Preflight requests are answered before any limit is applied
+            if let Some(response) = options.cors.preflight(&req) {
+                return Ok(response);
+            }
+
+            let origin = req.headers().get(ORIGIN).cloned();
+            let encoding = options.compression.negotiate(req.headers());
+
+            let client =
+                Client::new(limiter, peer, client_cert, cert_targets, &req)
+                    .and_then(|client| client.admit().map(|_| client));
+            let mut response = match client {
+                Ok(client) => {
+                    handle_request(req, client, &options, shutdown, sources)
+                        .await
+                        .unwrap_or_else(|error| {
+                            Response::builder()
+                                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                .body(Body::from(error.to_string()))
+                                .expect("Failed to build response")
+                        })
+                }
+                Err(rejection) => rejection.into_response(),
+            };
+
+            options.cors.apply(origin.as_ref(), &mut response);
+            if let Some(encoding) = encoding {
+                response = options.compression.compress(response, encoding);
+            }
+
+            Ok(response)
         })
     }
 }
 
 async fn handle_request<H>(
     mut req: Request<Body>,
+    client: Client,
+    options: &HttpOptions,
     mut shutdown: broadcast::Receiver<Infallible>,
     sources: Arc<H>,
 ) -> Result<Response<Body>, ExecutionError>
 where
     H: HandleRequest,
 {
+    if options.metrics && metrics::is_metrics_request(&req) {
+        return Ok(metrics::response());
+    }
+
     if hyper_tungstenite::is_upgrade_request(&req) {
         let target = req.uri().path().try_into()?;
-        let (response, websocket) = hyper_tungstenite::upgrade(&mut req, None)?;
-        task::spawn(handle_stream(sources, websocket, target, shutdown));
+        let config = WebSocketConfig {
+            max_message_size: Some(client.limiter.max_body_size()),
+            max_frame_size: Some(client.limiter.max_body_size()),
+            ..WebSocketConfig::default()
+        };
+        let (response, websocket) =
+            hyper_tungstenite::upgrade(&mut req, Some(config))?;
+        task::spawn(handle_stream(
+            sources,
+            websocket,
+            target,
+            client,
+            options.keepalive.clone(),
+            shutdown,
+        ));
 
         Ok(response)
     } else {
+        let req = match client.limiter.limit_body(req).await {
+            Ok(req) => req,
+            Err(LimitBodyError::Rejected(rejection)) => {
+                return Ok(rejection.into_response())
+            }
+            Err(LimitBodyError::Hyper(err)) => return Err(err.into()),
+        };
+
         let (execution_request, binary_resp) =
             MessageRequest::from_request(req).await?;
 
+        let _permit = match client.acquire(&execution_request.event) {
+            Ok(permit) => permit,
+            Err(rejection) => return Ok(rejection.into_response()),
+        };
+
         let mut resp_headers = execution_request.x_headers();
 
         let (responder, mut receiver) = mpsc::unbounded_channel();
@@ -446,6 +702,7 @@ pub trait HandleRequest: Send + Sync + 'static {
 
 #[cfg(test)]
 mod tests {
+    use std::time::{Duration, Instant};
     use std::{fs, thread};
 
     use super::*;
@@ -488,11 +745,16 @@ mod tests {
 
     #[tokio::test]
     async fn http_query() {
-        let cert_and_key: Option<(String, String)> = None;
+        let tls: Option<TlsConfig> = None;
 
-        let server = HttpServer::bind(TestHandle, "localhost:0", cert_and_key)
-            .await
-            .expect("Binding the server to the address should succeed");
+        let server = HttpServer::bind(
+            TestHandle,
+            "localhost:0",
+            tls,
+            HttpOptions::default(),
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
 
         let data = Vec::from(&b"I am call data 0"[..]);
         let data = RequestData::Binary(BinaryWrapper { inner: data });
@@ -526,6 +788,235 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn http_limits() {
+        let tls: Option<TlsConfig> = None;
+
+        let now = Arc::new(parking_lot::Mutex::new(Instant::now()));
+        let clock = {
+            let now = now.clone();
+            limits::Clock::new(move || *now.lock())
+        };
+
+        let limits = HttpLimits {
+            max_body_size: 1024,
+            ip_rate: Some(RateLimit {
+                burst: 2,
+                per_second: 1,
+            }),
+            clock,
+            ..HttpLimits::default()
+        };
+
+        let options = HttpOptions {
+            limits,
+            ..HttpOptions::default()
+        };
+
+        let server = HttpServer::bind(TestHandle, "localhost:0", tls, options)
+            .await
+            .expect("Binding the server to the address should succeed");
+
+        let event = EventRequest {
+            target: Target::None,
+            data: RequestData::Binary(BinaryWrapper {
+                inner: vec![0; 1024],
+            }),
+            topic: "topic".into(),
+        };
+        let request = serde_json::to_vec(&event)
+            .expect("Serializing request should succeed");
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/01/target", server.local_addr);
+
+        let post = |body: Vec<u8>| client.post(&url).body(body).send();
+
+        // The oversized request still takes a token from the bucket
+        let response = post(request).await.expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let response = post(b"{}".to_vec())
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        for _ in 0..2 {
+            let response = post(b"{}".to_vec())
+                .await
+                .expect("Requesting should succeed");
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+            let retry_after = response
+                .headers()
+                .get(hyper::header::RETRY_AFTER)
+                .expect("Retry-After header should be set");
+            assert_eq!(retry_after, "1");
+        }
+
+        // Half a second refills half a token, which isn't enough
+        *now.lock() += Duration::from_millis(500);
+        let response = post(b"{}".to_vec())
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // A full second refills exactly one token
+        *now.lock() += Duration::from_millis(500);
+        let response = post(b"{}".to_vec())
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let response = post(b"{}".to_vec())
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn http_admin() {
+        let tls: Option<TlsConfig> = None;
+
+        let options = HttpOptions {
+            limits: HttpLimits {
+                api_keys: ["secret".to_string()].into(),
+                ..HttpLimits::default()
+            },
+            ..HttpOptions::default()
+        };
+
+        let server = HttpServer::bind(TestHandle, "localhost:0", tls, options)
+            .await
+            .expect("Binding the server to the address should succeed");
+
+        let event = EventRequest {
+            target: Target::None,
+            data: RequestData::Text("info".into()),
+            topic: "log_filter".into(),
+        };
+        let request = serde_json::to_vec(&event)
+            .expect("Serializing request should succeed");
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/02/admin", server.local_addr);
+
+        let response = client
+            .post(&url)
+            .body(request.clone())
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client
+            .post(&url)
+            .header(RUSK_API_KEY_HEADER, "secret")
+            .body(request)
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn http_cors_and_compression() {
+        let tls: Option<TlsConfig> = None;
+
+        let options = HttpOptions {
+            cors: CorsConfig {
+                allowed_origins: vec!["https://explorer.dusk".into()],
+                ..CorsConfig::default()
+            },
+            ..HttpOptions::default()
+        };
+
+        let server = HttpServer::bind(TestHandle, "localhost:0", tls, options)
+            .await
+            .expect("Binding the server to the address should succeed");
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/01/target", server.local_addr);
+
+        let response = client
+            .request(hyper::Method::OPTIONS, &url)
+            .header(ORIGIN, "https://explorer.dusk")
+            .header(hyper::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let data = vec![42u8; 4096];
+        let event = EventRequest {
+            target: Target::None,
+            data: RequestData::Binary(BinaryWrapper {
+                inner: data.clone(),
+            }),
+            topic: "topic".into(),
+        };
+        let request = serde_json::to_vec(&event)
+            .expect("Serializing request should succeed");
+
+        let response = client
+            .post(&url)
+            .header(ORIGIN, "https://explorer.dusk")
+            .header(hyper::header::ACCEPT_ENCODING, "zstd")
+            .body(request)
+            .send()
+            .await
+            .expect("Requesting should succeed");
+
+        let headers = response.headers();
+        assert_eq!(
+            headers[hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://explorer.dusk"
+        );
+        assert_eq!(headers[hyper::header::CONTENT_ENCODING], "zstd");
+
+        let response_bytes =
+            response.bytes().await.expect("There should be a response");
+        let response_bytes = zstd::decode_all(&response_bytes[..])
+            .expect("Response should be zstd encoded");
+        let response_bytes =
+            hex::decode(response_bytes).expect("data to be hex encoded");
+        assert_eq!(response_bytes, data, "Data received the same as sent");
+    }
+
+    #[tokio::test]
+    async fn http_metrics() {
+        let tls: Option<TlsConfig> = None;
+
+        let counter = prometheus::register_int_counter!(
+            "dusk_test_metric_total",
+            "Metric served in tests"
+        )
+        .expect("Registering the metric should succeed");
+        counter.inc();
+
+        let options = HttpOptions {
+            metrics: true,
+            ..HttpOptions::default()
+        };
+
+        let server = HttpServer::bind(TestHandle, "localhost:0", tls, options)
+            .await
+            .expect("Binding the server to the address should succeed");
+
+        let response =
+            reqwest::get(format!("http://{}/metrics", server.local_addr))
+                .await
+                .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[hyper::header::CONTENT_TYPE]
+            .to_str()
+            .expect("Content type should be a string")
+            .starts_with("text/plain"));
+
+        let metrics = response.text().await.expect("There should be metrics");
+        assert!(metrics.contains("dusk_test_metric_total 1"));
+    }
+
     #[tokio::test]
     async fn https_query() {
         let cert_path = "tests/assets/cert.pem";
@@ -538,7 +1029,8 @@ mod tests {
         let server = HttpServer::bind(
             TestHandle,
             "localhost:0",
-            Some((cert_path, key_path)),
+            Some(TlsConfig::new(cert_path, key_path)),
+            HttpOptions::default(),
         )
         .await
         .expect("Binding the server to the address should succeed");
@@ -583,13 +1075,96 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn https_client_cert() {
+        let cert_path = "tests/assets/cert.pem";
+        let key_path = "tests/assets/key.pem";
+
+        let tls = TlsConfig {
+            client_ca: Some("tests/assets/ca.pem".into()),
+            client_cert_targets: HashSet::from(["prover".to_string()]),
+            ..TlsConfig::new(cert_path, key_path)
+        };
+
+        let server = HttpServer::bind(
+            TestHandle,
+            "localhost:0",
+            Some(tls),
+            HttpOptions::default(),
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
+
+        let cert_bytes = fs::read(cert_path).expect("cert file should exist");
+        let certificate = reqwest::tls::Certificate::from_pem(&cert_bytes)
+            .expect("cert should be valid");
+
+        let client_cert = fs::read("tests/assets/client.pem")
+            .expect("client cert file should exist");
+        let client_key = fs::read("tests/assets/client.key")
+            .expect("client key file should exist");
+        let identity =
+            reqwest::Identity::from_pkcs8_pem(&client_cert, &client_key)
+                .expect("client identity should be valid");
+
+        let client = |identity: Option<reqwest::Identity>| {
+            let builder = reqwest::ClientBuilder::new()
+                .add_root_certificate(certificate.clone())
+                .danger_accept_invalid_certs(true);
+            match identity {
+                Some(identity) => builder.identity(identity),
+                None => builder,
+            }
+            .build()
+            .expect("creating client should succeed")
+        };
+
+        let event = EventRequest {
+            target: Target::None,
+            data: RequestData::Text("I am call data 0".into()),
+            topic: "prove_execute".into(),
+        };
+        let request = serde_json::to_vec(&event)
+            .expect("Serializing request should succeed");
+
+        let port = server.local_addr.port();
+        let post = |client: &reqwest::Client, target: &str| {
+            client
+                .post(format!("https://localhost:{port}/02/{target}"))
+                .body(request.clone())
+                .send()
+        };
+
+        let anonymous = client(None);
+        let response = post(&anonymous, "other")
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = post(&anonymous, "prover")
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let authenticated = client(Some(identity));
+        let response = post(&authenticated, "prover")
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn websocket_queries() {
-        let cert_and_key: Option<(String, String)> = None;
+        let tls: Option<TlsConfig> = None;
 
-        let server = HttpServer::bind(TestHandle, "localhost:0", cert_and_key)
-            .await
-            .expect("Binding the server to the address should succeed");
+        let server = HttpServer::bind(
+            TestHandle,
+            "localhost:0",
+            tls,
+            HttpOptions::default(),
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
 
         let stream = TcpStream::connect(server.local_addr)
             .expect("Connecting to the server should succeed");
@@ -0,0 +1,171 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use tracing::info;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::{reload, Registry};
+
+use super::event::{MessageRequest, ResponseData};
+use super::DataSources;
+
+/// Handle changing the log filter of the running process.
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Target of the requests controlling the node. These may only be made by
+/// clients with a valid API key or client certificate.
+pub(crate) const ADMIN_TARGET: &str = "admin";
+
+impl DataSources {
+    pub(crate) async fn handle_admin(
+        &self,
+        request: &MessageRequest,
+    ) -> anyhow::Result<ResponseData> {
+        match request.event.topic.as_str() {
+            "log_filter" => {
+                self.set_log_filter(&request.event.data.as_string())
+            }
+            #[cfg(feature = "node")]
+            topic => self.node.handle_admin(topic).await,
+            #[cfg(not(feature = "node"))]
+            _ => anyhow::bail!("Unsupported"),
+        }
+    }
+
+    fn set_log_filter(&self, filter: &str) -> anyhow::Result<ResponseData> {
+        let handle = self
+            .log_filter
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Log filter cannot be changed"))?;
+
+        let filter = EnvFilter::try_new(filter.trim())?;
+        let filter_str = filter.to_string();
+        handle.reload(filter)?;
+
+        info!("Changed log filter to {filter_str}");
+        Ok(ResponseData::new(filter_str))
+    }
+}
+
+#[cfg(feature = "node")]
+mod node {
+    use std::time::UNIX_EPOCH;
+
+    use dusk_consensus::metrics::step_label;
+    use node::chain::{Command, CommandOutput};
+    use node::database::{Mempool, DB};
+    use serde_json::json;
+
+    use super::ResponseData;
+    use crate::http::RuskNode;
+
+    impl RuskNode {
+        pub(crate) async fn handle_admin(
+            &self,
+            topic: &str,
+        ) -> anyhow::Result<ResponseData> {
+            let cmd = match topic {
+                "peers" => return self.peers().await,
+                "peer_scores" => return self.peer_scores().await,
+                "clear_mempool" => return self.clear_mempool().await,
+                "revert" => Command::RevertToFinal,
+                "revert_epoch" => Command::RevertToEpoch,
+                "pause_consensus" => Command::PauseConsensus,
+                "resume_consensus" => Command::ResumeConsensus,
+                "iteration_state" => Command::IterationState,
+                _ => anyhow::bail!("Unsupported"),
+            };
+
+            let output = match self.admin().execute(cmd).await? {
+                CommandOutput::Reverted { height } => {
+                    json!({ "height": height })
+                }
+                CommandOutput::Done => json!({}),
+                CommandOutput::IterationState(None) => json!(null),
+                CommandOutput::IterationState(Some(state)) => {
+                    let timeouts: serde_json::Map<_, _> = state
+                        .timeouts
+                        .iter()
+                        .map(|(step, timeout)| {
+                            let ms = timeout.as_millis() as u64;
+                            (step_label(*step).to_string(), json!(ms))
+                        })
+                        .collect();
+
+                    json!({
+                        "round": state.round,
+                        "iteration": state.iteration,
+                        "step": step_label(state.step),
+                        "timeouts_ms": timeouts,
+                        "generator": state.generator.map(|g| g.to_base58()),
+                        "committees": state.committees,
+                    })
+                }
+            };
+
+            Ok(ResponseData::new(output))
+        }
+
+        /// Lists the alive peers, with the time a message was last received
+        /// from them, in seconds since the Unix epoch.
+        async fn peers(&self) -> anyhow::Result<ResponseData> {
+            let peers =
+                self.network().read().await.alive_peers(usize::MAX).await;
+
+            let peers: Vec<_> = peers
+                .into_iter()
+                .map(|(address, last_seen)| {
+                    let last_seen = last_seen
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs());
+                    json!({
+                        "address": address.to_string(),
+                        "last_seen": last_seen,
+                    })
+                })
+                .collect();
+
+            Ok(ResponseData::new(json!(peers)))
+        }
+
+        /// Lists the scores of the peers having misbehaved, along with the
+        /// end of their ban, if any, in seconds since the Unix epoch.
+        async fn peer_scores(&self) -> anyhow::Result<ResponseData> {
+            let scores = self.network().read().await.peer_scores();
+
+            let scores: Vec<_> = scores
+                .into_iter()
+                .map(|peer| {
+                    let banned_until = peer
+                        .banned_until
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs());
+                    json!({
+                        "address": peer.address.to_string(),
+                        "score": peer.score,
+                        "offences": peer.offences,
+                        "last_offence": peer.last_offence.map(|m| format!("{m:?}")),
+                        "banned_until": banned_until,
+                    })
+                })
+                .collect();
+
+            Ok(ResponseData::new(json!(scores)))
+        }
+
+        async fn clear_mempool(&self) -> anyhow::Result<ResponseData> {
+            let removed = self.db().read().await.update(|t| {
+                let hashes = t.get_txs_hashes()?;
+                for hash in &hashes {
+                    t.delete_tx(*hash)?;
+                }
+                Ok(hashes.len())
+            })?;
+
+            tracing::info!("Cleared {removed} transactions from the mempool");
+            Ok(ResponseData::new(json!({ "removed": removed })))
+        }
+    }
+}
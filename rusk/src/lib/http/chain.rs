@@ -11,8 +11,8 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use node::database::rocksdb::{Backend, DBTransaction};
-use node::database::{Mempool, DB};
-use node::network::Kadcast;
+use node::database::{Mempool, Metadata, DB};
+use node::network::{Kadcast, PROTOCOL_VERSION};
 use node::Network;
 use node_data::ledger::Transaction;
 use node_data::message::Message;
@@ -73,6 +73,9 @@ impl HandleRequest for RuskNode {
                     .unwrap_or(usize::MAX);
                 self.get_gas_price(max_transactions).await
             }
+            (Target::Host(_), "Chain", "checkpoints") => {
+                self.get_checkpoints().await
+            }
             _ => anyhow::bail!("Unsupported"),
         }
     }
@@ -124,15 +127,66 @@ impl RuskNode {
         Ok(ResponseData::new(serde_json::to_value(nodes)?))
     }
 
+    /// Lists the epoch checkpoints the chain can be reverted to, oldest
+    /// first.
+    async fn get_checkpoints(&self) -> anyhow::Result<ResponseData> {
+        let checkpoints =
+            self.db().read().await.view(|t| t.fetch_checkpoints())?;
+
+        let checkpoints: Vec<_> = checkpoints
+            .into_iter()
+            .map(|c| {
+                let provisioners: Vec<_> = c
+                    .provisioners
+                    .iter()
+                    .map(|p| {
+                        json!({
+                            "key": p.provisioner.to_bs58(),
+                            "amount": p.value,
+                            "reward": p.reward,
+                            "eligible_since": p.eligible_since,
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "height": c.height,
+                    "block_hash": hex::encode(c.block_hash),
+                    "state_root": hex::encode(c.state_root),
+                    "provisioners": provisioners,
+                })
+            })
+            .collect();
+
+        Ok(ResponseData::new(json!(checkpoints)))
+    }
+
     async fn get_info(&self) -> anyhow::Result<ResponseData> {
         let mut info: HashMap<&str, serde_json::Value> = HashMap::new();
         info.insert("version", VERSION.as_str().into());
         info.insert("version_build", VERSION_BUILD.as_str().into());
 
-        let n_conf = self.network().read().await.conf().clone();
+        let network = self.network();
+        let network = network.read().await;
+        let n_conf = network.conf().clone();
         info.insert("bootstrapping_nodes", n_conf.bootstrapping_nodes.into());
         info.insert("chain_id", n_conf.kadcast_id.into());
         info.insert("kadcast_address", n_conf.public_address.into());
+        info.insert("protocol_version", PROTOCOL_VERSION.to_string().into());
+        info.insert("capabilities", network.capabilities().names().into());
+
+        let peers: Vec<_> = network
+            .peer_versions()
+            .into_iter()
+            .map(|(address, peer)| {
+                json!({
+                    "address": address.to_string(),
+                    "protocol_version": peer.version.to_string(),
+                    "capabilities": peer.capabilities.map(|c| c.names()),
+                })
+            })
+            .collect();
+        info.insert("peers", peers.into());
 
         Ok(ResponseData::new(serde_json::to_value(&info)?))
     }
@@ -6,10 +6,12 @@
 
 mod block;
 mod data;
+mod provisioner;
 mod tx;
 
 use block::*;
 use data::*;
+use provisioner::*;
 use tx::*;
 
 use async_graphql::{Context, FieldError, FieldResult, Object};
@@ -48,6 +50,15 @@ impl Query {
         tx_by_hash(ctx, hash).await
     }
 
+    /// Returns the transaction spending a nullifier, hex-encoded.
+    async fn tx_by_nullifier(
+        &self,
+        ctx: &Context<'_>,
+        nullifier: String,
+    ) -> OptResult<SpentTransaction> {
+        tx_by_nullifier(ctx, nullifier).await
+    }
+
     async fn transactions(
         &self,
         ctx: &Context<'_>,
@@ -113,6 +124,19 @@ impl Query {
         }
     }
 
+    /// Returns the blocks generated by a provisioner, identified by its
+    /// base58-encoded public key, between the heights `from` and `to`
+    /// (inclusive).
+    async fn blocks_by_generator(
+        &self,
+        ctx: &Context<'_>,
+        key: String,
+        from: u64,
+        to: u64,
+    ) -> FieldResult<Vec<Block>> {
+        blocks_by_generator(ctx, key, from, to).await
+    }
+
     async fn mempool_txs(
         &self,
         ctx: &Context<'_>,
@@ -127,4 +151,17 @@ impl Query {
     ) -> OptResult<Transaction> {
         mempool_by_hash(ctx, hash).await
     }
+
+    /// Returns the stake changes of a provisioner, identified by its
+    /// base58-encoded public key, between the heights `from` and `to`
+    /// (inclusive).
+    async fn provisioner_history(
+        &self,
+        ctx: &Context<'_>,
+        key: String,
+        from: u64,
+        to: u64,
+    ) -> FieldResult<Vec<StakeEvent>> {
+        provisioner_history(ctx, key, from, to).await
+    }
 }
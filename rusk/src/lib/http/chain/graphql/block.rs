@@ -7,6 +7,7 @@
 use super::*;
 use node::database::rocksdb::MD_HASH_KEY;
 use node::database::{into_array, Metadata};
+use node_data::bls::PublicKeyBytes;
 
 pub async fn block_by_height(
     ctx: &Context<'_>,
@@ -106,3 +107,29 @@ pub async fn blocks_range(
     blocks.reverse();
     Ok(blocks)
 }
+
+pub async fn blocks_by_generator(
+    ctx: &Context<'_>,
+    key: String,
+    from: u64,
+    to: u64,
+) -> FieldResult<Vec<Block>> {
+    if from > to {
+        return Err(FieldError::new("from must not be greater than to"));
+    }
+
+    let key = bs58::decode(key).into_vec()?;
+    let key = PublicKeyBytes(key[..].try_into()?);
+
+    let db = ctx.data::<DBContext>()?;
+    let blocks = db.read().await.view(|t| {
+        let mut blocks = vec![];
+        for hash in t.fetch_block_hashes_by_generator(&key, from, to)? {
+            if let Some((header, txs_id)) = t.fetch_block_header(&hash)? {
+                blocks.push(Block::new(header, txs_id));
+            }
+        }
+        Ok::<_, anyhow::Error>(blocks)
+    })?;
+    Ok(blocks)
+}
@@ -30,6 +30,7 @@ impl Block {
 pub struct Header<'a>(&'a node_data::ledger::Header);
 pub struct SpentTransaction(pub node_data::ledger::SpentTransaction);
 pub struct Transaction<'a>(TransactionData<'a>);
+pub struct StakeEvent(pub node_data::ledger::StakeEvent);
 
 impl<'a> From<&'a node_data::ledger::Transaction> for Transaction<'a> {
     fn from(value: &'a node_data::ledger::Transaction) -> Self {
@@ -264,6 +265,25 @@ impl Transaction<'_> {
     }
 }
 
+#[Object]
+impl StakeEvent {
+    pub async fn topic(&self) -> &str {
+        &self.0.topic
+    }
+
+    pub async fn provisioner(&self) -> String {
+        bs58::encode(self.0.provisioner.0).into_string()
+    }
+
+    pub async fn value(&self) -> u64 {
+        self.0.value
+    }
+
+    pub async fn height(&self) -> u64 {
+        self.0.height
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct CallData {
     contract_id: String,
@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use node_data::bls::PublicKeyBytes;
+
+use super::*;
+
+pub async fn provisioner_history(
+    ctx: &Context<'_>,
+    key: String,
+    from: u64,
+    to: u64,
+) -> FieldResult<Vec<StakeEvent>> {
+    if from > to {
+        return Err(FieldError::new("from must not be greater than to"));
+    }
+
+    let key = bs58::decode(key).into_vec()?;
+    let key = PublicKeyBytes(key[..].try_into()?);
+
+    let db = ctx.data::<DBContext>()?;
+    let events = db
+        .read()
+        .await
+        .view(|t| t.fetch_stake_events(&key, from, to))?;
+
+    Ok(events.into_iter().map(StakeEvent).collect())
+}
@@ -19,6 +19,19 @@ pub async fn tx_by_hash(
     Ok(tx.map(SpentTransaction))
 }
 
+pub async fn tx_by_nullifier(
+    ctx: &Context<'_>,
+    nullifier: String,
+) -> OptResult<SpentTransaction> {
+    let db = ctx.data::<DBContext>()?;
+    let nullifier = hex::decode(nullifier)?;
+    let tx = db
+        .read()
+        .await
+        .view(|t| t.get_ledger_tx_by_nullifier(&nullifier))?;
+    Ok(tx.map(SpentTransaction))
+}
+
 pub async fn last_transactions(
     ctx: &Context<'_>,
     count: usize,
@@ -0,0 +1,288 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::io::{self, Write};
+
+use flate2::write::GzEncoder;
+use futures_util::stream;
+use hyper::body::HttpBody;
+use hyper::header::{
+    HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, VARY,
+};
+use hyper::{Body, HeaderMap, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// Responses smaller than this, in bytes, are not compressed by default.
+const DEFAULT_MIN_SIZE: u64 = 1024;
+
+/// Compression of the responses of the HTTP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionConfig {
+    /// The encodings the server may use, in order of preference. Compression
+    /// is disabled if empty.
+    pub encodings: Vec<Encoding>,
+    /// Responses whose size is known to be smaller than this, in bytes, are
+    /// sent uncompressed.
+    pub min_size: u64,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            encodings: vec![Encoding::Zstd, Encoding::Gzip],
+            min_size: DEFAULT_MIN_SIZE,
+        }
+    }
+}
+
+/// An encoding a response may be compressed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    Gzip,
+    Zstd,
+}
+
+impl Encoding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Picks the encoding of the response to a request with the given
+    /// headers, according to its `Accept-Encoding` header.
+    pub(crate) fn negotiate(&self, headers: &HeaderMap) -> Option<Encoding> {
+        let accepted: Vec<(String, f32)> = headers
+            .get_all(ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(parse_coding)
+            .collect();
+
+        let quality = |encoding: &Encoding| {
+            accepted
+                .iter()
+                .find(|(coding, _)| coding == encoding.as_str())
+                .or_else(|| accepted.iter().find(|(coding, _)| coding == "*"))
+                .map(|(_, q)| *q)
+                .unwrap_or_default()
+        };
+
+        // The client's preference takes precedence, with ties broken by the
+        // order of the configured encodings.
+        let mut best: Option<(Encoding, f32)> = None;
+        for encoding in &self.encodings {
+            let q = quality(encoding);
+            if q > 0.0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((*encoding, q));
+            }
+        }
+        best.map(|(encoding, _)| encoding)
+    }
+
+    /// Compresses the body of the response with the given encoding, unless it
+    /// is empty, too small or already encoded.
+    pub(crate) fn compress(
+        &self,
+        response: Response<Body>,
+        encoding: Encoding,
+    ) -> Response<Body> {
+        let status = response.status();
+        let skip = status == StatusCode::SWITCHING_PROTOCOLS
+            || status == StatusCode::NO_CONTENT
+            || response.headers().contains_key(CONTENT_ENCODING)
+            || response.body().is_end_stream()
+            || response
+                .body()
+                .size_hint()
+                .exact()
+                .map_or(false, |size| size < self.min_size);
+        if skip {
+            return response;
+        }
+
+        let encoder = match Encoder::new(encoding) {
+            Ok(encoder) => encoder,
+            Err(_) => return response,
+        };
+
+        let (mut parts, body) = response.into_parts();
+
+        parts.headers.remove(CONTENT_LENGTH);
+        parts.headers.insert(
+            CONTENT_ENCODING,
+            HeaderValue::from_static(encoding.as_str()),
+        );
+        parts
+            .headers
+            .append(VARY, HeaderValue::from_static("accept-encoding"));
+
+        // Each chunk is flushed as soon as it is compressed, so streamed
+        // responses keep being streamed.
+        let chunks = stream::unfold(
+            (body, Some(encoder)),
+            |(mut body, encoder)| async move {
+                let mut encoder = encoder?;
+                let chunk = match body.data().await {
+                    Some(Ok(chunk)) => encoder.write_chunk(&chunk),
+                    Some(Err(err)) => {
+                        Err(io::Error::new(io::ErrorKind::Other, err))
+                    }
+                    None => {
+                        let chunk = encoder.finish();
+                        return Some((chunk, (body, None)));
+                    }
+                };
+                Some((chunk, (body, Some(encoder))))
+            },
+        );
+
+        Response::from_parts(parts, Body::wrap_stream(chunks))
+    }
+}
+
+/// Parses a coding of an `Accept-Encoding` header, with its quality.
+fn parse_coding(coding: &str) -> Option<(String, f32)> {
+    let mut params = coding.split(';');
+    let name = params.next()?.trim().to_ascii_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let quality = params
+        .filter_map(|param| param.trim().strip_prefix("q="))
+        .find_map(|q| q.trim().parse().ok())
+        .unwrap_or(1.0);
+
+    Some((name, quality))
+}
+
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding) -> io::Result<Self> {
+        Ok(match encoding {
+            Encoding::Gzip => Self::Gzip(GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            )),
+            Encoding::Zstd => Self::Zstd(zstd::stream::write::Encoder::new(
+                Vec::new(),
+                zstd::DEFAULT_COMPRESSION_LEVEL,
+            )?),
+        })
+    }
+
+    /// Compresses the chunk, returning the compressed bytes produced so far.
+    fn write_chunk(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        let buf = match self {
+            Self::Gzip(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+            Self::Zstd(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+        };
+        Ok(std::mem::take(buf))
+    }
+
+    /// Finishes the compression, returning the remaining compressed bytes.
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip(encoder) => encoder.finish(),
+            Self::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn negotiate() {
+        let config = CompressionConfig::default();
+
+        assert_eq!(config.negotiate(&HeaderMap::new()), None);
+        assert_eq!(config.negotiate(&accept("br")), None);
+        assert_eq!(config.negotiate(&accept("gzip")), Some(Encoding::Gzip));
+        assert_eq!(
+            config.negotiate(&accept("gzip, zstd")),
+            Some(Encoding::Zstd)
+        );
+        assert_eq!(
+            config.negotiate(&accept("zstd;q=0.5, gzip")),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(config.negotiate(&accept("*")), Some(Encoding::Zstd));
+        assert_eq!(
+            config.negotiate(&accept("*, zstd;q=0")),
+            Some(Encoding::Gzip)
+        );
+
+        let disabled = CompressionConfig {
+            encodings: vec![],
+            ..CompressionConfig::default()
+        };
+        assert_eq!(disabled.negotiate(&accept("gzip")), None);
+    }
+
+    #[tokio::test]
+    async fn compress() {
+        let config = CompressionConfig::default();
+        let data = vec![42u8; 4096];
+
+        let response = Response::new(Body::from(data.clone()));
+        let response = config.compress(response, Encoding::Gzip);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+
+        let bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("Reading the body should succeed");
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&bytes[..])
+            .read_to_end(&mut decompressed)
+            .expect("Decompressing should succeed");
+        assert_eq!(decompressed, data);
+
+        let response = Response::new(Body::from(data.clone()));
+        let response = config.compress(response, Encoding::Zstd);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "zstd");
+
+        let bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("Reading the body should succeed");
+        let decompressed =
+            zstd::decode_all(&bytes[..]).expect("Decompressing should succeed");
+        assert_eq!(decompressed, data);
+
+        // Small responses are left as they are
+        let response = Response::new(Body::from(vec![42u8; 16]));
+        let response = config.compress(response, Encoding::Zstd);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+    }
+}
@@ -0,0 +1,197 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::time::Duration;
+
+use hyper::header::{
+    HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// Cross-origin resource sharing policy of the HTTP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to make requests, or `*` for any origin. CORS is
+    /// disabled if empty.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests.
+    pub allowed_methods: Vec<String>,
+    /// Headers allowed in cross-origin requests.
+    pub allowed_headers: Vec<String>,
+    /// Response headers exposed to cross-origin requests.
+    pub exposed_headers: Vec<String>,
+    /// How long the result of a preflight request may be cached.
+    #[serde(with = "humantime_serde")]
+    pub max_age: Option<Duration>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![],
+            allowed_methods: ["GET", "POST", "OPTIONS"]
+                .map(String::from)
+                .to_vec(),
+            allowed_headers: [
+                "Content-Type",
+                "Rusk-Version",
+                "Rusk-Feeder",
+                "Rusk-Api-Key",
+            ]
+            .map(String::from)
+            .to_vec(),
+            exposed_headers: ["Rusk-Version"].map(String::from).to_vec(),
+            max_age: Some(Duration::from_secs(3600)),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// The value of the `Access-Control-Allow-Origin` header for a request
+    /// with the given origin, if it is allowed.
+    fn allow_origin(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        self.allowed_origins
+            .iter()
+            .find_map(|allowed| match allowed {
+                allowed if allowed == "*" => {
+                    Some(HeaderValue::from_static("*"))
+                }
+                allowed if allowed.as_bytes() == origin.as_bytes() => {
+                    Some(origin.clone())
+                }
+                _ => None,
+            })
+    }
+
+    /// Answers the request if it is a CORS preflight request.
+    pub(crate) fn preflight(
+        &self,
+        req: &Request<Body>,
+    ) -> Option<Response<Body>> {
+        if self.allowed_origins.is_empty()
+            || req.method() != Method::OPTIONS
+            || !req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+        {
+            return None;
+        }
+        let origin = req.headers().get(ORIGIN)?;
+
+        let allow_origin = match self.allow_origin(origin) {
+            Some(allow_origin) => allow_origin,
+            None => {
+                return Some(
+                    Response::builder()
+                        .status(StatusCode::FORBIDDEN)
+                        .body(Body::from("Origin not allowed"))
+                        .expect("Building a preflight response should succeed"),
+                )
+            }
+        };
+
+        let mut response = Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .header(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin)
+            .header(
+                ACCESS_CONTROL_ALLOW_METHODS,
+                self.allowed_methods.join(", "),
+            )
+            .header(
+                ACCESS_CONTROL_ALLOW_HEADERS,
+                self.allowed_headers.join(", "),
+            )
+            .header(VARY, "origin");
+        if let Some(max_age) = self.max_age {
+            response =
+                response.header(ACCESS_CONTROL_MAX_AGE, max_age.as_secs());
+        }
+
+        Some(
+            response
+                .body(Body::empty())
+                .expect("Building a preflight response should succeed"),
+        )
+    }
+
+    /// Adds the CORS headers to the response to a request from the given
+    /// origin.
+    pub(crate) fn apply(
+        &self,
+        origin: Option<&HeaderValue>,
+        response: &mut Response<Body>,
+    ) {
+        let allow_origin = match origin.and_then(|o| self.allow_origin(o)) {
+            Some(allow_origin) => allow_origin,
+            None => return,
+        };
+
+        let headers = response.headers_mut();
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        headers.append(VARY, HeaderValue::from_static("origin"));
+        if !self.exposed_headers.is_empty() {
+            if let Ok(exposed) =
+                HeaderValue::from_str(&self.exposed_headers.join(", "))
+            {
+                headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, exposed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .header(ORIGIN, origin)
+            .header(ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .expect("Building the request should succeed")
+    }
+
+    #[test]
+    fn cors() {
+        let disabled = CorsConfig::default();
+        assert!(disabled.preflight(&preflight("https://a.dusk")).is_none());
+
+        let config = CorsConfig {
+            allowed_origins: vec!["https://a.dusk".into()],
+            ..CorsConfig::default()
+        };
+
+        let response = config
+            .preflight(&preflight("https://a.dusk"))
+            .expect("Preflight should be answered");
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://a.dusk"
+        );
+        assert_eq!(response.headers()[ACCESS_CONTROL_MAX_AGE], "3600");
+
+        let response = config
+            .preflight(&preflight("https://b.dusk"))
+            .expect("Preflight should be answered");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let mut response = Response::new(Body::empty());
+        let origin = HeaderValue::from_static("https://b.dusk");
+        config.apply(Some(&origin), &mut response);
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let any = CorsConfig {
+            allowed_origins: vec!["*".into()],
+            ..CorsConfig::default()
+        };
+        any.apply(Some(&origin), &mut response);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+}
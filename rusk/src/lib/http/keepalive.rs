@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
+
+/// Keepalive and idle timeouts of the connections to the HTTP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeepAliveConfig {
+    /// Interval between the HTTP/2 pings sent to keep connections alive.
+    #[serde(with = "humantime_serde")]
+    pub http2_interval: Option<Duration>,
+    /// How long to wait for the acknowledgement of an HTTP/2 ping before
+    /// closing the connection.
+    #[serde(with = "humantime_serde")]
+    pub http2_timeout: Duration,
+    /// Interval between the pings sent over websockets.
+    #[serde(with = "humantime_serde")]
+    pub ws_ping_interval: Option<Duration>,
+    /// How long a connection, or a websocket, may stay idle before it is
+    /// closed.
+    #[serde(with = "humantime_serde")]
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self {
+            http2_interval: Some(Duration::from_secs(30)),
+            http2_timeout: Duration::from_secs(20),
+            ws_ping_interval: Some(Duration::from_secs(30)),
+            idle_timeout: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+impl KeepAliveConfig {
+    /// The interval at which to ping websockets, if enabled.
+    pub(crate) fn ws_ping(&self) -> Option<Interval> {
+        self.ws_ping_interval.map(|period| {
+            let mut interval =
+                time::interval_at(Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        })
+    }
+}
+
+/// Ticks the given interval, never completing if there is none.
+pub(crate) async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Completes at the given deadline, never completing if there is none.
+pub(crate) async fn deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Keeps track of the requests being handled on a connection, to find when it
+/// has become idle.
+pub(crate) struct Activity {
+    in_flight: AtomicUsize,
+    last_active: Mutex<Instant>,
+}
+
+impl Activity {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            in_flight: AtomicUsize::new(0),
+            last_active: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Marks a request as being handled, until the returned guard is dropped.
+    pub fn busy(self: &Arc<Self>) -> Busy {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        Busy(self.clone())
+    }
+
+    /// Completes once no request has been handled for `timeout`.
+    pub async fn idle(&self, timeout: Duration) {
+        loop {
+            let idle_at = *self.last_active.lock() + timeout;
+            let busy = self.in_flight.load(Ordering::SeqCst) > 0;
+
+            if !busy && idle_at <= Instant::now() {
+                return;
+            }
+
+            // While busy the connection is checked again after a full timeout
+            let wake_at = match busy {
+                true => Instant::now() + timeout,
+                false => idle_at,
+            };
+            time::sleep_until(wake_at).await;
+        }
+    }
+}
+
+/// Guard marking a request as being handled.
+pub(crate) struct Busy(Arc<Activity>);
+
+impl Drop for Busy {
+    fn drop(&mut self) {
+        *self.0.last_active.lock() = Instant::now();
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,435 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::body::HttpBody;
+use hyper::header::{CONTENT_LENGTH, RETRY_AFTER};
+use hyper::{Body, Request, Response, StatusCode};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Header carrying the API key of a request.
+pub const RUSK_API_KEY_HEADER: &str = "Rusk-Api-Key";
+
+/// Default maximum size of a request body, in bytes.
+const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Number of clients tracked before the ones with full buckets are forgotten.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Limits applied by the HTTP server to the requests it receives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpLimits {
+    /// Maximum size of the body of a request, or of a websocket message, in
+    /// bytes.
+    pub max_body_size: usize,
+    /// Rate limit applied to each client address. Clients presenting a valid
+    /// API key are limited by `api_key_rate` instead.
+    pub ip_rate: Option<RateLimit>,
+    /// Rate limit applied to each API key.
+    pub api_key_rate: Option<RateLimit>,
+    /// The accepted API keys.
+    pub api_keys: HashSet<String>,
+    /// Topics that may only be requested with a valid API key.
+    pub protected_topics: HashSet<String>,
+    /// Maximum number of requests for a topic handled at the same time.
+    pub topic_concurrency: HashMap<String, usize>,
+    /// The clock the rate limits are computed with.
+    #[serde(skip)]
+    pub(crate) clock: Clock,
+}
+
+impl Default for HttpLimits {
+    fn default() -> Self {
+        Self {
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            ip_rate: None,
+            api_key_rate: None,
+            api_keys: HashSet::new(),
+            protected_topics: HashSet::new(),
+            topic_concurrency: HashMap::new(),
+            clock: Clock::default(),
+        }
+    }
+}
+
+/// The source of the current time, replaceable to control the refilling of
+/// the rate limits in tests.
+#[derive(Clone)]
+pub(crate) struct Clock(Arc<dyn Fn() -> Instant + Send + Sync>);
+
+impl Clock {
+    #[cfg(test)]
+    pub fn new<F>(now: F) -> Self
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        Self(Arc::new(now))
+    }
+
+    fn now(&self) -> Instant {
+        (self.0)()
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self(Arc::new(Instant::now))
+    }
+}
+
+impl std::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Clock")
+    }
+}
+
+/// A token bucket rate limit: up to `burst` requests may be made at once, with
+/// the bucket refilling at `per_second` requests per second.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Maximum number of requests that can be made at once.
+    pub burst: u32,
+    /// Number of requests the bucket is refilled with each second.
+    pub per_second: u32,
+}
+
+/// The reason a request was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The API key is invalid, or missing for a protected topic.
+    Unauthorized,
+    /// The client did not present a certificate, required for the target.
+    ClientCertRequired,
+    /// The body of the request is too large.
+    PayloadTooLarge,
+    /// The client, or the topic, has too many requests ongoing.
+    TooManyRequests { retry_after: Duration },
+}
+
+impl Rejection {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::ClientCertRequired => StatusCode::FORBIDDEN,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    pub fn into_response(self) -> Response<Body> {
+        let mut response = Response::builder().status(self.status());
+
+        if let Self::TooManyRequests { retry_after } = self {
+            // Retry-After is given in whole seconds, so it is rounded up
+            let secs = retry_after.as_secs()
+                + u64::from(retry_after.subsec_nanos() > 0);
+            response = response.header(RETRY_AFTER, secs.max(1));
+        }
+
+        response
+            .body(Body::from(self.to_string()))
+            .expect("Building a rejection response should succeed")
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized => write!(f, "Invalid or missing API key"),
+            Self::ClientCertRequired => {
+                write!(f, "Client certificate required")
+            }
+            Self::PayloadTooLarge => write!(f, "Request body too large"),
+            Self::TooManyRequests { .. } => write!(f, "Too many requests"),
+        }
+    }
+}
+
+/// Enforces the [`HttpLimits`] on the requests received by the server.
+pub(crate) struct Limiter {
+    limits: HttpLimits,
+    ip_buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+    key_buckets: Mutex<HashMap<String, TokenBucket>>,
+    topics: HashMap<String, Arc<Semaphore>>,
+}
+
+impl Limiter {
+    pub fn new(limits: HttpLimits) -> Self {
+        let topics = limits
+            .topic_concurrency
+            .iter()
+            .map(|(topic, n)| (topic.clone(), Arc::new(Semaphore::new(*n))))
+            .collect();
+
+        Self {
+            limits,
+            ip_buckets: Mutex::new(HashMap::new()),
+            key_buckets: Mutex::new(HashMap::new()),
+            topics,
+        }
+    }
+
+    pub fn max_body_size(&self) -> usize {
+        self.limits.max_body_size
+    }
+
+    /// Checks the given API key, returning if the client is authorized.
+    pub fn authorize(&self, api_key: Option<&str>) -> Result<bool, Rejection> {
+        match api_key {
+            None => Ok(false),
+            Some(key) if self.limits.api_keys.contains(key) => Ok(true),
+            Some(_) => Err(Rejection::Unauthorized),
+        }
+    }
+
+    /// Takes a token from the bucket of the client, identified by its API key
+    /// if authorized, and by its address otherwise.
+    pub fn admit(
+        &self,
+        ip: IpAddr,
+        api_key: Option<&str>,
+        authorized: bool,
+    ) -> Result<(), Rejection> {
+        let now = self.limits.clock.now();
+
+        let result = match api_key.filter(|_| authorized) {
+            Some(key) => self.limits.api_key_rate.map_or(Ok(()), |rate| {
+                take(&self.key_buckets, key.to_string(), rate, now)
+            }),
+            None => self
+                .limits
+                .ip_rate
+                .map_or(Ok(()), |rate| take(&self.ip_buckets, ip, rate, now)),
+        };
+
+        result.map_err(|retry_after| Rejection::TooManyRequests { retry_after })
+    }
+
+    /// Acquires a permit to handle a request for the given topic, which must
+    /// be held until the request is handled.
+    pub fn acquire(
+        &self,
+        topic: &str,
+        authorized: bool,
+    ) -> Result<Option<OwnedSemaphorePermit>, Rejection> {
+        if !authorized && self.limits.protected_topics.contains(topic) {
+            return Err(Rejection::Unauthorized);
+        }
+
+        self.topics
+            .get(topic)
+            .map(|semaphore| {
+                semaphore.clone().try_acquire_owned().map_err(|_| {
+                    Rejection::TooManyRequests {
+                        retry_after: Duration::from_secs(1),
+                    }
+                })
+            })
+            .transpose()
+    }
+
+    /// Reads the body of the request, rejecting it if it exceeds the maximum
+    /// size.
+    pub async fn limit_body(
+        &self,
+        req: Request<Body>,
+    ) -> Result<Request<Body>, LimitBodyError> {
+        let max = self.limits.max_body_size;
+
+        let declared_len = req
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok())
+            .and_then(|len| len.parse::<usize>().ok());
+        if declared_len.unwrap_or_default() > max {
+            return Err(Rejection::PayloadTooLarge.into());
+        }
+
+        let (parts, mut body) = req.into_parts();
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            if bytes.len() + chunk.len() > max {
+                return Err(Rejection::PayloadTooLarge.into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(Request::from_parts(parts, Body::from(bytes)))
+    }
+}
+
+/// Error returned when limiting the body of a request.
+pub(crate) enum LimitBodyError {
+    Rejected(Rejection),
+    Hyper(hyper::Error),
+}
+
+impl From<Rejection> for LimitBodyError {
+    fn from(rejection: Rejection) -> Self {
+        Self::Rejected(rejection)
+    }
+}
+
+impl From<hyper::Error> for LimitBodyError {
+    fn from(err: hyper::Error) -> Self {
+        Self::Hyper(err)
+    }
+}
+
+fn take<K: std::hash::Hash + Eq>(
+    buckets: &Mutex<HashMap<K, TokenBucket>>,
+    client: K,
+    rate: RateLimit,
+    now: Instant,
+) -> Result<(), Duration> {
+    let mut buckets = buckets.lock();
+
+    if buckets.len() >= MAX_TRACKED_CLIENTS {
+        buckets.retain(|_, bucket| !bucket.is_full(rate, now));
+    }
+
+    buckets
+        .entry(client)
+        .or_insert_with(|| TokenBucket::new(rate, now))
+        .take(rate, now)
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: rate.burst as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, rate: RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        let refill = elapsed.as_secs_f64() * rate.per_second as f64;
+
+        self.tokens = (self.tokens + refill).min(rate.burst as f64);
+        self.updated = now;
+    }
+
+    fn is_full(&mut self, rate: RateLimit, now: Instant) -> bool {
+        self.refill(rate, now);
+        self.tokens >= rate.burst as f64
+    }
+
+    /// Takes a token from the bucket, or returns how long to wait until one is
+    /// available.
+    fn take(&mut self, rate: RateLimit, now: Instant) -> Result<(), Duration> {
+        self.refill(rate, now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        if rate.per_second == 0 {
+            return Err(Duration::MAX);
+        }
+
+        let missing = 1.0 - self.tokens;
+        Err(Duration::from_secs_f64(missing / rate.per_second as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: RateLimit = RateLimit {
+        burst: 2,
+        per_second: 1,
+    };
+
+    #[test]
+    fn token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(RATE, now);
+
+        assert!(bucket.take(RATE, now).is_ok());
+        assert!(bucket.take(RATE, now).is_ok());
+
+        let retry_after = bucket.take(RATE, now).expect_err("Bucket is empty");
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        let later = now + Duration::from_millis(500);
+        let retry_after = bucket.take(RATE, later).expect_err("Half a token");
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        let later = now + Duration::from_secs(1);
+        assert!(bucket.take(RATE, later).is_ok(), "Refilled a token");
+    }
+
+    #[test]
+    fn api_keys() {
+        let limiter = Limiter::new(HttpLimits {
+            ip_rate: Some(RateLimit {
+                burst: 1,
+                per_second: 0,
+            }),
+            api_key_rate: Some(RATE),
+            api_keys: HashSet::from(["key".to_string()]),
+            protected_topics: HashSet::from(["prove_execute".to_string()]),
+            ..HttpLimits::default()
+        });
+        let ip = IpAddr::from([127, 0, 0, 1]);
+
+        assert_eq!(
+            limiter.authorize(Some("wrong")),
+            Err(Rejection::Unauthorized)
+        );
+        assert_eq!(limiter.authorize(Some("key")), Ok(true));
+        assert_eq!(limiter.authorize(None), Ok(false));
+
+        assert!(limiter.admit(ip, None, false).is_ok());
+        assert!(matches!(
+            limiter.admit(ip, None, false),
+            Err(Rejection::TooManyRequests { .. })
+        ));
+
+        // Authorized clients are limited by their key instead
+        assert!(limiter.admit(ip, Some("key"), true).is_ok());
+        assert!(limiter.admit(ip, Some("key"), true).is_ok());
+        assert!(limiter.admit(ip, Some("key"), true).is_err());
+
+        assert!(limiter.acquire("prove_execute", false).is_err());
+        assert!(limiter.acquire("prove_execute", true).is_ok());
+    }
+
+    #[test]
+    fn topic_concurrency() {
+        let limiter = Limiter::new(HttpLimits {
+            topic_concurrency: HashMap::from([("gql".to_string(), 1)]),
+            ..HttpLimits::default()
+        });
+
+        let permit = limiter
+            .acquire("gql", false)
+            .expect("The first request should be admitted");
+        assert!(permit.is_some());
+        assert!(limiter.acquire("gql", false).is_err());
+
+        drop(permit);
+        assert!(limiter.acquire("gql", false).is_ok());
+        assert!(matches!(limiter.acquire("other", false), Ok(None)));
+    }
+}
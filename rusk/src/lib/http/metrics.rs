@@ -0,0 +1,34 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use hyper::header::CONTENT_TYPE;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use prometheus::{Encoder, TextEncoder};
+
+/// Path the metrics are served at.
+const METRICS_PATH: &str = "/metrics";
+
+/// Whether the request is a request for the metrics.
+pub(crate) fn is_metrics_request(req: &Request<Body>) -> bool {
+    req.method() == Method::GET && req.uri().path() == METRICS_PATH
+}
+
+/// Responds with the metrics of the default registry, in the Prometheus text
+/// format.
+pub(crate) fn response() -> Response<Body> {
+    let encoder = TextEncoder::new();
+    let mut buf = Vec::new();
+
+    match encoder.encode(&prometheus::gather(), &mut buf) {
+        Ok(()) => Response::builder()
+            .header(CONTENT_TYPE, encoder.format_type())
+            .body(Body::from(buf)),
+        Err(err) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(err.to_string())),
+    }
+    .expect("Building a metrics response should succeed")
+}
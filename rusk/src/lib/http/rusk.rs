@@ -58,6 +58,10 @@ impl Rusk {
             .map_err(|_| anyhow::anyhow!("Invalid contract bytes"))?;
 
         if feeder {
+            let contract_id = ContractId::from_bytes(contract_bytes);
+            Rusk::check_feeder_query(contract_id)
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+
             let (sender, receiver) = mpsc::channel();
 
             let rusk = self.clone();
@@ -65,12 +69,7 @@ impl Rusk {
             let arg = event.data.as_bytes().to_vec();
 
             thread::spawn(move || {
-                rusk.feeder_query_raw(
-                    ContractId::from_bytes(contract_bytes),
-                    topic,
-                    arg,
-                    sender,
-                );
+                rusk.feeder_query_raw(contract_id, topic, arg, sender);
             });
             Ok(ResponseData::new(receiver))
         } else {
@@ -4,92 +4,62 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use std::fs::File;
-use std::future::Future;
-use std::io::BufReader;
+use std::collections::HashSet;
 use std::net::SocketAddr;
-use std::path::Path;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use hyper::body::HttpBody;
-use rustls_pemfile::{certs, pkcs8_private_keys};
 use tokio::io::{self, AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
-use tokio_rustls::rustls::internal::msgs::codec::Codec;
-use tokio_rustls::rustls::pki_types::{
-    CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer,
-};
-use tokio_rustls::rustls::ServerConfig;
 use tokio_rustls::server::TlsStream;
 use tokio_rustls::TlsAcceptor;
 
+use super::tls::Tls;
+
 pub struct Listener {
-    acceptor: Option<TlsAcceptor>,
+    tls: Option<Arc<Tls>>,
     inner: TcpListener,
 }
 
 impl Listener {
     pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
         Ok(Self {
-            acceptor: None,
+            tls: None,
             inner: TcpListener::bind(addr).await?,
         })
     }
 
-    pub async fn bind_tls<A, P1, P2>(
-        addr: A,
-        cert_and_key: (P1, P2),
-    ) -> io::Result<Self>
+    pub async fn bind_tls<A>(addr: A, tls: Arc<Tls>) -> io::Result<Self>
     where
         A: ToSocketAddrs,
-        P1: AsRef<Path>,
-        P2: AsRef<Path>,
     {
-        let (cert, key) = cert_and_key;
-
-        let cert_file = File::open(cert)?;
-        let key_file = File::open(key)?;
-
-        let mut cert_reader = BufReader::new(cert_file);
-        let mut key_reader = BufReader::new(key_file);
-
-        let cert = certs(&mut cert_reader)
-            .collect::<io::Result<Vec<CertificateDer>>>()?;
-        let key = pkcs8_private_keys(&mut key_reader).next().ok_or(
-            io::Error::new(io::ErrorKind::InvalidInput, "Invalid private key"),
-        )??;
-        let key = PrivateKeyDer::Pkcs8(key);
-
-        let config = ServerConfig::builder()
-            .with_no_client_auth()
-            .with_single_cert(cert, key)
-            .map_err(|e| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("Invalid certificate/key: {e}"),
-                )
-            })?;
-
         Ok(Self {
-            acceptor: Some(TlsAcceptor::from(Arc::new(config))),
+            tls: Some(tls),
             inner: TcpListener::bind(addr).await?,
         })
     }
 
-    pub async fn accept(&self) -> io::Result<Stream> {
-        let (stream, _) = self.inner.accept().await?;
+    /// Accepts a connection. The TLS handshake, if any, is left to be
+    /// performed by the caller, so it doesn't hold up other connections.
+    pub async fn accept(&self) -> io::Result<(Handshake, SocketAddr)> {
+        let (stream, peer) = self.inner.accept().await?;
 
-        let stream = match &self.acceptor {
-            None => Stream::Raw(stream),
-            Some(acceptor) => {
-                let stream = acceptor.accept(stream).await?;
-                Stream::Tls(stream)
-            }
+        let handshake = Handshake {
+            stream,
+            acceptor: self.tls.as_ref().map(|tls| tls.acceptor()),
         };
 
-        Ok(stream)
+        Ok((handshake, peer))
+    }
+
+    /// Targets and topics that may only be requested by clients presenting a
+    /// certificate.
+    pub fn client_cert_targets(&self) -> HashSet<String> {
+        self.tls
+            .as_ref()
+            .map(|tls| tls.client_cert_targets().clone())
+            .unwrap_or_default()
     }
 
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -97,11 +67,44 @@ impl Listener {
     }
 }
 
+/// An accepted connection, yet to perform the TLS handshake.
+pub struct Handshake {
+    stream: TcpStream,
+    acceptor: Option<TlsAcceptor>,
+}
+
+impl Handshake {
+    pub async fn finish(self) -> io::Result<Stream> {
+        let stream = match self.acceptor {
+            None => Stream::Raw(self.stream),
+            Some(acceptor) => {
+                let stream = acceptor.accept(self.stream).await?;
+                Stream::Tls(stream)
+            }
+        };
+
+        Ok(stream)
+    }
+}
+
 pub enum Stream {
     Raw(TcpStream),
     Tls(TlsStream<TcpStream>),
 }
 
+impl Stream {
+    /// Returns if the client presented a certificate, which was verified
+    /// during the handshake.
+    pub fn has_client_cert(&self) -> bool {
+        match self {
+            Stream::Raw(_) => false,
+            Stream::Tls(stream) => {
+                stream.get_ref().1.peer_certificates().is_some()
+            }
+        }
+    }
+}
+
 impl AsyncRead for Stream {
     fn poll_read(
         mut self: Pin<&mut Self>,
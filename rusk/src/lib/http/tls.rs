@@ -0,0 +1,245 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use rustls_pemfile::{certs, private_key};
+use tokio::sync::broadcast;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tracing::{info, warn};
+
+/// TLS configuration of the HTTP server.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// Certificate chain of the server, in PEM format.
+    pub cert: PathBuf,
+    /// Private key of the server, in PEM format. PKCS#8, PKCS#1 (RSA) and
+    /// SEC1 (EC) keys are supported.
+    pub key: PathBuf,
+    /// Bundle of the certificate authorities client certificates are verified
+    /// with, in PEM format. Clients are not authenticated if unset.
+    pub client_ca: Option<PathBuf>,
+    /// Targets and topics that may only be requested by clients presenting a
+    /// valid certificate. If empty, and `client_ca` is set, a certificate is
+    /// required to connect at all.
+    pub client_cert_targets: HashSet<String>,
+    /// Interval at which the files are checked for changes, reloading them if
+    /// they do. They are also reloaded on `SIGHUP`.
+    pub reload_interval: Option<Duration>,
+}
+
+impl TlsConfig {
+    /// A configuration with the given certificate and key, not authenticating
+    /// clients.
+    pub fn new<P1, P2>(cert: P1, key: P2) -> Self
+    where
+        P1: AsRef<Path>,
+        P2: AsRef<Path>,
+    {
+        Self {
+            cert: cert.as_ref().into(),
+            key: key.as_ref().into(),
+            client_ca: None,
+            client_cert_targets: HashSet::new(),
+            reload_interval: None,
+        }
+    }
+
+    /// Builds the server configuration from the files.
+    fn load(&self) -> io::Result<ServerConfig> {
+        let cert = load_certs(&self.cert)?;
+
+        let mut key_reader = BufReader::new(File::open(&self.key)?);
+        let key = private_key(&mut key_reader)?.ok_or(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid private key",
+        ))?;
+
+        let builder = ServerConfig::builder();
+        let builder = match &self.client_ca {
+            None => builder.with_no_client_auth(),
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                for cert in load_certs(client_ca)? {
+                    roots.add(cert).map_err(invalid_input)?;
+                }
+
+                let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+                let verifier = match self.client_cert_targets.is_empty() {
+                    true => verifier,
+                    false => verifier.allow_unauthenticated(),
+                };
+
+                builder.with_client_cert_verifier(
+                    verifier.build().map_err(invalid_input)?,
+                )
+            }
+        };
+
+        builder.with_single_cert(cert, key).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid certificate/key: {e}"),
+            )
+        })
+    }
+
+    fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        [&self.cert, &self.key].into_iter().chain(&self.client_ca)
+    }
+}
+
+fn load_certs(path: &Path) -> io::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    certs(&mut reader).collect()
+}
+
+fn invalid_input<E: std::fmt::Display>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+}
+
+/// The TLS acceptor of the server, which may be reloaded while the server is
+/// running.
+pub(crate) struct Tls {
+    config: TlsConfig,
+    acceptor: RwLock<TlsAcceptor>,
+}
+
+impl Tls {
+    pub fn new(config: TlsConfig) -> io::Result<Self> {
+        let acceptor = TlsAcceptor::from(Arc::new(config.load()?));
+        Ok(Self {
+            config,
+            acceptor: RwLock::new(acceptor),
+        })
+    }
+
+    pub fn acceptor(&self) -> TlsAcceptor {
+        self.acceptor.read().clone()
+    }
+
+    pub fn client_cert_targets(&self) -> &HashSet<String> {
+        &self.config.client_cert_targets
+    }
+
+    /// Reloads the certificate, key and client CA bundle from their files.
+    /// Connections are accepted with the previous ones if this fails.
+    pub fn reload(&self) -> io::Result<()> {
+        let config = self.config.load()?;
+        *self.acceptor.write() = TlsAcceptor::from(Arc::new(config));
+        Ok(())
+    }
+
+    /// Reloads the files on `SIGHUP`, or when they change if a reload interval
+    /// is configured, until the server shuts down.
+    pub async fn watch(
+        self: Arc<Self>,
+        mut shutdown: broadcast::Receiver<Infallible>,
+    ) {
+        let mut interval =
+            self.config.reload_interval.map(tokio::time::interval);
+        let mut modified = self.modified();
+
+        #[cfg(unix)]
+        let mut hangup = tokio::signal::unix::signal(
+            tokio::signal::unix::SignalKind::hangup(),
+        )
+        .map_err(|err| warn!("Cannot listen for SIGHUP: {err}"))
+        .ok();
+
+        loop {
+            #[cfg(unix)]
+            let hangup = async {
+                match hangup.as_mut() {
+                    Some(hangup) => hangup.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
+            #[cfg(not(unix))]
+            let hangup = std::future::pending::<Option<()>>();
+
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = hangup => {}
+                _ = super::keepalive::tick(&mut interval) => {
+                    let current = self.modified();
+                    if current == modified {
+                        continue;
+                    }
+                    modified = current;
+                }
+            }
+
+            match self.reload() {
+                Ok(()) => info!("Reloaded TLS certificate and key"),
+                Err(err) => warn!("Failed reloading TLS certificate: {err}"),
+            }
+        }
+    }
+
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        self.config
+            .files()
+            .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_formats() {
+        for (cert, key) in [
+            ("tests/assets/cert.pem", "tests/assets/key.pem"),
+            ("tests/assets/rsa_cert.pem", "tests/assets/rsa_key.pem"),
+            ("tests/assets/ec_cert.pem", "tests/assets/ec_key.pem"),
+        ] {
+            TlsConfig::new(cert, key).load().unwrap_or_else(|e| {
+                panic!("Loading {key} should succeed: {e}")
+            });
+        }
+
+        let config = TlsConfig {
+            client_ca: Some("tests/assets/ca.pem".into()),
+            ..TlsConfig::new("tests/assets/cert.pem", "tests/assets/key.pem")
+        };
+        config.load().expect("Loading the client CA should succeed");
+    }
+
+    #[test]
+    fn reload() {
+        let dir = tempfile::tempdir().expect("Creating a dir should succeed");
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+
+        fs::copy("tests/assets/cert.pem", &cert).unwrap();
+        fs::copy("tests/assets/key.pem", &key).unwrap();
+
+        let tls = Tls::new(TlsConfig::new(&cert, &key))
+            .expect("Loading the certificate should succeed");
+
+        fs::copy("tests/assets/ec_cert.pem", &cert).unwrap();
+        fs::copy("tests/assets/ec_key.pem", &key).unwrap();
+        tls.reload().expect("Reloading should succeed");
+
+        // An invalid key is rejected, keeping the current one
+        fs::write(&key, "").unwrap();
+        tls.reload()
+            .expect_err("Reloading an invalid key should fail");
+    }
+}
@@ -96,6 +96,7 @@ pub fn generator_procedure(
         block_gas_limit,
         generator_pubkey,
         missed_generators,
+        equivocators: vec![],
     };
 
     let (transfer_txs, discarded, execute_output) =
@@ -128,7 +129,7 @@ pub fn generator_procedure(
     let verify_output = rusk.verify_state_transition(&block)?;
     info!("verify_state_transition new verification: {verify_output}",);
 
-    let (accept_txs, accept_output) = rusk.accept(&block)?;
+    let (accept_txs, accept_output, _) = rusk.accept(&block)?;
 
     assert_eq!(accept_txs.len(), expected.executed, "all txs accepted");
 
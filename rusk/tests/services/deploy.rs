@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use dusk_bytes::Serializable;
+use dusk_pki::SecretKey;
+use dusk_schnorr::Signature;
+use dusk_wallet_core::{self as wallet, Store};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rusk::{Result, Rusk};
+use rusk_abi::TRANSFER_CONTRACT;
+use tempfile::tempdir;
+use tracing::info;
+use transfer_contract_types::{Deploy, DEPLOY_FN_NAME};
+
+use crate::common::logger;
+use crate::common::state::{generator_procedure, new_state};
+use crate::common::wallet::{TestProverClient, TestStateClient, TestStore};
+
+const BLOCK_HEIGHT: u64 = 1;
+const BLOCK_GAS_LIMIT: u64 = 1_000_000_000_000;
+const GAS_LIMIT: u64 = 1_000_000_000;
+
+const SENDER_INDEX_0: u64 = 0;
+const SENDER_INDEX_1: u64 = 1;
+
+const BYTECODE: &[u8] =
+    include_bytes!("../../../target/wasm32-unknown-unknown/release/bob.wasm");
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/gas-behavior.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot)
+}
+
+fn make_transactions(
+    rusk: &Rusk,
+    wallet: &wallet::Wallet<TestStore, TestStateClient, TestProverClient>,
+) {
+    let refund_0 = wallet
+        .public_spend_key(SENDER_INDEX_0)
+        .expect("Getting a public spend key should succeed");
+    let refund_1 = wallet
+        .public_spend_key(SENDER_INDEX_1)
+        .expect("Getting a public spend key should succeed");
+
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let ssk = TestStore
+        .retrieve_ssk(SENDER_INDEX_0)
+        .expect("Retrieving a secret spend key should succeed");
+    let msg =
+        Deploy::signature_message(BYTECODE, &refund_0.to_bytes(), None, 0);
+    let signature = Signature::new(
+        &SecretKey::from(ssk.a()),
+        &mut rng,
+        rusk_abi::hash(msg),
+    );
+
+    let deploy = Deploy {
+        bytecode: BYTECODE.to_vec(),
+        owner: refund_0.to_bytes(),
+        init_args: None,
+        nonce: 0,
+        signature,
+    };
+    let contract_id = rusk_abi::gen_contract_id(&deploy.id_bytes());
+
+    // Both transactions deploy the same contract with the same nonce. The
+    // first should succeed, while the second should fail since the contract
+    // already exists.
+    let txs: Vec<_> =
+        [(SENDER_INDEX_0, &refund_0), (SENDER_INDEX_1, &refund_1)]
+            .into_iter()
+            .map(|(sender, refund)| {
+                wallet
+                    .execute(
+                        &mut rng,
+                        TRANSFER_CONTRACT.to_bytes().into(),
+                        String::from(DEPLOY_FN_NAME),
+                        deploy.clone(),
+                        sender,
+                        refund,
+                        GAS_LIMIT,
+                        1,
+                    )
+                    .expect("Making the transaction should succeed")
+            })
+            .collect();
+
+    let spent_transactions = generator_procedure(
+        rusk,
+        &txs,
+        BLOCK_HEIGHT,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        None,
+    )
+    .expect("generator procedure should succeed");
+
+    let mut spent_transactions = spent_transactions.into_iter();
+    let tx_0 = spent_transactions
+        .next()
+        .expect("There should be two spent transactions");
+    let tx_1 = spent_transactions
+        .next()
+        .expect("There should be two spent transactions");
+
+    assert!(tx_0.err.is_none(), "The first deployment should succeed");
+    assert!(tx_1.err.is_some(), "The second deployment should fail");
+    assert_eq!(
+        tx_1.gas_spent, GAS_LIMIT,
+        "Failed deployments should consume all gas"
+    );
+
+    rusk.query_raw(contract_id, "ping", Vec::new())
+        .expect("Querying the deployed contract should succeed");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn deploy_contract() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+
+    // Create a wallet
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+        TestProverClient::default(),
+    );
+
+    let original_root = rusk.state_root();
+
+    info!("Original Root: {:?}", hex::encode(original_root));
+
+    make_transactions(&rusk, &wallet);
+
+    let new_root = rusk.state_root();
+    info!("New root after the deployment: {:?}", hex::encode(new_root));
+    assert_ne!(original_root, new_root, "Root should have changed");
+
+    Ok(())
+}
@@ -4,9 +4,11 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+pub mod deploy;
 pub mod gas_behavior;
 pub mod multi_transfer;
 pub mod stake;
 pub mod transfer;
+pub mod upgrade;
 
 pub mod unspendable;
@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_pki::{PublicSpendKey, SecretKey};
+use dusk_schnorr::Signature;
+use dusk_wallet_core::{self as wallet, Store};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rusk::{Result, Rusk};
+use rusk_abi::{ContractId, TRANSFER_CONTRACT};
+use tempfile::tempdir;
+use transfer_contract_types::{
+    Deploy, ModuleId, Upgrade, UpgradeEvent, UpgradeStatus, DEPLOY_FN_NAME,
+    UPGRADE_FN_NAME, UPGRADE_TIMELOCK,
+};
+
+use crate::common::logger;
+use crate::common::state::{generator_procedure, new_state};
+use crate::common::wallet::{TestProverClient, TestStateClient, TestStore};
+
+const BLOCK_GAS_LIMIT: u64 = 1_000_000_000_000;
+const GAS_LIMIT: u64 = 1_000_000_000;
+
+const OWNER_INDEX: u64 = 0;
+const OTHER_INDEX: u64 = 1;
+
+const BYTECODE: &[u8] =
+    include_bytes!("../../../target/wasm32-unknown-unknown/release/bob.wasm");
+
+type Wallet = wallet::Wallet<TestStore, TestStateClient, TestProverClient>;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/gas-behavior.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot)
+}
+
+fn call_transfer<C>(
+    wallet: &Wallet,
+    rng: &mut StdRng,
+    sender: u64,
+    fn_name: &str,
+    fn_args: C,
+) -> wallet::Transaction
+where
+    C: rkyv::Serialize<
+        rkyv::ser::serializers::AllocSerializer<{ wallet::MAX_CALL_SIZE }>,
+    >,
+{
+    let refund = wallet
+        .public_spend_key(sender)
+        .expect("Getting a public spend key should succeed");
+
+    wallet
+        .execute(
+            rng,
+            TRANSFER_CONTRACT.to_bytes().into(),
+            String::from(fn_name),
+            fn_args,
+            sender,
+            &refund,
+            GAS_LIMIT,
+            1,
+        )
+        .expect("Making the transaction should succeed")
+}
+
+fn query_transfer<R>(rusk: &Rusk, fn_name: &str, contract: ModuleId) -> R
+where
+    R: rkyv::Archive,
+    R::Archived: rkyv::Deserialize<R, rkyv::de::deserializers::SharedDeserializeMap>
+        + for<'a> bytecheck::CheckBytes<
+            rkyv::validation::validators::DefaultValidator<'a>,
+        >,
+{
+    let arg = rkyv::to_bytes::<_, 32>(&contract)
+        .expect("Serializing the argument should succeed");
+    let data = rusk
+        .query_raw(TRANSFER_CONTRACT, fn_name, arg.to_vec())
+        .expect("Querying the transfer contract should succeed");
+
+    rkyv::from_bytes(&data).expect("Result should deserialize")
+}
+
+fn sign_upgrade(
+    ssk_index: u64,
+    contract: &ModuleId,
+    nonce: u64,
+    rng: &mut StdRng,
+) -> Signature {
+    let ssk = TestStore
+        .retrieve_ssk(ssk_index)
+        .expect("Retrieving a secret spend key should succeed");
+    let sk = SecretKey::from(ssk.a());
+
+    let msg = Upgrade::signature_message(contract, nonce, BYTECODE, None);
+    let msg = rusk_abi::hash(msg);
+
+    Signature::new(&sk, rng, msg)
+}
+
+fn sign_deploy(
+    ssk_index: u64,
+    owner: &PublicSpendKey,
+    rng: &mut StdRng,
+) -> Signature {
+    let ssk = TestStore
+        .retrieve_ssk(ssk_index)
+        .expect("Retrieving a secret spend key should succeed");
+    let sk = SecretKey::from(ssk.a());
+
+    let msg = Deploy::signature_message(BYTECODE, &owner.to_bytes(), None, 0);
+    let msg = rusk_abi::hash(msg);
+
+    Signature::new(&sk, rng, msg)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn upgrade_contract() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+
+    // Create a wallet
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+        TestProverClient::default(),
+    );
+
+    let mut rng = StdRng::seed_from_u64(0xcafe);
+
+    let owner: PublicSpendKey = wallet
+        .public_spend_key(OWNER_INDEX)
+        .expect("Getting a public spend key should succeed");
+
+    // Deploy the contract that is going to be upgraded
+    let deploy = Deploy {
+        bytecode: BYTECODE.to_vec(),
+        owner: owner.to_bytes(),
+        init_args: None,
+        nonce: 0,
+        signature: sign_deploy(OWNER_INDEX, &owner, &mut rng),
+    };
+    let contract = rusk_abi::gen_contract_id(&deploy.id_bytes()).to_bytes();
+
+    let tx =
+        call_transfer(&wallet, &mut rng, OWNER_INDEX, DEPLOY_FN_NAME, deploy);
+    let spent =
+        generator_procedure(&rusk, &[tx], 1, BLOCK_GAS_LIMIT, vec![], None)
+            .expect("generator procedure should succeed");
+    assert!(spent[0].err.is_none(), "The deployment should succeed");
+
+    let code_hash: Option<BlsScalar> =
+        query_transfer(&rusk, "code_hash", contract);
+    assert_eq!(code_hash, Some(rusk_abi::hash(BYTECODE.to_vec())));
+
+    let contract_owner: Option<[u8; PublicSpendKey::SIZE]> =
+        query_transfer(&rusk, "owner", contract);
+    assert_eq!(contract_owner, Some(owner.to_bytes()));
+
+    // Schedule the upgrade, both with the owner's signature and with a
+    // signature from someone else.
+    let upgrade = |signer| Upgrade {
+        contract,
+        bytecode: BYTECODE.to_vec(),
+        migration: None,
+        signature: sign_upgrade(
+            signer,
+            &contract,
+            0,
+            &mut StdRng::seed_from_u64(signer),
+        ),
+    };
+
+    let txs = [
+        call_transfer(
+            &wallet,
+            &mut rng,
+            OTHER_INDEX,
+            UPGRADE_FN_NAME,
+            upgrade(OTHER_INDEX),
+        ),
+        call_transfer(
+            &wallet,
+            &mut rng,
+            OWNER_INDEX,
+            UPGRADE_FN_NAME,
+            upgrade(OWNER_INDEX),
+        ),
+    ];
+    let spent =
+        generator_procedure(&rusk, &txs, 2, BLOCK_GAS_LIMIT, vec![], None)
+            .expect("generator procedure should succeed");
+    assert!(spent[0].err.is_some(), "Only the owner can upgrade");
+    assert!(
+        spent[1].err.is_none(),
+        "The owner should schedule the upgrade"
+    );
+
+    let nonce: u64 = query_transfer(&rusk, "upgrade_nonce", contract);
+    assert_eq!(nonce, 1, "Scheduling should increment the nonce");
+
+    let activation_height = 2 + UPGRADE_TIMELOCK;
+
+    let events: Vec<UpgradeEvent> =
+        query_transfer(&rusk, "upgrade_events", contract);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].status, UpgradeStatus::Scheduled);
+    assert_eq!(events[0].height, activation_height);
+
+    // The upgrade is applied at the start of the block at the activation
+    // height.
+    generator_procedure(
+        &rusk,
+        &[],
+        activation_height,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        None,
+    )
+    .expect("generator procedure should succeed");
+
+    let events: Vec<UpgradeEvent> =
+        query_transfer(&rusk, "upgrade_events", contract);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].status, UpgradeStatus::Applied);
+    assert_eq!(events[1].height, activation_height);
+
+    rusk.query_raw(ContractId::from_bytes(contract), "ping", Vec::new())
+        .expect("Querying the upgraded contract should succeed");
+
+    Ok(())
+}